@@ -1,122 +1,1284 @@
+use crate::archaeologist::Archaeologist;
+use crate::archaeologist_panel::ArchaeologistPanel;
+use crate::attraction::{self, Attraction, ATTRACTION_DIE};
+use crate::attractions_panel::AttractionsPanel;
+use crate::battle_report;
+use crate::card::FlipCard;
+#[cfg(feature = "scryfall")]
+use crate::card_of_the_day::{CardOfTheDay, DailyCard};
+use crate::cast::{
+    Board, CastError, CastOutcome, CostReducer, Ritual, Spell, Tokens, AETHERFLUX_COST,
+    BARBARIAN_CLASS_ABILITIES, BRAIN_FREEZE, BRAIN_FREEZE_MILL, EMPTY_THE_WARRENS, PUZZLEBOX_GOAL,
+    WARRENS_GOBLINS,
+};
+use crate::celebration::Celebration;
+#[cfg(feature = "networking")]
+use crate::chat::{self, Chat};
+use crate::cleanup;
 use crate::coin_flip::*;
+use crate::coin_skin::outcome_icon;
+use crate::combat::{CombatEffect, Role};
+use crate::command_palette::CommandPalette;
+use crate::control_panel::ControlPanel;
+use crate::count_input::CountInput;
+use crate::counter::Counter;
+use crate::creature::{Creature, PumpTrigger, WYLL};
+use crate::creature_list::CreatureList;
+use crate::custom_card::{CardDefinition, CustomCard};
+use crate::custom_card_list::CustomCardList;
+use crate::d20_card::{D20Card, D20Modes};
+use crate::day_night::DayNight;
+use crate::deck::Decklist;
+use crate::deck_import::DeckImport;
+use crate::dice::{celebr_8000, d20_band, DiceExpr, Roll, CELEBR_8000_BONUSES};
+use crate::dice_roller::DiceRoller;
+use crate::draw_odds::DrawOdds;
+use crate::effect::{okaun_size, CardEffect, Note};
+use crate::events::{EngineEvent, EventBus};
+use crate::export;
+use crate::flip_log::FlipLog;
+use crate::game::{Game, TableDesignation, TrackedCard, UpkeepFlip};
+use crate::haptics;
+use crate::history::{self, FlipRecord, RecordStream, StatsAccumulator};
+use crate::i18n::Language;
+use crate::mana_pool::ManaPool;
+use crate::modal::Modal;
+use crate::mulligan_trainer::MulliganTrainer;
+use crate::name_form::NameForm;
+use crate::neq_assign::NeqAssign;
+use crate::opponent::{
+    self, Designation, Opponent, PlayerCounter, PlayerCounters, StormPayoff, Wheel, OPENING_HAND,
+};
+use crate::opponent_row::OpponentRow;
+use crate::pace::Pace;
+use crate::packed::Packed;
+use crate::perf;
+use crate::persisted::{PersistedState, StateError};
+use crate::probability::ChainOdds;
+use crate::qr_image::QrImage;
+use crate::records::{RecordKind, Records};
+use crate::records_table::RecordsTable;
+use crate::reducer_list::ReducerList;
+use crate::resolution_log::ResolutionLog;
+use crate::risky_move::RiskyMove;
+use crate::risky_move_panel::RiskyMovePanel;
+use crate::run_lengths::RunLengths;
+#[cfg(feature = "scripting")]
+use crate::script::Script;
+use crate::script_editor::ScriptEditor;
+use crate::seating;
+use crate::seed_explorer::SeedExplorer;
+use crate::seed_input::SeedInput;
+use crate::session::{SessionExport, SessionSummary};
+use crate::session_dashboard::SessionDashboard;
+use crate::session_import::{Import, SessionImport};
+use crate::settings::{Panel, Settings};
+use crate::settings_panel::SettingsPanel;
+use crate::simulator::Simulator;
+use crate::snapshot;
+use crate::spectate;
+use crate::speech;
+use crate::spell_table::SpellTable;
+use crate::stats_panel::StatsPanel;
+use crate::stats_tables::StatsTables;
+use crate::token_list::TokenList;
+use crate::tour::Tour;
+use crate::tracked_cards::TrackedCards;
+use crate::trigger::{CastChain, Resolution, StepCast, StepLine, MAX_RECASTS};
+use crate::turn_timer::TurnTimer;
+use crate::unfinity::Unfinity;
+use crate::unfinity_panel::UnfinityPanel;
+use crate::upkeep_flips::UpkeepFlips;
+use crate::voice;
+use crate::voice::{Listener, VoiceCommand};
 use log::*;
-use rand::thread_rng;
-use serde_derive::{Deserialize, Serialize};
-use strum_macros::{EnumIter, ToString};
-use yew::format::Json;
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use yew::format::{Json, Text};
 use yew::prelude::*;
+use yew::services::keyboard::{KeyListenerHandle, KeyboardService};
 use yew::services::storage::{Area, StorageService};
 
-const KEY: &str = "yew.todomvc.self";
+/// Where the template this app grew out of kept its todo entries.
+const TODO_KEY: &str = "yew.todomvc.self";
+/// Where sessions were saved before they moved into the persisted state.
+const SESSIONS_KEY: &str = "krarkinator.sessions";
+const STATE_KEY: &str = "krarkinator.state";
+/// Where a stored state that could not be read is kept, since saving
+/// replaces it.
+const STATE_BACKUP_KEY: &str = "krarkinator.state.unreadable";
+const SETTINGS_KEY: &str = "krarkinator.settings";
+const UNFINITY_KEY: &str = "krarkinator.unfinity";
+const CUSTOM_CARDS_KEY: &str = "krarkinator.custom_cards";
+const RECORDS_KEY: &str = "krarkinator.records";
+const DECKLIST_KEY: &str = "krarkinator.decklist";
+/// Cards left in your library at which drawing more gets a warning.
+const LOW_LIBRARY: u32 = 5;
+
+/// Win streaks are announced each time they reach a multiple of this.
+const STREAK_MILESTONE: usize = 5;
+/// Upper bound on flips for cards that flip until they lose.
+const MAX_CHAIN: usize = 1000;
+/// Earlier states of the active game kept for undo.
+const UNDO_LIMIT: usize = 20;
+/// The history may outgrow its cap by one part in this many before it is
+/// pruned, so archives come in batches rather than one file per flip.
+const PRUNE_SLACK: usize = 4;
+/// Brainiacs tapped to make The Big Idea roll two dice.
+const BIG_IDEA_BRAINIACS: u32 = 3;
 
 pub struct App {
     link: ComponentLink<Self>,
     storage: StorageService,
-    state: State,
+    /// Why the stored state could not be loaded, if it could not.
+    state_error: Option<StateError>,
+    /// Every flip and roll of the game in front, seeded from its seed.
+    /// Each game's position in it is kept with the game. ChaCha12Rng
+    /// rather than StdRng because only it can report and seek to a
+    /// position, with `get_word_pos` and `set_word_pos`. In rand 0.8 StdRng
+    /// is ChaCha12 too, so a seed gives the flips it always has.
+    rng: ChaCha12Rng,
+    /// The game being shown.
+    game: Game,
+    /// Every open game, in tab order. The active one's slot is empty while
+    /// it is swapped out into `game`.
+    games: Vec<Game>,
+    active_game: usize,
+    /// Whether `game` came from a snapshot link and is shown read-only.
+    spectating: bool,
+    /// The active game as it was before each of the latest flips, rolls,
+    /// casts and turn ends, newest last.
+    undo: Vec<Game>,
+    /// Voice commands, while the microphone is on.
+    voice: Option<Listener>,
+    /// Outcomes of the flips made so far by a `FlipMany`, buzzed once it
+    /// is done rather than flip by flip.
+    batch_outcomes: Option<Vec<bool>>,
+    /// Streak milestones reached so far by a `FlipMany`, announced along
+    /// with its totals.
+    batch_streaks: Vec<usize>,
+    /// Streak celebrations so far, and the streak that set off the last.
+    celebrations: usize,
+    celebrated_streak: usize,
+    /// Upkeep flips not made, while asking whether to end the turn
+    /// anyway.
+    upkeep_warning: Option<Vec<String>>,
+    /// Link to a snapshot of the active game, once asked for.
+    snapshot_link: Option<String>,
+    /// A link to the same snapshot short enough for a QR code.
+    snapshot_qr_link: Option<String>,
+    /// Totals over `history`, kept in step with it by subscribing to
+    /// `events`.
+    stats: Rc<RefCell<StatsAccumulator>>,
+    events: EventBus,
+    flip_source: FlipCard,
+    selected_chain: Option<usize>,
+    sessions: Vec<SessionSummary>,
+    settings: Settings,
+    announcement: String,
+    touring: bool,
+    settings_open: bool,
+    palette_open: bool,
+    _key_listener: KeyListenerHandle,
+    storm_payoff: StormPayoff,
+    ritual_x: String,
+    custom_cards: Vec<CustomCard>,
+    /// Personal bests over every session, kept when sessions are pruned.
+    records: Records,
+    /// Every coin behind your latest flips, to show what Krark's Thumb
+    /// discarded, and the side that won them.
+    tosses: Vec<Toss>,
+    toss_call: Call,
+    /// The cast being resolved one trigger at a time, if any.
+    step_cast: Option<StepCast>,
+    /// Coins tossed for its current trigger, while one must be kept.
+    step_pending: Option<Vec<Call>>,
+    step_lines: Vec<StepLine>,
+    /// The imported decklist, for the mulligan trainer.
+    decklist: Option<Decklist>,
+    /// The compiled script, when scripts are on and it compiles.
+    #[cfg(feature = "scripting")]
+    script: Option<Script>,
+    script_error: Option<String>,
+    unfinity: Unfinity,
+    /// Whether Brainiacs were tapped so The Big Idea's next roll uses two
+    /// dice.
+    big_idea_boosted: bool,
+    /// Whether rolling for first player also shuffles the seating.
+    seat_randomly: bool,
+    /// Band of the last "roll a d20" roll.
+    d20_band: Option<usize>,
+    callbacks: Callbacks,
+}
+
+/// Callbacks handed to child components, made once so that they compare
+/// equal from one render to the next and a child only renders again when
+/// its data changes.
+struct Callbacks {
+    #[cfg(feature = "scryfall")]
+    track_card: Callback<DailyCard>,
+    flip_many: Callback<usize>,
+    end_turn: Callback<()>,
+    undo: Callback<()>,
+    toggle_voice: Callback<()>,
+    save_session: Callback<()>,
+    clear_history: Callback<()>,
+    toggle_settings: Callback<()>,
+    roll: Callback<DiceExpr>,
+    finish_tour: Callback<()>,
+    cycle_day_night: Callback<()>,
+    select_chain: Callback<usize>,
+    import: Callback<Import>,
+    step_flip: Callback<()>,
+    keep_coin: Callback<Call>,
+    clear_resolution_log: Callback<()>,
+    run_command: Callback<usize>,
+    close_palette: Callback<()>,
+    change_settings: Callback<Settings>,
+    start_tour: Callback<()>,
+    cancel_end_turn: Callback<()>,
+    close_chain: Callback<()>,
+    add_opponent: Callback<String>,
+    set_tokens: Callback<Vec<Tokens>>,
+    create_tokens: Callback<(String, bool)>,
+    set_creatures: Callback<Vec<Creature>>,
+    set_mana: Callback<u32>,
+    set_infinite_mana: Callback<Option<String>>,
+    set_reducers: Callback<Vec<CostReducer>>,
+    set_spells: Callback<Vec<Spell>>,
+    cast: Callback<usize>,
+    cast_until: Callback<usize>,
+    set_custom_cards: Callback<Vec<CustomCard>>,
+    flip_custom_card: Callback<usize>,
+    import_deck: Callback<Decklist>,
+    roll_d20: Callback<D20Modes>,
+    toggle_scripts: Callback<()>,
+    load_script: Callback<String>,
+    remove_session: Callback<usize>,
+    set_attractions: Callback<Vec<Attraction>>,
+    roll_to_visit: Callback<()>,
+    set_archaeologist: Callback<Archaeologist>,
+    activate_archaeologist: Callback<()>,
+    set_unfinity: Callback<Unfinity>,
+    set_risky_move: Callback<Option<RiskyMove>>,
+    set_krarks: Callback<usize>,
+    set_thumbs: Callback<usize>,
+    set_seed: Callback<u64>,
+    set_tracked_cards: Callback<Vec<TrackedCard>>,
+    set_upkeep_flips: Callback<Vec<UpkeepFlip>>,
+    set_opponent: Callback<(usize, Opponent)>,
+    set_target: Callback<usize>,
+    remove_opponent: Callback<usize>,
+    risky_move_upkeep: Callback<()>,
+    flip_risky_move: Callback<(String, Option<usize>)>,
 }
 
-#[derive(Serialize, Deserialize)]
-pub struct State {
-    entries: Vec<Entry>,
-    filter: Filter,
-    value: String,
-    edit_value: String,
+impl Callbacks {
+    fn new(link: &ComponentLink<App>) -> Self {
+        Callbacks {
+            #[cfg(feature = "scryfall")]
+            track_card: link.callback(Msg::TrackCard),
+            flip_many: link.callback(Msg::FlipMany),
+            end_turn: link.callback(|_| Msg::EndTurn),
+            undo: link.callback(|_| Msg::Undo),
+            toggle_voice: link.callback(|_| Msg::ToggleVoice),
+            save_session: link.callback(|_| Msg::SaveSession),
+            clear_history: link.callback(|_| Msg::ClearHistory),
+            toggle_settings: link.callback(|_| Msg::ToggleSettings),
+            roll: link.callback(Msg::RollExpression),
+            finish_tour: link.callback(|_| Msg::FinishTour),
+            cycle_day_night: link.callback(|_| Msg::CycleDayNight),
+            select_chain: link.callback(Msg::SelectChain),
+            import: link.callback(Msg::Import),
+            step_flip: link.callback(|_| Msg::StepFlip),
+            keep_coin: link.callback(Msg::KeepCoin),
+            clear_resolution_log: link.callback(|_| Msg::ClearResolutionLog),
+            run_command: link.callback(Msg::RunCommand),
+            close_palette: link.callback(|_| Msg::ClosePalette),
+            change_settings: link.callback(|settings| Msg::ChangeSettings(Box::new(settings))),
+            start_tour: link.callback(|_| Msg::StartTour),
+            cancel_end_turn: link.callback(|_| Msg::CancelEndTurn),
+            close_chain: link.callback(|_| Msg::CloseChain),
+            add_opponent: link.callback(Msg::AddOpponent),
+            set_tokens: link.callback(Msg::SetTokens),
+            create_tokens: link.callback(|(name, temporary)| Msg::CreateTokens(name, temporary)),
+            set_creatures: link.callback(Msg::SetCreatures),
+            set_mana: link.callback(Msg::SetMana),
+            set_infinite_mana: link.callback(Msg::SetInfiniteMana),
+            set_reducers: link.callback(Msg::SetReducers),
+            set_spells: link.callback(Msg::SetSpells),
+            cast: link.callback(Msg::Cast),
+            cast_until: link.callback(Msg::CastSpell),
+            set_custom_cards: link.callback(Msg::SetCustomCards),
+            flip_custom_card: link.callback(Msg::FlipCustomCard),
+            import_deck: link.callback(Msg::ImportDeck),
+            roll_d20: link.callback(Msg::RollD20),
+            toggle_scripts: link.callback(|_| Msg::ToggleScripts),
+            load_script: link.callback(Msg::LoadScript),
+            remove_session: link.callback(Msg::RemoveSession),
+            set_attractions: link.callback(Msg::SetAttractions),
+            roll_to_visit: link.callback(|_| Msg::RollToVisit),
+            set_archaeologist: link.callback(Msg::SetArchaeologist),
+            activate_archaeologist: link.callback(|_| Msg::ActivateArchaeologist),
+            set_unfinity: link.callback(Msg::SetUnfinity),
+            set_risky_move: link.callback(Msg::SetRiskyMove),
+            set_krarks: link.callback(Msg::SetKrarks),
+            set_thumbs: link.callback(Msg::SetThumbs),
+            set_seed: link.callback(Msg::SetSeed),
+            set_tracked_cards: link.callback(Msg::SetTrackedCards),
+            set_upkeep_flips: link.callback(Msg::SetUpkeepFlips),
+            set_opponent: link.callback(|(idx, opponent)| Msg::SetOpponent(idx, opponent)),
+            set_target: link.callback(Msg::SetTarget),
+            remove_opponent: link.callback(Msg::RemoveOpponent),
+            risky_move_upkeep: link.callback(|_| Msg::RiskyMoveUpkeep),
+            flip_risky_move: link
+                .callback(|(creature, recipient)| Msg::FlipRiskyMove(creature, recipient)),
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize)]
-struct Entry {
-    description: String,
-    completed: bool,
-    editing: bool,
+/// Actions offered by the command palette.
+#[derive(Clone, Copy)]
+enum Command {
+    Flip,
+    Cast(usize),
+    CastRitual(Ritual),
+    BigIdea,
+    EndTurn,
+    SaveSession,
+    ClearHistory,
+    OpenSettings,
+    StartTour,
 }
 
 pub enum Msg {
-    Add,
-    Edit(usize),
-    Update(String),
-    UpdateEdit(String),
-    Remove(usize),
-    SetFilter(Filter),
-    ToggleAll,
-    ToggleEdit(usize),
-    Toggle(usize),
-    ClearCompleted,
+    NewGame,
+    SwitchGame(usize),
+    CloseGame(usize),
+    SetHolder(TableDesignation, Option<usize>),
+    CycleDayNight,
+    #[cfg(feature = "scryfall")]
+    TrackCard(DailyCard),
+    SetTrackedCards(Vec<TrackedCard>),
+    ShareSnapshot,
+    StopSpectating,
+    Flip,
+    /// Flips this many times as one step.
+    FlipMany(usize),
+    /// Flips for the next Krark trigger of a cast resolved by hand.
+    StepFlip,
+    /// Keeps a coin that landed this way for the trigger being resolved.
+    KeepCoin(Call),
+    ClearResolutionLog,
+    SetFlipSource(FlipCard),
+    ClearHistory,
+    SelectChain(usize),
+    CloseChain,
+    SetKrarks(usize),
+    SetThumbs(usize),
+    SetSeed(u64),
+    SetPayoffs(String),
+    SetLife(String),
+    AdjustLife(i32),
+    ToggleAetherflux,
+    ToggleOkaun,
+    ExportBattleReport,
+    ExportSessionCsv,
+    ExportSessionJson,
+    Import(Import),
+    TakeSnapshot,
+    FireAetherflux,
+    AddOpponent(String),
+    RemoveOpponent(usize),
+    SetOpponent(usize, Opponent),
+    SetStormPayoff(StormPayoff),
+    SetTarget(usize),
+    /// Changes a hand size: yours for `None`, else that opponent's.
+    AdjustHand(Option<usize>, i32),
+    AdjustCounter(Option<usize>, PlayerCounter, i32),
+    ToggleDesignation(Option<usize>, Designation),
+    Wheel(Wheel),
+    RollExpression(DiceExpr),
+    RollD20(D20Modes),
+    RollCelebr8000,
+    TogglePuzzlebox,
+    FetchWithPuzzlebox,
+    ToggleWyll,
+    SetBarbarianClass(Option<u32>),
+    SetAttractions(Vec<Attraction>),
+    RollToVisit,
+    SetArchaeologist(Archaeologist),
+    ActivateArchaeologist,
+    SetCustomCards(Vec<CustomCard>),
+    ImportDeck(Decklist),
+    FlipCustomCard(usize),
+    LoadScript(String),
+    ToggleScripts,
+    ToggleRandomSeating,
+    RollForFirst,
+    SetUnfinity(Unfinity),
+    SetDrawPingers(String),
+    Draw,
+    SetLibrary(String),
+    ApplyPings,
+    SetCreatures(Vec<Creature>),
+    SetTokens(Vec<Tokens>),
+    /// Creates a token by name, lasting only until end of turn if asked.
+    CreateTokens(String, bool),
+    BoostBigIdea,
+    BigIdea,
+    SetMana(u32),
+    /// Declares an infinite mana loop with its note, or ends it.
+    SetInfiniteMana(Option<String>),
+    SetReducers(Vec<CostReducer>),
+    SetSpells(Vec<Spell>),
+    Cast(usize),
+    /// Casts a spell, then again each time Krark returns it while the
+    /// pool can pay.
+    CastSpell(usize),
+    UpdateRitualX(String),
+    CastRitual(Ritual),
+    EndTurn,
+    SaveSession,
+    RemoveSession(usize),
+    Undo,
+    ToggleVoice,
+    Heard(String),
+    VoiceEnded,
+    ResetTimings,
+    ChangeSettings(Box<Settings>),
+    StartTour,
+    FinishTour,
+    ToggleSettings,
+    SetCombatPlayer(Role, usize),
+    SetRiskyMove(Option<RiskyMove>),
+    RiskyMoveUpkeep,
+    /// Makes the Risky Move flip that is due, staking the named creature
+    /// on the picked opponent.
+    FlipRiskyMove(String, Option<usize>),
+    SetUpkeepFlips(Vec<UpkeepFlip>),
+    EndTurnAnyway,
+    CancelEndTurn,
+    GlobalKey(KeyboardEvent),
+    ClosePalette,
+    RunCommand(usize),
     Nope,
 }
 
+impl Msg {
+    /// Whether handling the message announces a flip, roll or cast result.
+    fn has_result(&self) -> bool {
+        matches!(
+            self,
+            Msg::Flip
+                | Msg::FlipMany(_)
+                | Msg::StepFlip
+                | Msg::KeepCoin(_)
+                | Msg::FlipRiskyMove(..)
+                | Msg::ActivateArchaeologist
+                | Msg::RollExpression(_)
+                | Msg::RollD20(_)
+                | Msg::RollCelebr8000
+                | Msg::RollToVisit
+                | Msg::BigIdea
+                | Msg::Cast(_)
+                | Msg::CastSpell(_)
+                | Msg::CastRitual(_)
+        )
+    }
+}
+
 impl Component for App {
     type Message = Msg;
     type Properties = ();
 
     fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
-        let storage = StorageService::new(Area::Local).unwrap();
-        let entries = {
-            if let Json(Ok(restored_entries)) = storage.restore(KEY) {
-                restored_entries
+        let mut storage = StorageService::new(Area::Local).unwrap();
+        let (mut state, state_error) = restore_state(&mut storage);
+        let mut settings = {
+            if let Json(Ok(restored_settings)) = storage.restore(SETTINGS_KEY) {
+                restored_settings
+            } else {
+                Settings::default()
+            }
+        };
+        open_chat_if_joining(&mut settings);
+        let unfinity = {
+            if let Json(Ok(restored_unfinity)) = storage.restore(UNFINITY_KEY) {
+                restored_unfinity
+            } else {
+                Unfinity::default()
+            }
+        };
+        let custom_cards = {
+            if let Json(Ok(restored_cards)) =
+                storage.restore::<Json<Result<Vec<CardDefinition>, _>>>(CUSTOM_CARDS_KEY)
+            {
+                restored_cards
+                    .into_iter()
+                    .filter_map(|card| CustomCard::new(card).ok())
+                    .collect()
             } else {
                 Vec::new()
             }
         };
-        let state = State {
-            entries,
-            filter: Filter::All,
-            value: "".into(),
-            edit_value: "".into(),
+        let decklist = {
+            if let Json(Ok(restored_deck)) = storage.restore(DECKLIST_KEY) {
+                Some(restored_deck)
+            } else {
+                None
+            }
+        };
+        let records = {
+            if let Json(Ok(restored_records)) = storage.restore(RECORDS_KEY) {
+                restored_records
+            } else {
+                Records::default()
+            }
+        };
+        settings.apply_font_scale();
+        settings.theme.apply();
+        let touring = !settings.tour_seen;
+        let key_listener = KeyboardService::register_key_down(
+            &web_sys::window().unwrap(),
+            link.callback(Msg::GlobalKey),
+        );
+        let spectated = spectate::from_location();
+        let spectating = spectated.is_some();
+        let (game, games, active_game) = match spectated {
+            Some(game) => (game, vec![Game::default()], 0),
+            None => {
+                let mut games = std::mem::take(&mut state.games);
+                // Games brought over from older saves have no name yet.
+                for (idx, game) in games.iter_mut().enumerate() {
+                    if game.name.is_empty() {
+                        game.name = settings.language.fmt("game_name", &[&(idx + 1)]);
+                    }
+                }
+                let game = std::mem::take(&mut games[state.active_game]);
+                (game, games, state.active_game)
+            }
         };
-        App {
+        let stats = Rc::new(RefCell::new(StatsAccumulator::new(&game.history)));
+        let mut events = EventBus::default();
+        events.subscribe(Box::new(stats.clone()));
+        let callbacks = Callbacks::new(&link);
+        let mut app = App {
             link,
             storage,
-            state,
-        }
+            state_error,
+            rng: ChaCha12Rng::from_entropy(),
+            game,
+            games,
+            active_game,
+            spectating,
+            undo: Vec::new(),
+            voice: None,
+            batch_outcomes: None,
+            batch_streaks: Vec::new(),
+            celebrations: 0,
+            celebrated_streak: 0,
+            upkeep_warning: None,
+            snapshot_link: None,
+            snapshot_qr_link: None,
+            stats,
+            events,
+            flip_source: FlipCard::Krark,
+            selected_chain: None,
+            sessions: state.sessions,
+            settings,
+            announcement: "".into(),
+            touring,
+            settings_open: false,
+            palette_open: false,
+            _key_listener: key_listener,
+            storm_payoff: StormPayoff::Grapeshot,
+            ritual_x: "".into(),
+            custom_cards,
+            records,
+            tosses: Vec::new(),
+            toss_call: Call::Heads,
+            step_cast: None,
+            step_pending: None,
+            step_lines: Vec::new(),
+            decklist,
+            #[cfg(feature = "scripting")]
+            script: None,
+            script_error: None,
+            unfinity,
+            big_idea_boosted: false,
+            seat_randomly: false,
+            d20_band: None,
+            callbacks,
+        };
+        app.compile_script();
+        app.resume_rng();
+        app
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let _span = perf::span("update");
+        let speak = self.settings.speak_results && msg.has_result();
+        let persist = msg.has_result();
+        if msg.has_result() || matches!(msg, Msg::EndTurn | Msg::EndTurnAnyway) {
+            self.undo.push(self.game.clone());
+            let excess = self.undo.len().saturating_sub(UNDO_LIMIT);
+            self.undo.drain(..excess);
+        }
         match msg {
-            Msg::Add => {
-                let entry = Entry {
-                    description: self.state.value.clone(),
-                    completed: false,
-                    editing: false,
+            Msg::Flip => self.flip(),
+            Msg::FlipMany(0) => {
+                // Nothing changed, so there is nothing to undo.
+                self.undo.pop();
+                return false;
+            }
+            Msg::FlipMany(times) => self.flip_many(times),
+            Msg::StepFlip => {
+                // A coin is waiting to be kept, or there is no Krark to
+                // trigger. Nothing changed, so there is nothing to undo.
+                if self.step_pending.is_some() || self.game.board.krarks == 0 {
+                    self.undo.pop();
+                    return false;
+                }
+                if self.step_cast.as_ref().is_none_or(StepCast::done) {
+                    self.step_cast = Some(StepCast::new(self.game.board.krarks));
+                }
+                let coins = FlipConfig::new()
+                    .thumbs(self.game.board.thumbs)
+                    .flips(1)
+                    .toss(&mut self.rng)
+                    .remove(0)
+                    .coins;
+                if coins.iter().all(|&coin| coin == coins[0]) {
+                    self.resolve_step(coins[0], coins);
+                } else {
+                    self.step_pending = Some(coins);
+                }
+            }
+            Msg::KeepCoin(kept) => match self.step_pending.take() {
+                Some(coins) => self.resolve_step(kept, coins),
+                None => return false,
+            },
+            Msg::ClearResolutionLog => {
+                self.step_lines.clear();
+            }
+            Msg::NewGame => {
+                let number = self.games.len() + 1;
+                let name = self.settings.language.fmt("game_name", &[&number]);
+                self.games.push(Game::new(name));
+                self.switch_game(self.games.len() - 1);
+                self.reseed(None);
+                self.save_state();
+            }
+            Msg::ShareSnapshot => {
+                self.snapshot_link = spectate::link(&self.game, spectate::HISTORY_LIMIT);
+                self.snapshot_qr_link = spectate::qr_link(&self.game);
+            }
+            Msg::StopSpectating => {
+                spectate::leave();
+                self.spectating = false;
+                self.game = Game::new(self.settings.language.fmt("game_name", &[&1]));
+                *self.stats.borrow_mut() = StatsAccumulator::default();
+                self.selected_chain = None;
+            }
+            Msg::SwitchGame(idx) => {
+                if idx == self.active_game {
+                    return false;
+                }
+                self.switch_game(idx);
+            }
+            Msg::CloseGame(idx) => {
+                if self.games.len() < 2 {
+                    return false;
+                }
+                if idx == self.active_game {
+                    self.switch_game(if idx == 0 { 1 } else { idx - 1 });
+                }
+                self.games.remove(idx);
+                if self.active_game > idx {
+                    self.active_game -= 1;
+                }
+                self.save_state();
+            }
+            Msg::SetFlipSource(source) => {
+                return self.flip_source.neq_assign(source);
+            }
+            Msg::ClearHistory => {
+                self.reset_session();
+            }
+            Msg::SelectChain(activation) => {
+                return self.selected_chain.neq_assign(Some(activation));
+            }
+            Msg::CloseChain => {
+                self.selected_chain = None;
+            }
+            Msg::SetKrarks(krarks) => {
+                if !self.game.board.krarks.neq_assign(krarks) {
+                    return false;
+                }
+                self.save_state();
+            }
+            Msg::SetThumbs(thumbs) => {
+                if !self.game.board.thumbs.neq_assign(thumbs) {
+                    return false;
+                }
+                self.save_state();
+            }
+            Msg::SetSeed(seed) => self.reseed(Some(seed)),
+            Msg::SetPayoffs(val) => {
+                return val
+                    .parse()
+                    .is_ok_and(|payoffs| self.game.board.payoffs.neq_assign(payoffs));
+            }
+            Msg::SetLife(val) => {
+                let changed = val
+                    .parse()
+                    .is_ok_and(|life| self.game.board.life.neq_assign(life));
+                if changed {
+                    self.save_state();
+                }
+                return changed;
+            }
+            Msg::AdjustLife(delta) => {
+                self.game.board.life += delta;
+                self.save_state();
+            }
+            Msg::ExportSessionCsv => {
+                let csv = SessionExport::new(&self.game, self.session_summary()).to_csv();
+                if let Err(err) = export::download("krarkinator-session.csv", "text/csv", &csv) {
+                    error!("session export failed: {:?}", err);
+                }
+                return false;
+            }
+            Msg::ExportSessionJson => {
+                let text: Text =
+                    Json(&SessionExport::new(&self.game, self.session_summary())).into();
+                let result = text.map_err(|err| err.to_string().into()).and_then(|json| {
+                    export::download("krarkinator-session.json", "application/json", &json)
+                });
+                if let Err(err) = result {
+                    error!("session export failed: {:?}", err);
+                }
+                return false;
+            }
+            Msg::Import(import) => {
+                match import {
+                    Import::Session(session) => {
+                        self.reset_session();
+                        session.restore(&mut self.game);
+                        *self.stats.borrow_mut() = StatsAccumulator::new(&self.game.history);
+                    }
+                    Import::Setup(state) => {
+                        self.undo.clear();
+                        if let Some(game) = state.active() {
+                            let name = std::mem::take(&mut self.game.name);
+                            self.game = Game {
+                                name,
+                                ..game.clone()
+                            };
+                        }
+                        *self.stats.borrow_mut() = StatsAccumulator::new(&self.game.history);
+                        self.selected_chain = None;
+                    }
+                }
+                self.save_state();
+                self.announcement = self.settings.language.t("announce_imported").into();
+            }
+            Msg::ExportBattleReport => {
+                let report =
+                    battle_report::render(&self.game, self.settings.language, js_sys::Date::now());
+                if let Err(err) = export::download("battle-report.md", "text/markdown", &report) {
+                    error!("battle report export failed: {:?}", err);
+                }
+                return false;
+            }
+            Msg::TakeSnapshot => {
+                let snapshot = snapshot::render(&self.game, self.settings.language);
+                let (width, height) = (snapshot.width, snapshot.height);
+                if let Err(err) = export::rasterize(&snapshot.svg, width, height, "board-snapshot")
+                {
+                    error!("board snapshot failed: {:?}", err);
+                }
+                return false;
+            }
+            Msg::ToggleOkaun => {
+                self.game.board.okaun = match self.game.board.okaun {
+                    Some(_) => None,
+                    None => Some(0),
+                };
+            }
+            Msg::ToggleAetherflux => {
+                self.game.board.aetherflux = !self.game.board.aetherflux;
+            }
+            Msg::FireAetherflux => {
+                if self.game.board.aetherflux_ready() {
+                    self.game.board.life -= AETHERFLUX_COST;
+                }
+            }
+            Msg::AddOpponent(name) => {
+                self.game.opponents.push(Opponent::new(name));
+                self.save_state();
+            }
+            Msg::RemoveOpponent(idx) => {
+                self.game.remove_opponent(idx);
+                self.save_state();
+            }
+            #[cfg(feature = "scryfall")]
+            Msg::TrackCard(card) => {
+                if self.game.tracked_cards.iter().any(|t| t.name == card.name) {
+                    return false;
+                }
+                self.game.tracked_cards.push(TrackedCard {
+                    name: card.name,
+                    text: card.oracle_text,
+                    active: true,
+                });
+            }
+            Msg::SetTrackedCards(cards) => {
+                self.game.tracked_cards = cards;
+            }
+            Msg::SetCombatPlayer(role, player) => {
+                self.game.combat.set_player(role, player);
+            }
+            Msg::SetRiskyMove(risky_move) => {
+                self.game.risky_move = risky_move;
+            }
+            Msg::RiskyMoveUpkeep => {
+                let players = self.game.opponents.len() + 1;
+                let taken = match &mut self.game.risky_move {
+                    Some(risky_move) => risky_move.next_upkeep(players),
+                    None => false,
+                };
+                if let (true, Some(risky_move)) = (taken, &self.game.risky_move) {
+                    let name = self.player_name(risky_move.controller);
+                    let event = self.settings.language.fmt("risky_move_taken", &[&name]);
+                    self.announcement = event.clone();
+                    self.game.table_log.push(event);
+                }
+            }
+            Msg::FlipRiskyMove(creature, recipient) => self.flip_risky_move(creature, recipient),
+            Msg::CycleDayNight => {
+                self.game.day_night = DayNight::cycle(self.game.day_night);
+                self.save_state();
+            }
+            Msg::SetHolder(designation, player) => {
+                if self.game.holder(designation) == player {
+                    return false;
+                }
+                self.game.set_holder(designation, player);
+                if let (TableDesignation::Initiative, Some(player)) = (designation, player) {
+                    let lang = self.settings.language;
+                    let name = self.player_name(player);
+                    let event = lang.fmt("initiative_taken", &[&name]);
+                    self.game.table_log.push(event);
+                }
+                self.save_state();
+            }
+            Msg::ToggleRandomSeating => {
+                self.seat_randomly = !self.seat_randomly;
+            }
+            Msg::RollForFirst => self.roll_for_first(),
+            Msg::SetTarget(idx) => {
+                return self.game.target.neq_assign(Some(idx));
+            }
+            Msg::AdjustHand(player, delta) => {
+                let hand = match player {
+                    None => Some(&mut self.game.board.hand),
+                    Some(idx) => self.game.opponents.get_mut(idx).map(|o| &mut o.hand),
+                };
+                if let Some(hand) = hand {
+                    *hand = (*hand as i32 + delta).max(0) as u32;
+                }
+            }
+            Msg::AdjustCounter(player, kind, delta) => {
+                let counters = match player {
+                    None => Some(&mut self.game.counters),
+                    Some(idx) => self.game.opponents.get_mut(idx).map(|o| &mut o.counters),
+                };
+                match counters {
+                    // Stored with the game they belong to.
+                    Some(counters) => counters.adjust(kind, delta),
+                    None => return false,
+                }
+                self.save_state();
+            }
+            Msg::ToggleDesignation(player, designation) => {
+                let designations = match player {
+                    None => Some(&mut self.game.designations),
+                    Some(idx) => self
+                        .game
+                        .opponents
+                        .get_mut(idx)
+                        .map(|o| &mut o.designations),
+                };
+                match designations {
+                    Some(designations) => opponent::toggle_designation(designations, designation),
+                    None => return false,
+                }
+                self.save_state();
+            }
+            Msg::SetDrawPingers(val) => {
+                return val
+                    .parse()
+                    .is_ok_and(|pingers| self.game.board.draw_pingers.neq_assign(pingers));
+            }
+            Msg::Draw => self.draw(1),
+            Msg::SetLibrary(val) => {
+                self.game.board.library = val.parse().ok();
+            }
+            Msg::ApplyPings => {
+                for opponent in &mut self.game.opponents {
+                    opponent.apply_pending_damage();
+                }
+            }
+            Msg::RollExpression(expr) => {
+                let roll = expr.roll(&mut self.rng, self.game.board.roll_rules());
+                self.record_roll(roll);
+            }
+            Msg::RollD20(text) => {
+                let roll = DiceExpr::die(20).roll(&mut self.rng, self.game.board.roll_rules());
+                let band = d20_band(roll.total);
+                let lang = self.settings.language;
+                let card = match text.card.trim() {
+                    "" => lang.t("d20_card"),
+                    card => card,
+                };
+                let event = lang.fmt("d20_event", &[&card, &roll.total, &text.modes[band]]);
+                self.game.table_log.push(event);
+                self.d20_band = Some(band);
+                self.record_roll(roll);
+            }
+            Msg::TogglePuzzlebox => {
+                self.game.board.puzzlebox = match self.game.board.puzzlebox {
+                    Some(_) => None,
+                    None => Some(0),
+                };
+            }
+            Msg::SetBarbarianClass(level) => {
+                return self.game.board.barbarian_class.neq_assign(level);
+            }
+            Msg::FetchWithPuzzlebox => {
+                if let Some(charge) = &mut self.game.board.puzzlebox {
+                    *charge = charge.saturating_sub(PUZZLEBOX_GOAL);
+                }
+            }
+            Msg::RollCelebr8000 => {
+                let roll = DiceExpr::parse("2d6")
+                    .expect("valid dice")
+                    .roll(&mut self.rng, self.game.board.roll_rules());
+                let kept = roll
+                    .dice
+                    .iter()
+                    .filter(|die| die.kept)
+                    .map(|die| die.value)
+                    .collect::<Vec<_>>();
+                let faces = celebr_8000(kept[0], kept[1]);
+                let lang = self.settings.language;
+                let bonuses = faces
+                    .iter()
+                    .map(|&face| lang.t(CELEBR_8000_BONUSES[face as usize - 1]))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                self.game
+                    .table_log
+                    .push(lang.fmt("celebr_event", &[&kept[0], &kept[1], &bonuses]));
+                self.game.celebr_bonuses = Some(faces);
+                self.record_roll(roll);
+            }
+            Msg::SetAttractions(attractions) => {
+                self.game.board.attractions = attractions;
+            }
+            Msg::SetUnfinity(unfinity) => {
+                self.unfinity = unfinity;
+                self.save_unfinity();
+            }
+            Msg::RollToVisit => {
+                let roll =
+                    DiceExpr::die(ATTRACTION_DIE).roll(&mut self.rng, self.game.board.roll_rules());
+                let result = roll.total as u32;
+                let visited = attraction::visit(&mut self.game.board.attractions, result);
+                self.record_roll(roll);
+                let lang = self.settings.language;
+                let event = if visited.is_empty() {
+                    lang.fmt("visit_none", &[&result])
+                } else {
+                    lang.fmt("visit_event", &[&result, &visited.join(", ")])
+                };
+                self.announcement = event.clone();
+                self.game.table_log.push(event);
+            }
+            Msg::SetArchaeologist(archaeologist) => {
+                self.game.board.archaeologist = archaeologist;
+            }
+            Msg::ActivateArchaeologist => {
+                if self.game.board.archaeologist.sacrificed {
+                    return false;
+                }
+                let flips = self.flip_for(FlipCard::GoblinArchaeologist);
+                self.record(FlipCard::GoblinArchaeologist, flips);
+            }
+            Msg::SetCustomCards(cards) => {
+                self.custom_cards = cards;
+                self.save_custom_cards();
+            }
+            Msg::ImportDeck(deck) => {
+                let _span = perf::span("persist");
+                self.storage.store(DECKLIST_KEY, Json(&deck));
+                self.decklist = Some(deck);
+            }
+            Msg::LoadScript(script) => {
+                self.settings.script = script;
+                self.save_settings();
+                self.compile_script();
+            }
+            Msg::ToggleScripts => {
+                self.settings.scripts_enabled = !self.settings.scripts_enabled;
+                self.save_settings();
+                self.compile_script();
+            }
+            Msg::FlipCustomCard(idx) => {
+                let card = self.custom_cards[idx].clone();
+                let win = card.definition.win;
+                let flips = self.flip_coins(card.definition.flips, win);
+                self.record_with(FlipCard::Homebrew, win, &card, flips);
+            }
+            Msg::Wheel(wheel) => {
+                let hands = std::iter::once(self.game.board.hand)
+                    .chain(self.game.opponents.iter().map(|o| o.hand))
+                    .collect::<Vec<_>>();
+                let refill = wheel.refill(&hands);
+                self.game.board.hand = refill;
+                self.game.board.drawn += refill;
+                for opponent in &mut self.game.opponents {
+                    opponent.hand = refill;
+                }
+                let event = self
+                    .settings
+                    .language
+                    .fmt("wheel_event", &[&wheel, &refill]);
+                self.announcement = event.clone();
+                self.game.table_log.push(event);
+            }
+            Msg::CreateTokens(name, temporary) => {
+                if temporary {
+                    self.game.board.create_temporary_tokens(&name, 1);
+                } else {
+                    self.game.board.create_tokens(&name, 1);
+                }
+            }
+            Msg::BoostBigIdea => {
+                if self.brainiacs() >= BIG_IDEA_BRAINIACS {
+                    self.big_idea_boosted = true;
+                }
+            }
+            Msg::BigIdea => {
+                let dice = if self.big_idea_boosted { 2 } else { 1 };
+                let roll = DiceExpr::parse(&format!("{}d6", dice))
+                    .expect("valid dice")
+                    .roll(&mut self.rng, self.game.board.roll_rules());
+                let name = self.settings.language.t("brainiac").to_string();
+                self.game.board.create_tokens(&name, roll.total as u32);
+                self.big_idea_boosted = false;
+                self.record_roll(roll);
+            }
+            Msg::SetTokens(tokens) => {
+                self.game.board.tokens = tokens;
+            }
+            Msg::SetOpponent(idx, opponent) => {
+                if let Some(slot) = self.game.opponents.get_mut(idx) {
+                    *slot = opponent;
+                    self.save_state();
+                }
+            }
+            Msg::SetStormPayoff(payoff) => {
+                return self.storm_payoff.neq_assign(payoff);
+            }
+            Msg::SetMana(mana) => {
+                return self.game.board.mana.neq_assign(mana);
+            }
+            Msg::SetCreatures(creatures) => {
+                self.game.board.creatures = creatures;
+            }
+            Msg::ToggleWyll => {
+                if self.game.board.creatures.iter().any(Creature::is_wyll) {
+                    self.game.board.creatures.retain(|c| !c.is_wyll());
+                } else {
+                    self.game.board.creatures.push(Creature {
+                        name: WYLL.to_string(),
+                        power: 1,
+                        toughness: 1,
+                        trigger: PumpTrigger::default(),
+                        bonus: (0, 0),
+                        pumps: 0,
+                        counters: 0,
+                    });
+                }
+            }
+            Msg::SetInfiniteMana(note) => {
+                self.game.board.infinite_mana = note;
+            }
+            Msg::SetReducers(reducers) => {
+                self.game.board.reducers = reducers;
+            }
+            Msg::SetSpells(spells) => {
+                self.game.spells = spells;
+                self.save_state();
+            }
+            Msg::Cast(idx) => {
+                if let Err(error) = self.cast(self.game.spells[idx].clone(), 0) {
+                    self.refuse_cast(error);
+                }
+            }
+            Msg::CastSpell(idx) => {
+                self.cast_until_it_sticks(self.game.spells[idx].clone());
+            }
+            Msg::UpdateRitualX(val) => {
+                return self.ritual_x.neq_assign(val);
+            }
+            Msg::CastRitual(ritual) => {
+                let x = self.ritual_x.parse().unwrap_or(0);
+                if let Err(error) = self.cast(ritual.spell(), ritual.mana(x)) {
+                    self.refuse_cast(error);
+                }
+            }
+            Msg::EndTurn => {
+                let unresolved = self.game.unresolved_upkeep_flips();
+                if unresolved.is_empty() {
+                    self.end_turn();
+                } else {
+                    // Nothing changed, so there is nothing to undo.
+                    self.undo.pop();
+                    self.upkeep_warning = Some(unresolved);
+                }
+            }
+            Msg::EndTurnAnyway => {
+                self.upkeep_warning = None;
+                self.end_turn();
+            }
+            Msg::CancelEndTurn => self.upkeep_warning = None,
+            Msg::SetUpkeepFlips(flips) => {
+                self.game.upkeep_flips = flips;
+                self.save_state();
+            }
+            Msg::SaveSession => {
+                self.sessions.push(self.session_summary());
+                self.prune_sessions();
+                self.save_state();
+                self.reset_session();
+            }
+            Msg::RemoveSession(idx) => {
+                self.sessions.remove(idx);
+                self.save_state();
+            }
+            Msg::Undo => {
+                let game = match self.undo.pop() {
+                    Some(game) => game,
+                    None => return false,
                 };
-                self.state.entries.push(entry);
-                self.state.value = "".to_string();
+                self.game = game;
+                *self.stats.borrow_mut() = StatsAccumulator::new(&self.game.history);
+                self.selected_chain = None;
+                self.announcement = self.settings.language.t("announce_undo").into();
+            }
+            Msg::ToggleVoice => {
+                if self.voice.take().is_none() {
+                    self.voice = Listener::start(
+                        self.settings.language,
+                        self.link.callback(Msg::Heard),
+                        self.link.callback(|_| Msg::VoiceEnded),
+                    );
+                    if self.voice.is_none() {
+                        self.announcement = self.settings.language.t("voice_unsupported").into();
+                    }
+                }
             }
-            Msg::Edit(idx) => {
-                let edit_value = self.state.edit_value.clone();
-                self.state.complete_edit(idx, edit_value);
-                self.state.edit_value = "".to_string();
+            Msg::Heard(transcript) => {
+                let lang = self.settings.language;
+                match voice::parse(&transcript, lang) {
+                    Some(VoiceCommand::Flip(times)) => {
+                        self.link.send_message(Msg::FlipMany(times));
+                    }
+                    Some(VoiceCommand::EndTurn) => self.link.send_message(Msg::EndTurn),
+                    Some(VoiceCommand::Undo) => self.link.send_message(Msg::Undo),
+                    None => self.announcement = lang.fmt("voice_unknown", &[&transcript]),
+                }
             }
-            Msg::Update(val) => {
-                println!("Input: {}", val);
-                self.state.value = val;
+            Msg::VoiceEnded => {
+                if let Some(voice) = &self.voice {
+                    voice.resume();
+                }
+                return false;
             }
-            Msg::UpdateEdit(val) => {
-                println!("Input: {}", val);
-                self.state.edit_value = val;
+            Msg::ResetTimings => perf::reset(),
+            Msg::ChangeSettings(settings) => {
+                if settings.theme != self.settings.theme {
+                    settings.theme.apply();
+                }
+                if settings.font_scale != self.settings.font_scale {
+                    settings.apply_font_scale();
+                }
+                let prune_flips = settings.max_flips != self.settings.max_flips;
+                let prune_sessions = settings.max_sessions != self.settings.max_sessions;
+                self.settings = *settings;
+                self.save_settings();
+                if prune_flips {
+                    self.prune_history(0);
+                }
+                if prune_sessions {
+                    self.prune_sessions();
+                }
             }
-            Msg::Remove(idx) => {
-                self.state.remove(idx);
+            Msg::StartTour => {
+                self.settings_open = true;
+                self.touring = true;
             }
-            Msg::SetFilter(filter) => {
-                self.state.filter = filter;
+            Msg::ToggleSettings => {
+                self.settings_open = !self.settings_open;
             }
-            Msg::ToggleEdit(idx) => {
-                self.state.edit_value = self.state.entries[idx].description.clone();
-                self.state.toggle_edit(idx);
+            Msg::GlobalKey(event) => {
+                if (event.ctrl_key() || event.meta_key()) && event.key().eq_ignore_ascii_case("k") {
+                    event.prevent_default();
+                    self.palette_open = true;
+                } else {
+                    return false;
+                }
             }
-            Msg::ToggleAll => {
-                let status = !self.state.is_all_completed();
-                self.state.toggle_all(status);
+            Msg::ClosePalette => {
+                self.palette_open = false;
             }
-            Msg::Toggle(idx) => {
-                self.state.toggle(idx);
+            Msg::RunCommand(idx) => {
+                self.palette_open = false;
+                let msg = match self.commands()[idx].0 {
+                    Command::Flip => Msg::Flip,
+                    Command::Cast(spell) => Msg::Cast(spell),
+                    Command::CastRitual(ritual) => Msg::CastRitual(ritual),
+                    Command::BigIdea => Msg::BigIdea,
+                    Command::EndTurn => Msg::EndTurn,
+                    Command::SaveSession => Msg::SaveSession,
+                    Command::ClearHistory => Msg::ClearHistory,
+                    Command::OpenSettings => Msg::ToggleSettings,
+                    Command::StartTour => Msg::StartTour,
+                };
+                self.update(msg);
             }
-            Msg::ClearCompleted => {
-                self.state.clear_completed();
+            Msg::FinishTour => {
+                self.touring = false;
+                self.settings.tour_seen = true;
+                self.save_settings();
             }
-            Msg::Nope => {}
+            Msg::Nope => return false,
+        }
+        if speak {
+            speech::speak(&self.announcement, self.settings.language);
+        }
+        // Once per message, so a burst of flips is stored once.
+        if persist {
+            self.save_state();
         }
-        self.storage.store(KEY, Json(&self.state.entries));
         true
     }
 
@@ -125,214 +1287,2140 @@ impl Component for App {
     }
 
     fn view(&self) -> Html {
+        let _span = perf::span("render");
         info!("rendered!");
-        let flips = Coin::flip(&mut thread_rng(), 1, 5).collect::<Vec<FlipResult>>();
+        let mut class = vec!["krarkinator"];
+        if self.settings.reduced_motion() {
+            class.push("reduced-motion");
+        }
+        if self.settings.outcome_icons {
+            class.push("outcome-icons");
+        }
+        if self.settings.outcome_patterns {
+            class.push("outcome-patterns");
+        }
+        if self.spectating {
+            class.push("spectating");
+            return html! {
+                <div class=class>{ self.view_spectator() }</div>
+            };
+        }
         html! {
-            <ol class="item-list">
-            {
-                for flips
-                .iter()
-                .map(|f| html!{
-                    <li>
-                    {f}
-                    </li>
-                })
-            }
-            </ol>
+            <div class=class>
+                <svg class="pattern-defs" width="0" height="0" aria-hidden="true">
+                    <defs>
+                        <pattern id="loss-hatch" width="4" height="4" patternUnits="userSpaceOnUse">
+                            <rect class="loss" width="4" height="4" />
+                            <path d="M0,4 L4,0" />
+                        </pattern>
+                    </defs>
+                </svg>
+                { self.view_games() }
+                { self.view_controls() }
+                { self.panel(Panel::CardOfTheDay, || self.view_card_of_the_day()) }
+                { self.panel(Panel::Board, || self.view_board()) }
+                { self.panel(Panel::Life, || self.view_life()) }
+                { self.panel(Panel::Opponents, || self.view_opponents()) }
+                { self.panel(Panel::TurnTimer, || self.view_turn_timer()) }
+                { self.panel(Panel::Creatures, || self.view_creatures()) }
+                { self.panel(Panel::Hands, || self.view_hands()) }
+                { self.panel(Panel::PlayerCounters, || self.view_player_counters()) }
+                { self.panel(Panel::Mana, || self.view_mana()) }
+                { self.panel(Panel::Tokens, || self.view_tokens()) }
+                { self.panel(Panel::Spells, || self.view_spells()) }
+                { self.panel(Panel::Archaeologist, || self.view_archaeologist()) }
+                { self.panel(Panel::Upkeep, || self.view_upkeep_flips()) }
+                { self.panel(Panel::CustomCards, || self.view_custom_cards()) }
+                { self.panel(Panel::Stats, || self.view_stats()) }
+                { self.panel(Panel::CardStats, || self.view_card_stats()) }
+                { self.panel(Panel::RunLengths, || self.view_run_lengths()) }
+                { self.panel(Panel::Dice, || self.view_dice()) }
+                { self.panel(Panel::Attractions, || self.view_attractions()) }
+                { self.panel(Panel::Unfinity, || self.view_unfinity()) }
+                { self.view_chain_odds() }
+                { self.panel(Panel::History, || self.view_history()) }
+                { self.panel(Panel::Resolution, || self.view_resolution()) }
+                { self.panel(Panel::Sessions, || self.view_sessions()) }
+                { self.panel(Panel::Records, || self.view_records()) }
+                { self.view_tools() }
+                { self.view_settings() }
+                { self.view_palette() }
+                { self.view_celebration() }
+                { self.view_tour() }
+                <div class="sr-only" role="status" aria-live="polite">
+                    { &self.announcement }
+                </div>
+            </div>
         }
     }
 }
 
 impl App {
-    fn view_filter(&self, filter: Filter) -> Html {
-        let flt = filter.clone();
+    /// What counts as winning `card`'s flips at this table.
+    fn win_condition(&self, card: FlipCard) -> WinCondition {
+        self.settings.win_condition(card)
+    }
 
-        html! {
-            <li>
-                <a class=if self.state.filter == flt { "selected" } else { "not-selected" }
-                   href=&flt
-                   onclick=self.link.callback(move |_| Msg::SetFilter(flt.clone()))>
-                    { filter }
-                </a>
-            </li>
+    /// Flips coins for one activation of `source` with the current thumbs.
+    fn flip_for(&mut self, source: FlipCard) -> Vec<FlipResult> {
+        self.flip_coins(source.flips(), self.win_condition(source))
+    }
+
+    /// Flips `flips` coins with the current thumbs, or until one is lost
+    /// as `win` says. Every coin tossed is kept for the history to show.
+    fn flip_coins(&mut self, flips: Option<usize>, win: WinCondition) -> Vec<FlipResult> {
+        let _span = perf::span("flip");
+        let config = FlipConfig::new()
+            .thumbs(self.game.board.thumbs)
+            .call(win.side())
+            .cap(MAX_CHAIN);
+        let config = match flips {
+            Some(times) => config.flips(times),
+            None => config.until_loss(),
+        };
+        self.tosses = config.toss(&mut self.rng);
+        self.toss_call = win.side();
+        self.tosses.iter().map(Toss::result).collect()
+    }
+
+    /// Resolves Mana Clash against the targeted opponent, or the first one:
+    /// both flip until both coins come up heads and each tails deals 1
+    /// damage to whoever flipped it.
+    fn mana_clash(&mut self) {
+        let lang = self.settings.language;
+        let opponents = self.game.opponents.len();
+        let target = match self.game.target.filter(|&idx| idx < opponents) {
+            Some(idx) => idx,
+            None if opponents > 0 => 0,
+            None => {
+                self.announcement = lang.t("mana_clash_no_opponent").into();
+                return;
+            }
+        };
+        let win = self.win_condition(FlipCard::ManaClash);
+        let mut yours = Vec::new();
+        let mut theirs = Vec::new();
+        let theirs_config = FlipConfig::new().flips(1);
+        while yours.len() < MAX_CHAIN {
+            let you = self.flip_coins(Some(1), win).remove(0);
+            let them = theirs_config.flip(&mut self.rng).remove(0);
+            let done = win.wins(&you) && win.wins(&them);
+            yours.push(you);
+            theirs.push(them);
+            if done {
+                break;
+            }
+        }
+
+        let rounds = yours.len();
+        let your_damage = yours.iter().filter(|f| !win.wins(f)).count();
+        let their_damage = theirs.iter().filter(|f| !win.wins(f)).count();
+        self.record(FlipCard::ManaClash, yours);
+
+        let opponent = &mut self.game.opponents[target];
+        opponent.life -= their_damage as i32;
+        let name = opponent.name.clone();
+        let activation = self.game.activations - 1;
+        let start = self.game.history.len();
+        self.game
+            .history
+            .extend(theirs.into_iter().map(|result| FlipRecord {
+                source: FlipCard::ManaClash,
+                activation,
+                thumbs: 0,
+                player: Some(name.clone()),
+                win,
+                result,
+            }));
+        self.events
+            .publish(&EngineEvent::Flipped(&self.game.history[start..]));
+        self.announcement = lang.fmt(
+            "announce_mana_clash",
+            &[&rounds, &your_damage, &name, &their_damage],
+        );
+    }
+
+    /// Moves on from your end step to your next upkeep.
+    fn end_turn(&mut self) {
+        if let Some(before) = self.game.day_night {
+            let after = before.next(self.game.storm);
+            if after != before {
+                let key = match after {
+                    DayNight::Day => "becomes_day",
+                    DayNight::Night => "becomes_night",
+                };
+                let event = self.settings.language.t(key).to_string();
+                self.game.table_log.push(event);
+            }
+            self.game.day_night = Some(after);
+        }
+        // Turns pass around the table from the first player, as on the
+        // turn timer.
+        let players = self.game.opponents.len() + 1;
+        let player = (self.game.first_player.unwrap_or(0) + self.game.turn_storms.len()) % players;
+        self.game.pace.end_turn(player, js_sys::Date::now());
+        cleanup::run(&mut self.game, &self.settings.kept_at_end_of_turn);
+        self.big_idea_boosted = false;
+        self.run_effects(|effect, board| effect.on_turn_end(board));
+        // Turns end on your end step and the next thing of yours
+        // is your upkeep, so both reminders come due now.
+        let lang = self.settings.language;
+        if self.game.holder(TableDesignation::Monarch) == Some(0) {
+            self.game.table_log.push(lang.t("monarch_end_step").into());
+        }
+        if self.game.holder(TableDesignation::Initiative) == Some(0) {
+            self.game.table_log.push(lang.t("initiative_upkeep").into());
+        }
+        let mark = self.game.mark();
+        self.game.turn_marks.push(mark);
+    }
+
+    /// Flips once for `source` as `player` and records it, returning
+    /// whether every flip was won. Opponents' flips count towards their
+    /// stats rather than yours.
+    fn flip_as(&mut self, player: usize, source: FlipCard) -> bool {
+        let win = self.win_condition(source);
+        if player == 0 {
+            let flips = self.flip_for(source);
+            let won = flips.iter().all(|f| win.wins(f));
+            self.record(source, flips);
+            return won;
+        }
+        let lang = self.settings.language;
+        let name = self.player_name(player);
+        // Your Krark's Thumbs don't help an opponent.
+        let flips = FlipConfig::new()
+            .flips(source.flips().unwrap_or(1))
+            .call(win.side())
+            .flip(&mut self.rng);
+        let won = flips.iter().all(|f| win.wins(f));
+        let results = flips
+            .iter()
+            .map(|f| lang.flip(f))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let activation = self.game.activations;
+        self.game.activations += 1;
+        self.game.pace.begin(js_sys::Date::now());
+        let start = self.game.history.len();
+        self.game
+            .history
+            .extend(flips.into_iter().map(|result| FlipRecord {
+                source,
+                activation,
+                thumbs: 0,
+                player: Some(name.clone()),
+                win,
+                result,
+            }));
+        self.events
+            .publish(&EngineEvent::Flipped(&self.game.history[start..]));
+        let source = format!("{} ({})", source, name);
+        self.announcement = lang.fmt("announce_flips", &[&source, &results]);
+        won
+    }
+
+    /// Makes the flip for Risky Move that is due, handing the picked
+    /// creature to the picked opponent if it is lost. A creature of yours
+    /// leaves your board.
+    fn flip_risky_move(&mut self, creature: String, recipient: Option<usize>) {
+        let lang = self.settings.language;
+        let players = self.game.opponents.len() + 1;
+        let (controller, recipient) = match &self.game.risky_move {
+            Some(risky_move) if risky_move.taken_from.is_some() => (
+                risky_move.controller,
+                recipient.filter(|&r| risky_move.recipients(players).any(|p| p == r)),
+            ),
+            _ => return,
+        };
+        let recipient = match recipient {
+            Some(recipient) if !creature.is_empty() => recipient,
+            _ => {
+                self.announcement = lang.t("risky_move_choose").into();
+                return;
+            }
+        };
+        let won = self.flip_as(controller, FlipCard::RiskyMove);
+        let handover = self
+            .game
+            .risky_move
+            .as_mut()
+            .and_then(|risky_move| risky_move.resolve(won, creature, recipient))
+            .cloned();
+        let event = match handover {
+            Some(handover) => {
+                if handover.from == 0 {
+                    let creatures = &mut self.game.board.creatures;
+                    if let Some(idx) = creatures.iter().position(|c| c.name == handover.creature) {
+                        creatures.remove(idx);
+                    }
+                }
+                let from = self.player_name(handover.from);
+                let to = self.player_name(handover.to);
+                lang.fmt("risky_move_lost", &[&from, &to, &handover.creature])
+            }
+            None => lang.fmt("risky_move_won", &[&self.player_name(controller)]),
+        };
+        self.announcement = format!("{} {}", self.announcement, event);
+        self.game.table_log.push(event);
+    }
+
+    /// Flips for a combat card as whoever controls it and logs what
+    /// happens to the creatures on the losing side. Opponents' flips count
+    /// towards their stats rather than yours.
+    fn combat_flip(&mut self, source: FlipCard, effect: CombatEffect) {
+        let lang = self.settings.language;
+        let combat = self.game.combat;
+        if !combat.seated(self.game.opponents.len() + 1) {
+            self.announcement = lang.t("combat_no_player").into();
+            return;
+        }
+        let flipper = self.player_name(combat.flipper);
+        let won = self.flip_as(combat.flipper, source);
+
+        let (side, fate) = effect.outcome(won);
+        let owner = self.player_name(combat.controller(side));
+        let flipped = lang.fmt(
+            if won { "combat_won" } else { "combat_lost" },
+            &[&source, &flipper],
+        );
+        let event = format!(
+            "{} {}",
+            flipped,
+            lang.fmt(side.as_ref(), &[&owner, &lang.t(fate.as_ref())])
+        );
+        self.announcement = format!("{} {}", self.announcement, event);
+        self.game.table_log.push(event);
+    }
+
+    /// Drops the oldest flips once the history is more than `slack` parts
+    /// over the retention cap, archiving them first if asked to.
+    fn prune_history(&mut self, slack: usize) {
+        let max = match self.settings.max_flips {
+            Some(max) => max,
+            None => return,
+        };
+        let allowed = max + max.checked_div(slack).unwrap_or(0);
+        if self.game.history.len() <= allowed {
+            return;
+        }
+        let pruned = history::prune(&mut self.game.history, max);
+        *self.stats.borrow_mut() = StatsAccumulator::new(&self.game.history);
+        if self.settings.archive_pruned {
+            let csv = history::to_csv(&pruned);
+            if let Err(err) = export::download("krarkinator-flips.csv", "text/csv", &csv) {
+                error!("flip archive failed: {:?}", err);
+            }
+        }
+    }
+
+    /// Drops the oldest saved sessions past the retention cap, archiving
+    /// them first if asked to.
+    fn prune_sessions(&mut self) {
+        let max = match self.settings.max_sessions {
+            Some(max) => max,
+            None => return,
+        };
+        let pruned = history::prune(&mut self.sessions, max);
+        if pruned.is_empty() {
+            return;
+        }
+        if self.settings.archive_pruned {
+            let text: Text = Json(&pruned).into();
+            if let Ok(json) = text {
+                let _ = export::download("krarkinator-sessions.json", "application/json", &json);
+            }
+        }
+        self.save_state();
+    }
+
+    fn save_settings(&mut self) {
+        let _span = perf::span("persist");
+        self.storage.store(SETTINGS_KEY, Json(&self.settings));
+    }
+
+    /// Stores every open game along with the saved sessions. A snapshot
+    /// being looked at is someone else's game, so nothing is stored while
+    /// it is shown. A state that could not be read was backed up by
+    /// `restore_state` before this can replace it.
+    fn save_state(&mut self) {
+        if self.state_error == Some(StateError::TooNew) || self.spectating {
+            return;
         }
+        let _span = perf::span("persist");
+        self.game.word_pos = self.rng.get_word_pos();
+        let mut games = self.games.clone();
+        games[self.active_game] = self.game.clone();
+        let state = PersistedState::capture(games, self.active_game, &self.sessions);
+        self.storage.store(STATE_KEY, Json(&state));
+    }
+
+    fn save_unfinity(&mut self) {
+        let _span = perf::span("persist");
+        self.storage.store(UNFINITY_KEY, Json(&self.unfinity));
+    }
+
+    /// Keeps `value` if it sets a personal best, and announces it.
+    fn offer_record(&mut self, kind: RecordKind, value: u64) {
+        let today = String::from(js_sys::Date::new_0().to_iso_string());
+        if !self.records.offer(kind, value, &today[..10]) {
+            return;
+        }
+        let lang = self.settings.language;
+        self.announcement.push(' ');
+        self.announcement
+            .push_str(&lang.fmt("announce_record", &[&lang.t(kind.as_ref())]));
+        let _span = perf::span("persist");
+        self.storage.store(RECORDS_KEY, Json(&self.records));
+    }
+
+    fn save_custom_cards(&mut self) {
+        let _span = perf::span("persist");
+        let definitions: Vec<_> = self
+            .custom_cards
+            .iter()
+            .map(|card| &card.definition)
+            .collect();
+        self.storage.store(CUSTOM_CARDS_KEY, Json(&definitions));
+    }
+
+    /// Puts the active game back in its tab and brings out game `idx`,
+    /// with its random numbers where it left them.
+    fn switch_game(&mut self, idx: usize) {
+        self.undo.clear();
+        self.game.word_pos = self.rng.get_word_pos();
+        std::mem::swap(&mut self.game, &mut self.games[self.active_game]);
+        std::mem::swap(&mut self.game, &mut self.games[idx]);
+        self.resume_rng();
+        self.active_game = idx;
+        *self.stats.borrow_mut() = StatsAccumulator::new(&self.game.history);
+        self.selected_chain = None;
+        self.save_state();
+    }
+
+    /// Headline numbers of the session so far, counting the turn in
+    /// progress if anything was cast in it.
+    fn session_summary(&self) -> SessionSummary {
+        let mut turn_storms = self.game.turn_storms.clone();
+        if self.game.storm > 0 {
+            turn_storms.push(self.game.storm);
+        }
+        let saved_at = String::from(js_sys::Date::new_0().to_iso_string());
+        SessionSummary::new(
+            saved_at[..16].replace('T', " "),
+            &self.game.history,
+            &turn_storms,
+            self.game.pace.elapsed(js_sys::Date::now()),
+            self.game.seed,
+        )
+    }
+
+    fn reset_session(&mut self) {
+        self.undo.clear();
+        self.game.history.clear();
+        self.game.triggers.clear();
+        *self.stats.borrow_mut() = StatsAccumulator::default();
+        self.game.activations = 0;
+        self.selected_chain = None;
+        self.game.storm = 0;
+        self.game.copies = 0;
+        self.game.turn_storms.clear();
+        self.game.turn_casts.clear();
+        self.game.turn_marks.clear();
+        self.game.session_start = self.game.mark();
+        self.game.pace = Pace::default();
+        self.reseed(None);
+        self.unfinity.new_game();
+        self.save_unfinity();
     }
 
-    fn view_input(&self) -> Html {
+    /// Starts the game's random numbers over from `seed`, or from a fresh
+    /// one, so the flips that follow can be replayed.
+    fn reseed(&mut self, seed: Option<u64>) {
+        let seed = seed.unwrap_or_else(|| thread_rng().gen());
+        self.game.seed = Some(seed);
+        self.game.word_pos = 0;
+        self.rng = ChaCha12Rng::seed_from_u64(seed);
+    }
+
+    /// Carries on with the game's random numbers from where they had got,
+    /// or starts them from a fresh seed if the game has none.
+    fn resume_rng(&mut self) {
+        match self.game.seed {
+            Some(seed) => {
+                self.rng = ChaCha12Rng::seed_from_u64(seed);
+                self.rng.set_word_pos(self.game.word_pos);
+            }
+            None => self.reseed(None),
+        }
+    }
+
+    /// "You" for player 0, otherwise the opponent's name.
+    fn player_name(&self, player: usize) -> String {
+        match player.checked_sub(1) {
+            Some(idx) => self.game.opponents[idx].name.clone(),
+            None => self.settings.language.t("you").into(),
+        }
+    }
+
+    #[cfg(feature = "scryfall")]
+    fn view_daily_card(&self) -> Html {
         html! {
-            // You can use standard Rust comments. One line:
-            // <li></li>
-            <input class="new-todo"
-                   placeholder="What needs to be done?"
-                   value=&self.state.value
-                   oninput=self.link.callback(|e: InputData| Msg::Update(e.value))
-                   onkeypress=self.link.callback(|e: KeyboardEvent| {
-                       if e.key() == "Enter" { Msg::Add } else { Msg::Nope }
-                   }) />
-            /* Or multiline:
-            <ul>
-                <li></li>
-            </ul>
-            */
+            <CardOfTheDay language=self.settings.language
+                          on_track=self.callbacks.track_card.clone() />
         }
     }
 
-    fn view_entry(&self, (idx, entry): (usize, &Entry)) -> Html {
-        let mut class = "todo".to_string();
-        if entry.editing {
-            class.push_str(" editing");
+    #[cfg(not(feature = "scryfall"))]
+    fn view_daily_card(&self) -> Html {
+        html! {}
+    }
+
+    /// Today's card and the cards being tracked, each switched on while it
+    /// is in play.
+    fn view_card_of_the_day(&self) -> Html {
+        html! {
+            <div class="card-of-the-day-panel">
+                { self.view_daily_card() }
+                <TrackedCards language=self.settings.language
+                              cards=self.game.tracked_cards.clone()
+                              on_change=self.callbacks.set_tracked_cards.clone() />
+            </div>
         }
-        if entry.completed {
-            class.push_str(" completed");
+    }
+
+    /// Your permanents that must flip in your upkeep, and whether each
+    /// has this turn.
+    fn view_upkeep_flips(&self) -> Html {
+        html! {
+            <UpkeepFlips language=self.settings.language
+                         flips=self.game.upkeep_flips.clone()
+                         on_change=self.callbacks.set_upkeep_flips.clone() />
         }
+    }
 
+    /// Asks before ending a turn with upkeep flips not made.
+    fn view_upkeep_warning(&self) -> Html {
+        let unresolved = match &self.upkeep_warning {
+            Some(unresolved) => unresolved,
+            None => return html! {},
+        };
+        let lang = self.settings.language;
         html! {
-            <li class=class>
-                <div class="view">
-                    <input class="toggle" type="checkbox" checked=entry.completed onclick=self.link.callback(move |_| Msg::Toggle(idx)) />
-                    <label ondblclick=self.link.callback(move |_| Msg::ToggleEdit(idx))>{ &entry.description }</label>
-                    <button class="destroy" onclick=self.link.callback(move |_| Msg::Remove(idx)) />
+            <Modal language=lang
+                   title=lang.t("upkeep_unresolved")
+                   on_close=self.callbacks.cancel_end_turn.clone()>
+                <div class="upkeep-warning">
+                    <p>{ lang.fmt("upkeep_unresolved_cards", &[&unresolved.join(", ")]) }</p>
+                    <button onclick=self.link.callback(|_| Msg::CancelEndTurn)>{ lang.t("upkeep_go_back") }</button>
+                    <button class="end-turn" onclick=self.link.callback(|_| Msg::EndTurnAnyway)>
+                        { lang.t("upkeep_end_anyway") }
+                    </button>
                 </div>
-                { self.view_entry_edit_input((&idx, &entry)) }
-            </li>
+            </Modal>
+        }
+    }
+
+    /// Text keys of the table designations `player` holds and their own
+    /// designations.
+    fn held(&self, player: usize) -> Vec<String> {
+        let own = match player.checked_sub(1) {
+            Some(idx) => &self.game.opponents[idx].designations,
+            None => &self.game.designations,
+        };
+        self.game
+            .held
+            .iter()
+            .filter(|&&(_, p)| p == player)
+            .map(|(designation, _)| designation.as_ref().to_string())
+            .chain(
+                own.iter()
+                    .map(|designation| designation.as_ref().to_string()),
+            )
+            .collect()
+    }
+
+    /// A picker per table designation for who holds it.
+    fn view_table_designations(&self) -> Html {
+        let lang = self.settings.language;
+        let players = self.game.opponents.len() + 1;
+        html! {
+            <div class="table-designations">
+                { for TableDesignation::iter().map(|designation| {
+                    let holder = self.game.holder(designation);
+                    html! {
+                        <label>
+                            { lang.t(designation.as_ref()) }
+                            <select onchange=self.link.callback(move |e: ChangeData| match e {
+                                ChangeData::Select(select) => {
+                                    Msg::SetHolder(designation, select.value().parse().ok())
+                                }
+                                _ => Msg::Nope,
+                            })>
+                                <option value="" selected=holder.is_none()>{ lang.t("nobody") }</option>
+                                { for (0..players).map(|player| html! {
+                                    <option value=player selected=holder == Some(player)>
+                                        { self.player_name(player) }
+                                    </option>
+                                }) }
+                            </select>
+                        </label>
+                    }
+                }) }
+            </div>
         }
     }
 
-    fn view_entry_edit_input(&self, (idx, entry): (&usize, &Entry)) -> Html {
-        let idx = *idx;
-        if entry.editing {
-            html! {
-                <input class="edit"
-                       type="text"
-                       value=&entry.description
-                       oninput=self.link.callback(move |e: InputData| Msg::UpdateEdit(e.value))
-                       onblur=self.link.callback(move |_| Msg::Edit(idx))
-                       onkeypress=self.link.callback(move |e: KeyboardEvent| {
-                          if e.key() == "Enter" { Msg::Edit(idx) } else { Msg::Nope }
-                       }) />
+    /// Rolls off for first player among you and the opponents, seating the
+    /// opponents randomly first if asked to, and opens the table log with
+    /// the result.
+    fn roll_for_first(&mut self) {
+        let lang = self.settings.language;
+        let rng = &mut self.rng;
+        let mut opening = Vec::new();
+        if self.seat_randomly {
+            // Seats only matter relative to each other, so you stay put and
+            // the opponents move around you.
+            let seats = seating::shuffle_seating(self.game.opponents.len(), rng);
+            self.game.reseat(&seats);
+        }
+        let names = std::iter::once(lang.t("you").to_string())
+            .chain(self.game.opponents.iter().map(|o| o.name.clone()))
+            .collect::<Vec<_>>();
+        if self.seat_randomly {
+            opening.push(lang.fmt("seating_order", &[&names.join(", ")]));
+        }
+        let roll_off = match seating::roll_off(names.len(), rng) {
+            Some(roll_off) => roll_off,
+            None => return,
+        };
+        for (round, rolls) in roll_off.rounds.iter().enumerate() {
+            let rolls = rolls
+                .iter()
+                .map(|&(player, roll)| format!("{} {}", names[player], roll))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let key = if round == 0 {
+                "roll_off"
+            } else {
+                "roll_off_reroll"
+            };
+            opening.push(lang.fmt(key, &[&rolls]));
+        }
+        let first = lang.fmt("first_player", &[&names[roll_off.winner]]);
+        opening.push(first.clone());
+        self.game.first_player = Some(roll_off.winner);
+        self.game.prepend_log(opening);
+        self.announcement = first;
+    }
+
+    /// Draws `cards`, each of which pings the target for every draw pinger.
+    fn draw(&mut self, cards: u32) {
+        self.game.board.draw(cards);
+        let pingers = self.game.board.draw_pingers;
+        let target = self
+            .game
+            .target
+            .and_then(|target| self.game.opponents.get_mut(target));
+        if let Some(opponent) = target {
+            opponent.pending_damage += pingers * cards;
+        }
+    }
+
+    /// Your library's size when the game began: as set, or else the
+    /// imported decklist less the opening hand.
+    fn library_size(&self) -> Option<u32> {
+        self.game.board.library.or_else(|| {
+            let deck = self.decklist.as_ref()?;
+            Some(deck.size().saturating_sub(OPENING_HAND))
+        })
+    }
+
+    /// Casts `spell` through the workflow: storm, Krark flips, mana and
+    /// any tracked effects. Rituals add `produced` mana per resolution.
+    /// Nothing happens if the pool can't pay for it.
+    fn cast(&mut self, spell: Spell, produced: u32) -> Result<Resolution, CastError> {
+        let cost = self.game.board.pay_for(&spell)?;
+        self.game.storm += 1;
+        let win = self.win_condition(FlipCard::Krark);
+        let flips = self.flip_coins(Some(self.game.board.krarks), win);
+        let outcome = CastOutcome::new(&spell, self.game.storm - 1, &flips, win);
+        let storm = self.game.storm;
+        self.run_effects(|effect, board| effect.on_cast(board, storm, &outcome));
+        let event = EngineEvent::Cast {
+            spell: &spell,
+            outcome: &outcome,
+        };
+        self.events.publish(&event);
+        #[cfg(feature = "scripting")]
+        let output = {
+            let board = &mut self.game.board;
+            self.script.as_ref().map(|s| s.run(&event, board))
+        };
+        #[cfg(feature = "scripting")]
+        self.log_script(output);
+        let gained = self.game.board.collect_mana(&outcome, produced);
+        let net_mana = i64::from(gained) - i64::from(cost);
+        for creature in &mut self.game.board.creatures {
+            creature.on_cast(&outcome);
+        }
+        if spell.is_named(BRAIN_FREEZE) {
+            let target = self
+                .game
+                .target
+                .and_then(|target| self.game.opponents.get_mut(target));
+            if let Some(opponent) = target {
+                opponent.milled += BRAIN_FREEZE_MILL * outcome.resolutions();
             }
+        }
+        let drawn = spell.cards_drawn(&outcome);
+        if drawn > 0 {
+            self.draw(drawn);
+        }
+        if spell.is_named(EMPTY_THE_WARRENS) {
+            let goblins = WARRENS_GOBLINS * outcome.resolutions();
+            let name = self.settings.language.t("goblin").to_string();
+            self.game.board.create_tokens(&name, goblins);
+        }
+        let resolution = Resolution::new(&flips, win);
+        let activation = self.game.activations;
+        self.record(FlipCard::Krark, flips);
+        for (idx, &trigger) in resolution.triggers.iter().enumerate() {
+            self.game.log_trigger(idx + 1, activation, trigger);
+        }
+        let lang = self.settings.language;
+        let prompt = lang.fmt("announce_cast", &[&spell.name, &self.game.board.krarks]);
+        let triggers = resolution
+            .triggers
+            .iter()
+            .map(|trigger| lang.t(trigger.as_ref()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.announcement = format!("{} {} {}", prompt, self.announcement, triggers);
+        self.game.turn_casts.push((spell.name, net_mana));
+        self.offer_record(RecordKind::HighestStorm, u64::from(storm));
+        let copies = outcome.copies();
+        self.game.copies += copies;
+        self.offer_record(RecordKind::MostCopies, u64::from(copies));
+        Ok(resolution)
+    }
+
+    /// Announces why a spell was not cast. Nothing changed, so there is
+    /// nothing to undo.
+    fn refuse_cast(&mut self, error: CastError) {
+        self.undo.pop();
+        self.announcement = self.settings.language.t(error.as_ref()).to_string();
+    }
+
+    /// Resolves the next trigger of the cast being stepped through with the
+    /// `kept` coin of those tossed, recording it as a Krark flip.
+    fn resolve_step(&mut self, kept: Call, coins: Vec<Call>) {
+        let win = self.win_condition(FlipCard::Krark);
+        let line = match &mut self.step_cast {
+            Some(cast) => cast.resolve(coins, kept, win),
+            None => return,
+        };
+        let flip = if kept == Call::Heads {
+            FlipResult::Heads
         } else {
-            html! { <input type="hidden" /> }
+            FlipResult::Tails
+        };
+        let activation = self.game.activations;
+        self.record_with(FlipCard::Krark, win, FlipCard::Krark.effect(), vec![flip]);
+        self.game.log_trigger(line.number, activation, line.trigger);
+        let lang = self.settings.language;
+        self.announcement.push(' ');
+        self.announcement.push_str(lang.t(line.trigger.as_ref()));
+        self.step_lines.push(line);
+    }
+
+    /// Casts `spell`, and again each time Krark returns it to hand, for as
+    /// long as the pool can pay and up to [`MAX_RECASTS`] more times.
+    fn cast_until_it_sticks(&mut self, spell: Spell) {
+        let mut chain = CastChain::default();
+        loop {
+            let resolution = match self.cast(spell.clone(), 0) {
+                Ok(resolution) => resolution,
+                Err(error) if chain.casts.is_empty() => return self.refuse_cast(error),
+                Err(_) => break,
+            };
+            let bounced = resolution.bounced();
+            chain.casts.push(resolution);
+            let affordable = self.game.board.can_pay(&spell);
+            if !bounced || !affordable || chain.casts.len() > MAX_RECASTS {
+                break;
+            }
         }
+        let lang = self.settings.language;
+        let key = if chain.bounced() {
+            "announce_chain_bounced"
+        } else {
+            "announce_chain_stuck"
+        };
+        let counts = chain.counts();
+        let summary = lang.fmt(key, &[&spell.name, &counts.casts, &counts.copies]);
+        self.announcement = format!("{} {}", summary, self.announcement);
     }
-}
 
-#[derive(EnumIter, ToString, Clone, PartialEq, Serialize, Deserialize)]
-pub enum Filter {
-    All,
-    Active,
-    Completed,
-}
+    fn brainiacs(&self) -> u32 {
+        let name = self.settings.language.t("brainiac");
+        self.game
+            .board
+            .tokens
+            .iter()
+            .find(|t| t.name == name)
+            .map_or(0, |t| t.count)
+    }
+
+    /// Shows `roll` as the latest roll and announces it. Every dice action
+    /// goes through here.
+    fn record_roll(&mut self, roll: Roll) {
+        let event = EngineEvent::Rolled(&roll);
+        self.events.publish(&event);
+        #[cfg(feature = "scripting")]
+        let output = {
+            let board = &mut self.game.board;
+            self.script.as_ref().map(|s| s.run(&event, board))
+        };
+        self.game.board.on_roll(roll.total);
+        if self.game.board.barbarian_class >= Some(2) {
+            let event = self.settings.language.t("barbarian_trigger").to_string();
+            self.game.table_log.push(event);
+        }
+        self.announcement = self
+            .settings
+            .language
+            .fmt("announce_roll", &[&roll.expression, &roll.total]);
+        #[cfg(feature = "scripting")]
+        self.log_script(output);
+        self.game.last_roll = Some(roll);
+    }
 
-impl<'a> Into<Href> for &'a Filter {
-    fn into(self) -> Href {
-        match *self {
-            Filter::All => "#/".into(),
-            Filter::Active => "#/active".into(),
-            Filter::Completed => "#/completed".into(),
+    /// Flips once for the chosen source.
+    fn flip(&mut self) {
+        if self.flip_source == FlipCard::ManaClash {
+            self.mana_clash();
+        } else if let Some(effect) = self.flip_source.combat() {
+            self.combat_flip(self.flip_source, effect);
+        } else {
+            let flips = self.flip_for(self.flip_source);
+            self.record(self.flip_source, flips);
         }
     }
-}
 
-impl Filter {
-    fn fit(&self, entry: &Entry) -> bool {
-        match *self {
-            Filter::All => true,
-            Filter::Active => !entry.completed,
-            Filter::Completed => entry.completed,
+    /// Flips `times` times in a row, at most [`MAX_CHAIN`], as one step:
+    /// one undo, one announcement and one buzz for the lot.
+    fn flip_many(&mut self, times: usize) {
+        let times = times.min(MAX_CHAIN);
+        if times == 1 {
+            return self.flip();
+        }
+        self.batch_outcomes = Some(Vec::new());
+        for _ in 0..times {
+            self.flip();
+        }
+        let outcomes = self.batch_outcomes.take().unwrap_or_default();
+        let streaks = std::mem::take(&mut self.batch_streaks);
+        let lang = self.settings.language;
+        let wins = outcomes.iter().filter(|&&won| won).count();
+        self.announcement = lang.fmt("announce_flip_many", &[&times, &wins, &outcomes.len()]);
+        for streak in &streaks {
+            self.announcement.push(' ');
+            self.announcement
+                .push_str(&lang.fmt("announce_streak", &[streak]));
+        }
+        if self.settings.haptics && !streaks.is_empty() {
+            haptics::streak();
+        } else if self.settings.haptics && !outcomes.is_empty() {
+            haptics::flips(&outcomes);
         }
     }
-}
 
-impl State {
-    fn total(&self) -> usize {
-        self.entries.len()
+    /// Appends one activation's flips to the history and sets the screen
+    /// reader announcement for them.
+    fn record(&mut self, source: FlipCard, flips: Vec<FlipResult>) {
+        let win = self.win_condition(source);
+        self.record_with(source, win, source.effect(), flips);
     }
 
-    fn total_completed(&self) -> usize {
-        self.entries
+    /// Records flips attributed to `source` while `effect` reacts to them.
+    fn record_with(
+        &mut self,
+        source: FlipCard,
+        win: WinCondition,
+        effect: &dyn CardEffect,
+        flips: Vec<FlipResult>,
+    ) {
+        self.game.pace.begin(js_sys::Date::now());
+        let lang = self.settings.language;
+        let results = flips
             .iter()
-            .filter(|e| Filter::Completed.fit(e))
-            .count()
-    }
+            .map(|f| lang.flip(f))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.announcement = lang.fmt("announce_flips", &[&source, &results]);
+        let streak_before = if flips.iter().all(|f| win.wins(f)) {
+            self.stats.borrow().current_streak
+        } else {
+            0
+        };
 
-    fn is_all_completed(&self) -> bool {
-        let mut filtered_iter = self
-            .entries
+        let notes = flips
             .iter()
-            .filter(|e| self.filter.fit(e))
-            .peekable();
+            .map(|flip| {
+                if win.wins(flip) {
+                    effect.on_flip_won(&mut self.game.board)
+                } else {
+                    effect.on_flip_lost(&mut self.game.board)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let activation = self.game.activations;
+        let thumbs = self.game.board.thumbs;
+        self.game.activations += 1;
+        self.game.flipped_in_upkeep(source);
+        let outcomes = flips.iter().map(|f| win.wins(f)).collect::<Vec<_>>();
+        let wins = outcomes.iter().filter(|&&won| won).count();
+        for record in flips
+            .into_iter()
+            .with_provenance(source, win, activation, thumbs)
+        {
+            self.game.history.push(record);
+        }
+        let start = self.game.history.len() - outcomes.len();
+        let event = EngineEvent::Flipped(&self.game.history[start..]);
+        self.events.publish(&event);
+        #[cfg(feature = "scripting")]
+        let output = {
+            let board = &mut self.game.board;
+            self.script.as_ref().map(|s| s.run(&event, board))
+        };
+        self.prune_history(PRUNE_SLACK);
 
-        if filtered_iter.peek().is_none() {
-            return false;
+        let streak = self.stats.borrow().current_streak;
+        let milestone = streak / STREAK_MILESTONE > streak_before / STREAK_MILESTONE;
+        if milestone && self.batch_outcomes.is_some() {
+            self.batch_streaks.push(streak);
+        } else if milestone {
+            self.announcement.push(' ');
+            self.announcement
+                .push_str(&lang.fmt("announce_streak", &[&streak]));
         }
+        if let Some(threshold) = self.settings.celebration_streak {
+            if streak_before < threshold && streak >= threshold {
+                self.celebrations += 1;
+                self.celebrated_streak = streak;
+            }
+        }
+        if let Some(batch) = &mut self.batch_outcomes {
+            batch.extend_from_slice(&outcomes);
+        } else if self.settings.haptics {
+            if milestone {
+                haptics::streak();
+            } else {
+                haptics::flips(&outcomes);
+            }
+        }
+        self.offer_record(RecordKind::BestStreak, streak as u64);
+        if let Some(doublings) = &mut self.game.board.okaun {
+            *doublings += wins as u32;
+            let doublings = *doublings;
+            self.offer_record(RecordKind::BiggestOkaun, u64::from(doublings));
+        }
+        for note in notes {
+            self.log_note(note);
+        }
+        #[cfg(feature = "scripting")]
+        self.log_script(output);
+    }
 
-        filtered_iter.all(|e| e.completed)
+    /// Compiles the saved script when scripts are on, or drops it.
+    #[cfg(feature = "scripting")]
+    fn compile_script(&mut self) {
+        self.script = None;
+        self.script_error = None;
+        if !self.settings.scripts_enabled || self.settings.script.trim().is_empty() {
+            return;
+        }
+        match Script::compile(&self.settings.script) {
+            Ok(script) => self.script = Some(script),
+            Err(error) => self.script_error = Some(error),
+        }
     }
 
-    fn toggle_all(&mut self, value: bool) {
-        for entry in self.entries.iter_mut() {
-            if self.filter.fit(entry) {
-                entry.completed = value;
+    #[cfg(not(feature = "scripting"))]
+    fn compile_script(&mut self) {}
+
+    /// Adds what the script printed, or why it failed, to the table log.
+    #[cfg(feature = "scripting")]
+    fn log_script(&mut self, output: Option<Result<Vec<String>, String>>) {
+        match output {
+            Some(Ok(lines)) => self.game.table_log.extend(lines),
+            Some(Err(error)) => {
+                let event = self.settings.language.fmt("script_error", &[&error]);
+                self.game.table_log.push(event);
             }
+            None => {}
         }
     }
 
-    fn clear_completed(&mut self) {
-        let entries = self
-            .entries
-            .drain(..)
-            .filter(|e| Filter::Active.fit(e))
-            .collect();
-        self.entries = entries;
+    /// Runs `hook` for every card in play, built in or custom, and logs
+    /// what they did.
+    fn run_effects(&mut self, hook: impl Fn(&dyn CardEffect, &mut Board) -> Option<Note>) {
+        let mut notes = Vec::new();
+        for effect in self.game.board.effects() {
+            notes.push(hook(effect, &mut self.game.board));
+        }
+        for card in &self.custom_cards {
+            notes.push(hook(card, &mut self.game.board));
+        }
+        for note in notes {
+            self.log_note(note);
+        }
     }
 
-    fn toggle(&mut self, idx: usize) {
-        let filter = self.filter.clone();
-        let mut entries = self
-            .entries
-            .iter_mut()
-            .filter(|e| filter.fit(e))
+    /// Adds what a card's effect did to the table log and announces it.
+    fn log_note(&mut self, note: Option<Note>) {
+        let note = match note {
+            Some(note) => note,
+            None => return,
+        };
+        let args = note
+            .args
+            .iter()
+            .map(|arg| arg as &dyn std::fmt::Display)
             .collect::<Vec<_>>();
-        let entry = entries.get_mut(idx).unwrap();
-        entry.completed = !entry.completed;
+        let event = self.settings.language.fmt(note.key, &args);
+        self.announcement = format!("{} {}", self.announcement, event);
+        self.game.table_log.push(event);
+    }
+
+    /// Renders a panel unless the user has hidden it.
+    fn panel(&self, panel: Panel, view: impl FnOnce() -> Html) -> Html {
+        if panel.is_built() && self.settings.shows(panel) {
+            view()
+        } else {
+            html! {}
+        }
+    }
+
+    /// One tab per open game, with buttons to open and close games.
+    fn view_games(&self) -> Html {
+        let lang = self.settings.language;
+        let closable = self.games.len() > 1;
+        html! {
+            <nav class="game-tabs" aria-label=lang.t("games")>
+                { for self.games.iter().enumerate().map(|(idx, game)| {
+                    let active = idx == self.active_game;
+                    let name = if active { &self.game.name } else { &game.name };
+                    html! {
+                        <span class=if active { "game-tab active" } else { "game-tab" }>
+                            <button aria-pressed=active.to_string()
+                                    onclick=self.link.callback(move |_| Msg::SwitchGame(idx))>{ name }</button>
+                            { if closable {
+                                html! {
+                                    <button class="destroy"
+                                            aria-label=lang.fmt("close_game", &[name])
+                                            onclick=self.link.callback(move |_| Msg::CloseGame(idx))>{ "×" }</button>
+                                }
+                            } else {
+                                html! {}
+                            } }
+                        </span>
+                    }
+                }) }
+                <button class="new-game"
+                        aria-label=lang.t("new_game")
+                        onclick=self.link.callback(|_| Msg::NewGame)>{ "+" }</button>
+                <button class="snapshot-link-button"
+                        onclick=self.link.callback(|_| Msg::ShareSnapshot)>{ lang.t("share_snapshot") }</button>
+                { match &self.snapshot_link {
+                    Some(link) => html! {
+                        <label class="snapshot-link">
+                            { lang.t("snapshot_link_hint") }
+                            <input readonly=true value=link />
+                        </label>
+                    },
+                    None => html! {},
+                } }
+                { for self.snapshot_qr_link.iter().map(|link| html! {
+                    <QrImage language=lang text=link.clone() />
+                }) }
+            </nav>
+        }
+    }
+
+    /// The game from a snapshot link, with nothing that could change it.
+    fn view_spectator(&self) -> Html {
+        let lang = self.settings.language;
+        let board = &self.game.board;
+        html! {
+            <>
+                <header class="snapshot-banner">
+                    <h2>{ lang.fmt("snapshot_of", &[&self.game.name]) }</h2>
+                    <button onclick=self.link.callback(|_| Msg::StopSpectating)>{ lang.t("stop_spectating") }</button>
+                </header>
+                { self.view_stats() }
+                <dl class="snapshot-board">
+                    <dt>{ lang.t("life") }</dt>
+                    <dd>{ board.life }</dd>
+                    <dt>{ lang.t("cards_in_hand") }</dt>
+                    <dd>{ board.hand }</dd>
+                    <dt>{ lang.t("mana_pool") }</dt>
+                    <dd>{ board.mana }</dd>
+                </dl>
+                <ul class="snapshot-opponents">
+                    { for self.game.opponents.iter().map(|opponent| html! {
+                        <li>{ format!("{}: {}", opponent.name, opponent.life) }</li>
+                    }) }
+                </ul>
+                <ol class="table-log">
+                    { for self.game.table_log.iter().map(|event| html! { <li>{ event }</li> }) }
+                </ol>
+                { self.view_history() }
+                { self.view_chain_odds() }
+            </>
+        }
+    }
+
+    fn view_controls(&self) -> Html {
+        // Fast-forward sends its flips as one batch, which Yew handles
+        // before rendering once.
+        html! {
+            <>
+                <ControlPanel language=self.settings.language
+                              max_times=MAX_CHAIN
+                              can_undo=!self.undo.is_empty()
+                              listening=self.voice.is_some()
+                              settings_open=self.settings_open
+                              on_flip=self.callbacks.flip_many.clone()
+                              on_end_turn=self.callbacks.end_turn.clone()
+                              on_undo=self.callbacks.undo.clone()
+                              on_toggle_voice=self.callbacks.toggle_voice.clone()
+                              on_save=self.callbacks.save_session.clone()
+                              on_clear=self.callbacks.clear_history.clone()
+                              on_toggle_settings=self.callbacks.toggle_settings.clone()>
+                    { self.view_flip_source() }
+                </ControlPanel>
+                { self.view_upkeep_warning() }
+                { for self.state_error.map(|error| html! {
+                    <p class="error" role="alert">{ self.settings.language.t(error.as_ref()) }</p>
+                }) }
+            </>
+        }
+    }
+
+    /// The card to flip for, and who flips for combat cards.
+    fn view_flip_source(&self) -> Html {
+        let lang = self.settings.language;
+        html! {
+            <>
+                <select aria-label=lang.t("flip_source")
+                        onchange=self.link.callback(|e: ChangeData| match e {
+                    ChangeData::Select(select) => FlipCard::from_str(&select.value())
+                        .map(Msg::SetFlipSource)
+                        .unwrap_or(Msg::Nope),
+                    _ => Msg::Nope,
+                })>
+                    { for FlipCard::iter().filter(|&card| card != FlipCard::Homebrew && card != FlipCard::RiskyMove).map(|card| html! {
+                        <option value=card selected=card == self.flip_source>{ card }</option>
+                    }) }
+                </select>
+                { self.view_combat() }
+            </>
+        }
+    }
+
+    /// Who controls Risky Move, passing it on each upkeep, and the flip
+    /// due when it changes hands.
+    fn view_risky_move(&self) -> Html {
+        let players = self.game.opponents.len() + 1;
+        html! {
+            <RiskyMovePanel language=self.settings.language
+                            risky_move=self.game.risky_move.clone()
+                            players=(0..players).map(|player| self.player_name(player)).collect::<Vec<_>>()
+                            creatures=self.game.board.creatures.iter().map(|c| c.name.clone()).collect::<Vec<_>>()
+                            on_change=self.callbacks.set_risky_move.clone()
+                            on_upkeep=self.callbacks.risky_move_upkeep.clone()
+                            on_flip=self.callbacks.flip_risky_move.clone() />
+        }
+    }
+
+    /// Who flips and who fights, for combat cards like Tide of War.
+    fn view_combat(&self) -> Html {
+        if self.flip_source.combat().is_none() {
+            return html! {};
+        }
+        let lang = self.settings.language;
+        let players = self.game.opponents.len() + 1;
+        html! {
+            <span class="combat">
+                { for Role::iter().map(|role| {
+                    let current = self.game.combat.player(role);
+                    html! {
+                        <label>
+                            { lang.t(role.as_ref()) }
+                            <select onchange=self.link.callback(move |e: ChangeData| match e {
+                                ChangeData::Select(select) => select
+                                    .value()
+                                    .parse()
+                                    .map(|player| Msg::SetCombatPlayer(role, player))
+                                    .unwrap_or(Msg::Nope),
+                                _ => Msg::Nope,
+                            })>
+                                { for (0..players).map(|player| html! {
+                                    <option value=player selected=player == current>
+                                        { self.player_name(player) }
+                                    </option>
+                                }) }
+                            </select>
+                        </label>
+                    }
+                }) }
+            </span>
+        }
+    }
+
+    fn view_board(&self) -> Html {
+        let lang = self.settings.language;
+        html! {
+            <div class="board">
+                <CountInput language=lang
+                            label=lang.t("krarks")
+                            value=self.game.board.krarks
+                            on_change=self.callbacks.set_krarks.clone() />
+                <CountInput language=lang
+                            label=lang.t("thumbs")
+                            value=self.game.board.thumbs
+                            on_change=self.callbacks.set_thumbs.clone() />
+                <SeedInput language=lang
+                           seed=self.game.seed
+                           on_change=self.callbacks.set_seed.clone() />
+                <label>
+                    { lang.t("payoffs") }
+                    <input type="number" min="0"
+                           value=self.game.board.payoffs
+                           oninput=self.link.callback(|e: InputData| Msg::SetPayoffs(e.value)) />
+                </label>
+                <label>
+                    <input type="checkbox"
+                           checked=self.game.board.okaun.is_some()
+                           onclick=self.link.callback(|_| Msg::ToggleOkaun) />
+                    { lang.t("okaun") }
+                </label>
+                { for self.game.board.okaun.map(|doublings| html! {
+                    <span class="okaun-size">{ okaun_size(doublings) }</span>
+                }) }
+                { self.view_risky_move() }
+                <button class="snapshot" onclick=self.link.callback(|_| Msg::TakeSnapshot)>
+                    { lang.t("take_snapshot") }
+                </button>
+            </div>
+        }
+    }
+
+    fn view_life(&self) -> Html {
+        let lang = self.settings.language;
+        html! {
+            <div class="life">
+                <button aria-label=lang.t("life_down")
+                        onclick=self.link.callback(|_| Msg::AdjustLife(-1))>{ "−" }</button>
+                <label>
+                    { lang.t("life") }
+                    <input type="number"
+                           value=self.game.board.life
+                           oninput=self.link.callback(|e: InputData| Msg::SetLife(e.value)) />
+                </label>
+                <button aria-label=lang.t("life_up")
+                        onclick=self.link.callback(|_| Msg::AdjustLife(1))>{ "+" }</button>
+                { for self.held(0).iter().map(|key| html! {
+                    <span class="designation-badge">{ lang.t(key) }</span>
+                }) }
+                <label>
+                    <input type="checkbox"
+                           checked=self.game.board.aetherflux
+                           onclick=self.link.callback(|_| Msg::ToggleAetherflux) />
+                    { lang.t("aetherflux") }
+                </label>
+                { if self.game.board.aetherflux {
+                    html! {
+                        <button class="aetherflux-fire"
+                                disabled=!self.game.board.aetherflux_ready()
+                                onclick=self.link.callback(|_| Msg::FireAetherflux)>
+                            { lang.fmt("aetherflux_fire", &[&AETHERFLUX_COST]) }
+                        </button>
+                    }
+                } else {
+                    html! {}
+                } }
+            </div>
+        }
+    }
+
+    /// Tracks opponents' life totals and whether the chosen storm payoff,
+    /// cast next, would finish each of them.
+    fn view_opponents(&self) -> Html {
+        let lang = self.settings.language;
+        let payoff = self.storm_payoff;
+        let total = match self.game.board.effective_storm(self.game.storm) {
+            Some(storm) => payoff.total(storm).to_string(),
+            None => "∞".to_string(),
+        };
+        html! {
+            <div class="opponents">
+                <label>
+                    { lang.t("storm_payoff") }
+                    <select onchange=self.link.callback(|e: ChangeData| match e {
+                        ChangeData::Select(select) => StormPayoff::from_str(&select.value())
+                            .map(Msg::SetStormPayoff)
+                            .unwrap_or(Msg::Nope),
+                        _ => Msg::Nope,
+                    })>
+                        { for StormPayoff::iter().map(|p| html! {
+                            <option value=p selected=p == payoff>{ p }</option>
+                        }) }
+                    </select>
+                </label>
+                <span class="payoff-total">{ lang.fmt("payoff_total", &[&total]) }</span>
+                <ul>
+                    { for self.game.opponents.iter().enumerate().map(|(idx, opponent)| self.view_opponent(idx, opponent)) }
+                </ul>
+                { self.view_pending_pings() }
+                <NameForm class="opponent-name"
+                          label=lang.t("opponent_name")
+                          button=lang.t("add_opponent")
+                          on_submit=self.callbacks.add_opponent.clone() />
+                { self.view_table_designations() }
+                <div class="pregame">
+                    <label>
+                        <input type="checkbox"
+                               checked=self.seat_randomly
+                               onclick=self.link.callback(|_| Msg::ToggleRandomSeating) />
+                        { lang.t("random_seating") }
+                    </label>
+                    <button onclick=self.link.callback(|_| Msg::RollForFirst)>{ lang.t("roll_for_first") }</button>
+                </div>
+            </div>
+        }
+    }
+
+    /// Creatures pumped by prowess or magecraft, at their size after this
+    /// turn's casts.
+    fn view_creatures(&self) -> Html {
+        html! {
+            <CreatureList language=self.settings.language
+                          creatures=self.game.board.creatures.clone()
+                          on_change=self.callbacks.set_creatures.clone() />
+        }
+    }
+
+    fn view_dice(&self) -> Html {
+        html! {
+            <>
+                <DiceRoller language=self.settings.language
+                            last_roll=self.game.last_roll.clone()
+                            on_roll=self.callbacks.roll.clone() />
+                <D20Card language=self.settings.language
+                         rolled=self.d20_band
+                         on_roll=self.callbacks.roll_d20.clone() />
+                { self.view_celebr_8000() }
+                { self.view_puzzlebox() }
+                { self.view_barbarian_class() }
+                <label class="wyll">
+                    <input type="checkbox"
+                           checked=self.game.board.creatures.iter().any(Creature::is_wyll)
+                           onclick=self.link.callback(|_| Msg::ToggleWyll) />
+                    { WYLL }
+                </label>
+            </>
+        }
+    }
+
+    /// Vexing Puzzlebox's charge counters, fed by every roll while it is
+    /// on the battlefield.
+    fn view_puzzlebox(&self) -> Html {
+        let lang = self.settings.language;
+        let ready = self
+            .game
+            .board
+            .puzzlebox
+            .is_some_and(|charge| charge >= PUZZLEBOX_GOAL);
+        html! {
+            <div class="puzzlebox">
+                <label>
+                    <input type="checkbox"
+                           checked=self.game.board.puzzlebox.is_some()
+                           onclick=self.link.callback(|_| Msg::TogglePuzzlebox) />
+                    { lang.t("puzzlebox") }
+                </label>
+                { match self.game.board.puzzlebox {
+                    Some(charge) => html! {
+                        <>
+                            <progress max=PUZZLEBOX_GOAL value=charge.min(PUZZLEBOX_GOAL)
+                                      aria-label=lang.t("puzzlebox") />
+                            <span>{ lang.fmt("puzzlebox_charge", &[&charge, &PUZZLEBOX_GOAL]) }</span>
+                            <button disabled=!ready
+                                    onclick=self.link.callback(|_| Msg::FetchWithPuzzlebox)>
+                                { lang.t("puzzlebox_fetch") }
+                            </button>
+                        </>
+                    },
+                    None => html! {},
+                } }
+            </div>
+        }
+    }
+
+    /// Barbarian Class's level, with the abilities it has gained so far
+    /// highlighted.
+    fn view_barbarian_class(&self) -> Html {
+        let lang = self.settings.language;
+        let level = self.game.board.barbarian_class.unwrap_or(0);
+        html! {
+            <div class="barbarian-class">
+                <label>
+                    { lang.t("barbarian_class") }
+                    <select onchange=self.link.callback(|e: ChangeData| match e {
+                        ChangeData::Select(select) => {
+                            Msg::SetBarbarianClass(select.value().parse().ok().filter(|&level| level > 0))
+                        }
+                        _ => Msg::Nope,
+                    })>
+                        <option value="0" selected=level == 0>{ lang.t("not_in_play") }</option>
+                        { for (1..=BARBARIAN_CLASS_ABILITIES.len() as u32).map(|n| html! {
+                            <option value=n selected=n == level>{ lang.fmt("class_level", &[&n]) }</option>
+                        }) }
+                    </select>
+                </label>
+                <ol>
+                    { for BARBARIAN_CLASS_ABILITIES.iter().zip(1..).map(|(key, n)| {
+                        let class = if n > level { "" } else { "active" };
+                        html! { <li class=class>{ lang.t(key) }</li> }
+                    }) }
+                </ol>
+            </div>
+        }
+    }
+
+    /// Celebr-8000's bonuses as a checklist, ticked for this turn's roll.
+    fn view_celebr_8000(&self) -> Html {
+        let lang = self.settings.language;
+        let active = self.game.celebr_bonuses.as_deref().unwrap_or_default();
+        html! {
+            <div class="celebr-8000">
+                <button onclick=self.link.callback(|_| Msg::RollCelebr8000)>{ lang.t("roll_celebr") }</button>
+                <ol>
+                    { for CELEBR_8000_BONUSES.iter().zip(1..).map(|(key, face)| {
+                        let on = active.contains(&face);
+                        html! {
+                            <li class=if on { "active" } else { "" }>
+                                { if on { "☑ " } else { "☐ " } }{ face }{ " — " }{ lang.t(key) }
+                            </li>
+                        }
+                    }) }
+                </ol>
+            </div>
+        }
     }
 
-    fn toggle_edit(&mut self, idx: usize) {
-        let filter = self.filter.clone();
-        let mut entries = self
-            .entries
-            .iter_mut()
-            .filter(|e| filter.fit(e))
+    fn view_archaeologist(&self) -> Html {
+        html! {
+            <ArchaeologistPanel language=self.settings.language
+                                archaeologist=self.game.board.archaeologist.clone()
+                                on_change=self.callbacks.set_archaeologist.clone()
+                                on_activate=self.callbacks.activate_archaeologist.clone() />
+        }
+    }
+
+    /// Loaded custom cards with a button to flip for each, and a box to
+    /// paste more definitions into.
+    fn view_custom_cards(&self) -> Html {
+        html! {
+            <CustomCardList language=self.settings.language
+                            cards=self.custom_cards.clone()
+                            on_change=self.callbacks.set_custom_cards.clone()
+                            on_flip=self.callbacks.flip_custom_card.clone() />
+        }
+    }
+
+    /// The imported decklist, a box to paste a new one into, and the
+    /// mulligan trainer once there is a deck to deal from.
+    fn view_mulligan(&self) -> Html {
+        let lang = self.settings.language;
+        html! {
+            <div class="decklist">
+                { match &self.decklist {
+                    Some(deck) => html! {
+                        <>
+                            <p>{ lang.fmt("deck_size", &[&deck.size(), &deck.cards.len()]) }</p>
+                            <MulliganTrainer language=lang
+                                             deck=deck.clone()
+                                             seed=self.game.seed.unwrap_or_default() />
+                        </>
+                    },
+                    None => html! { <p>{ lang.t("deck_none") }</p> },
+                } }
+                <DeckImport language=lang on_import=self.callbacks.import_deck.clone() />
+            </div>
+        }
+    }
+
+    /// Panels for simulating, exploring seeds, scripting and debugging.
+    fn view_tools(&self) -> Html {
+        let lang = self.settings.language;
+        html! {
+            <>
+                { self.panel(Panel::Simulator, || html! {
+                    <Simulator language=lang krarks=self.game.board.krarks thumbs=self.game.board.thumbs />
+                }) }
+                { self.panel(Panel::Mulligan, || self.view_mulligan()) }
+                { self.panel(Panel::DrawOdds, || html! {
+                    <DrawOdds language=lang deck=self.decklist.clone() />
+                }) }
+                { self.panel(Panel::SeedExplorer, || html! {
+                    <SeedExplorer language=lang thumbs=self.game.board.thumbs />
+                }) }
+                { self.panel(Panel::Chat, || self.view_chat()) }
+                { self.panel(Panel::Scripts, || self.view_scripts()) }
+                { self.panel(Panel::Debug, || self.view_debug()) }
+            </>
+        }
+    }
+
+    #[cfg(feature = "networking")]
+    fn view_chat(&self) -> Html {
+        html! { <Chat language=self.settings.language /> }
+    }
+
+    #[cfg(not(feature = "networking"))]
+    fn view_chat(&self) -> Html {
+        html! {}
+    }
+
+    fn view_scripts(&self) -> Html {
+        if cfg!(not(feature = "scripting")) {
+            return html! {};
+        }
+        html! {
+            <ScriptEditor language=self.settings.language
+                          enabled=self.settings.scripts_enabled
+                          source=self.settings.script.clone()
+                          error=self.script_error.clone()
+                          on_toggle=self.callbacks.toggle_scripts.clone()
+                          on_load=self.callbacks.load_script.clone() />
+        }
+    }
+
+    fn view_attractions(&self) -> Html {
+        html! {
+            <AttractionsPanel language=self.settings.language
+                              attractions=self.game.board.attractions.clone()
+                              on_change=self.callbacks.set_attractions.clone()
+                              on_roll=self.callbacks.roll_to_visit.clone() />
+        }
+    }
+
+    fn view_unfinity(&self) -> Html {
+        html! {
+            <UnfinityPanel language=self.settings.language
+                           unfinity=self.unfinity.clone()
+                           on_change=self.callbacks.set_unfinity.clone() />
+        }
+    }
+
+    /// Poison, energy, experience and designations for every player, with
+    /// anyone at lethal poison highlighted.
+    fn view_player_counters(&self) -> Html {
+        let lang = self.settings.language;
+        let players = std::iter::once((
+            None,
+            lang.t("you"),
+            self.game.counters,
+            &self.game.designations,
+        ))
+        .chain(
+            self.game
+                .opponents
+                .iter()
+                .enumerate()
+                .map(|(idx, o)| (Some(idx), o.name.as_str(), o.counters, &o.designations)),
+        );
+        html! {
+            <table class="player-counters">
+                <thead>
+                    <tr>
+                        <th>{ lang.t("player") }</th>
+                        { for PlayerCounter::iter().map(|kind| html! { <th>{ lang.t(kind.as_ref()) }</th> }) }
+                        <th>{ lang.t("designations") }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for players.map(|(player, name, counters, designations)| {
+                        self.view_player_counters_row(player, name, counters, designations)
+                    }) }
+                </tbody>
+            </table>
+        }
+    }
+
+    fn view_player_counters_row(
+        &self,
+        player: Option<usize>,
+        name: &str,
+        counters: PlayerCounters,
+        designations: &[Designation],
+    ) -> Html {
+        let lang = self.settings.language;
+        html! {
+            <tr class=if counters.poisoned() { "poisoned" } else { "" }>
+                <td>
+                    { name }
+                    { if counters.poisoned() {
+                        html! { <span class="poisoned-badge">{ lang.t("poisoned") }</span> }
+                    } else {
+                        html! {}
+                    } }
+                </td>
+                { for PlayerCounter::iter().map(|kind| {
+                    let counter = lang.t(kind.as_ref());
+                    html! {
+                        <td>
+                            <Counter label=""
+                                     value=i64::from(counters.get(kind))
+                                     decrement_label=lang.fmt("counter_down", &[&name, &counter])
+                                     increment_label=lang.fmt("counter_up", &[&name, &counter])
+                                     on_step=self.link.callback(move |step| Msg::AdjustCounter(player, kind, step)) />
+                        </td>
+                    }
+                }) }
+                { self.view_designation_toggles(player, designations) }
+            </tr>
+        }
+    }
+
+    /// A switch per designation for one player's row.
+    fn view_designation_toggles(
+        &self,
+        player: Option<usize>,
+        designations: &[Designation],
+    ) -> Html {
+        let lang = self.settings.language;
+        html! {
+            <td class="designations">
+                { for Designation::iter().map(|designation| {
+                    let on = designations.contains(&designation);
+                    html! {
+                        <button class=if on { "designation on" } else { "designation" }
+                                aria-pressed=on.to_string()
+                                onclick=self.link.callback(move |_| Msg::ToggleDesignation(player, designation))>
+                            { lang.t(designation.as_ref()) }
+                        </button>
+                    }
+                }) }
+            </td>
+        }
+    }
+
+    /// Hand sizes around the table, with one-tap wheels.
+    fn view_hands(&self) -> Html {
+        let lang = self.settings.language;
+        let players = std::iter::once((None, lang.t("you"), self.game.board.hand)).chain(
+            self.game
+                .opponents
+                .iter()
+                .enumerate()
+                .map(|(idx, o)| (Some(idx), o.name.as_str(), o.hand)),
+        );
+        html! {
+            <div class="hands">
+                <ul>
+                    { for players.map(|(player, name, hand)| html! {
+                        <li>
+                            <Counter label=name
+                                     value=i64::from(hand)
+                                     decrement_label=lang.fmt("hand_down", &[&name])
+                                     increment_label=lang.fmt("hand_up", &[&name])
+                                     on_step=self.link.callback(move |step| Msg::AdjustHand(player, step)) />
+                        </li>
+                    }) }
+                </ul>
+                <label>
+                    { lang.t("draw_pingers") }
+                    <input type="number" min="0"
+                           value=self.game.board.draw_pingers
+                           oninput=self.link.callback(|e: InputData| Msg::SetDrawPingers(e.value)) />
+                </label>
+                <button class="draw" onclick=self.link.callback(|_| Msg::Draw)>{ lang.t("draw_card") }</button>
+                { self.view_draws() }
+                { for Wheel::iter().map(|wheel| html! {
+                    <button class="wheel" onclick=self.link.callback(move |_| Msg::Wheel(wheel))>{ wheel }</button>
+                }) }
+                <ol class="table-log">
+                    { for self.game.table_log.iter().map(|event| html! { <li>{ event }</li> }) }
+                </ol>
+                <button class="export-report" onclick=self.link.callback(|_| Msg::ExportBattleReport)>
+                    { lang.t("export_report") }
+                </button>
+            </div>
+        }
+    }
+
+    /// Cards drawn this game and what is left in your library.
+    fn view_draws(&self) -> Html {
+        let lang = self.settings.language;
+        let board = &self.game.board;
+        let left = board.library_left(self.library_size());
+        let placeholder = self
+            .library_size()
+            .map(|size| size.to_string())
+            .unwrap_or_default();
+        html! {
+            <div class="draws">
+                <span class="drawn">{ lang.fmt("cards_drawn", &[&board.drawn]) }</span>
+                <label>
+                    { lang.t("library") }
+                    <input type="number" min="0"
+                           placeholder=placeholder
+                           value=board.library.map(|size| size.to_string()).unwrap_or_default()
+                           oninput=self.link.callback(|e: InputData| Msg::SetLibrary(e.value)) />
+                </label>
+                { match left {
+                    Some(0) => html! {
+                        <span class="decked-badge" role="alert">{ outcome_icon(false) }{ lang.t("library_empty") }</span>
+                    },
+                    Some(left) if left <= LOW_LIBRARY => html! {
+                        <span class="library-low" role="alert">{ lang.fmt("library_low", &[&left]) }</span>
+                    },
+                    Some(left) => html! { <span class="library-left">{ lang.fmt("library_left", &[&left]) }</span> },
+                    None => html! {},
+                } }
+            </div>
+        }
+    }
+
+    fn view_tour(&self) -> Html {
+        if !self.touring {
+            return html! {};
+        }
+        let lang = self.settings.language;
+        html! {
+            <Tour language=lang on_finish=self.callbacks.finish_tour.clone() />
+        }
+    }
+
+    fn view_celebration(&self) -> Html {
+        html! {
+            <Celebration language=self.settings.language
+                         bursts=self.celebrations
+                         streak=self.celebrated_streak
+                         reduced_motion=self.settings.reduced_motion() />
+        }
+    }
+
+    fn view_turn_timer(&self) -> Html {
+        let lang = self.settings.language;
+        let players = std::iter::once(lang.t("you").to_string())
+            .chain(self.game.opponents.iter().map(|o| o.name.clone()))
             .collect::<Vec<_>>();
-        let entry = entries.get_mut(idx).unwrap();
-        entry.editing = !entry.editing;
+        html! {
+            <TurnTimer language=lang
+                       players=players
+                       first=self.game.first_player.unwrap_or(0)
+                       turn=self.game.turn_storms.len() />
+        }
+    }
+
+    fn view_pending_pings(&self) -> Html {
+        let pending: u32 = self.game.opponents.iter().map(|o| o.pending_damage).sum();
+        if pending == 0 {
+            return html! {};
+        }
+        html! {
+            <button class="apply-pings" onclick=self.link.callback(|_| Msg::ApplyPings)>
+                { self.settings.language.fmt("apply_pings", &[&pending]) }
+            </button>
+        }
+    }
+
+    fn view_opponent(&self, idx: usize, opponent: &Opponent) -> Html {
+        let lethal = self
+            .storm_payoff
+            .is_lethal(self.game.board.effective_storm(self.game.storm), opponent);
+        html! {
+            <OpponentRow language=self.settings.language
+                         idx=idx
+                         opponent=opponent.clone()
+                         targeted=self.game.target == Some(idx)
+                         lethal=lethal
+                         held=self.held(idx + 1)
+                         on_change=self.callbacks.set_opponent.clone()
+                         on_target=self.callbacks.set_target.clone()
+                         on_remove=self.callbacks.remove_opponent.clone() />
+        }
+    }
+
+    /// The mana pool, and the cost reducers the cast workflow applies
+    /// before paying from it.
+    fn view_mana(&self) -> Html {
+        let lang = self.settings.language;
+        html! {
+            <div class="mana">
+                <ManaPool language=lang
+                          mana=self.game.board.mana
+                          infinite=self.game.board.infinite_mana.clone()
+                          on_mana=self.callbacks.set_mana.clone()
+                          on_infinite=self.callbacks.set_infinite_mana.clone() />
+                <ReducerList language=lang
+                             reducers=self.game.board.reducers.clone()
+                             on_change=self.callbacks.set_reducers.clone() />
+                { self.view_rituals() }
+                { self.view_turn_casts() }
+            </div>
+        }
+    }
+
+    fn view_rituals(&self) -> Html {
+        let lang = self.settings.language;
+        html! {
+            <div class="rituals">
+                <label>
+                    { lang.t("ritual_x") }
+                    <input type="number" min="0"
+                           value=&self.ritual_x
+                           oninput=self.link.callback(|e: InputData| Msg::UpdateRitualX(e.value)) />
+                </label>
+                { for Ritual::iter().map(|ritual| html! {
+                    <button class="ritual"
+                            title=if ritual.is_variable() { lang.t("ritual_uses_x") } else { "" }
+                            onclick=self.link.callback(move |_| Msg::CastRitual(ritual))>
+                        { ritual }
+                    </button>
+                }) }
+            </div>
+        }
+    }
+
+    /// Logs this turn's casts with the mana each made or cost.
+    fn view_turn_casts(&self) -> Html {
+        let lang = self.settings.language;
+        let net: i64 = self.game.turn_casts.iter().map(|(_, mana)| mana).sum();
+        html! {
+            <div class="turn-casts">
+                <ol>
+                    { for self.game.turn_casts.iter().map(|(name, mana)| html! {
+                        <li>{ name }{ ": " }{ format!("{:+}", mana) }</li>
+                    }) }
+                </ol>
+                <span class="turn-net-mana">{ lang.fmt("turn_net_mana", &[&format!("{:+}", net)]) }</span>
+            </div>
+        }
+    }
+
+    fn view_tokens(&self) -> Html {
+        let lang = self.settings.language;
+        let can_boost = !self.big_idea_boosted && self.brainiacs() >= BIG_IDEA_BRAINIACS;
+        html! {
+            <div class="tokens">
+                <TokenList language=lang
+                           tokens=self.game.board.tokens.clone()
+                           on_change=self.callbacks.set_tokens.clone()
+                           on_create=self.callbacks.create_tokens.clone() />
+                <button class="big-idea" onclick=self.link.callback(|_| Msg::BigIdea)>
+                    { lang.t(if self.big_idea_boosted { "big_idea_boosted" } else { "big_idea" }) }
+                </button>
+                <button disabled=!can_boost
+                        onclick=self.link.callback(|_| Msg::BoostBigIdea)>
+                    { lang.fmt("big_idea_boost", &[&BIG_IDEA_BRAINIACS]) }
+                </button>
+            </div>
+        }
     }
 
-    fn complete_edit(&mut self, idx: usize, val: String) {
-        let filter = self.filter.clone();
-        let mut entries = self
-            .entries
-            .iter_mut()
-            .filter(|e| filter.fit(e))
+    /// Lists the registered spells with the expected value of casting each
+    /// one against the current board.
+    fn view_spells(&self) -> Html {
+        html! {
+            <SpellTable language=self.settings.language
+                        spells=self.game.spells.clone()
+                        board=self.game.board.clone()
+                        on_change=self.callbacks.set_spells.clone()
+                        on_cast=self.callbacks.cast.clone()
+                        on_cast_until=self.callbacks.cast_until.clone() />
+        }
+    }
+
+    fn view_stats(&self) -> Html {
+        let (flips, wins) = {
+            let stats = self.stats.borrow();
+            (stats.flips, stats.wins)
+        };
+        html! {
+            <StatsPanel language=self.settings.language
+                        storm=self.game.storm
+                        copies=self.game.copies
+                        day_night=self.game.day_night
+                        flips=flips
+                        wins=wins
+                        history=self.game.history.clone()
+                        faces=self.settings.coin_faces.clone()
+                        pace=self.game.pace.clone()
+                        on_cycle_day_night=self.callbacks.cycle_day_night.clone() />
+        }
+    }
+
+    fn view_card_stats(&self) -> Html {
+        let stats = self.stats.borrow();
+        let pace = &self.game.pace;
+        let turn_times = pace
+            .by_player()
+            .into_iter()
+            .map(|(player, millis)| (self.player_name(player), millis))
             .collect::<Vec<_>>();
-        let entry = entries.get_mut(idx).unwrap();
-        entry.description = val;
-        entry.editing = !entry.editing;
+        html! {
+            <StatsTables language=self.settings.language
+                         cards=stats.card_stats().to_vec()
+                         players=stats.player_stats().to_vec()
+                         turn_times=turn_times
+                         average_turn=pace.average_turn() />
+        }
+    }
+
+    fn view_run_lengths(&self) -> Html {
+        html! {
+            <RunLengths language=self.settings.language
+                        counts=self.stats.borrow().streak_counts.clone()
+                        thumbs=self.game.board.thumbs />
+        }
+    }
+
+    fn view_history(&self) -> Html {
+        let lang = self.settings.language;
+        html! {
+            <>
+                <FlipLog language=lang
+                         history=self.game.history.clone()
+                         activations=self.game.activations
+                         selected=self.selected_chain
+                         faces=self.settings.coin_faces.clone()
+                         tosses=self.tosses.clone()
+                         toss_call=self.toss_call
+                         on_select=self.callbacks.select_chain.clone() />
+                <div class="session-export">
+                    <button onclick=self.link.callback(|_| Msg::ExportSessionCsv)>{ lang.t("export_csv") }</button>
+                    <button onclick=self.link.callback(|_| Msg::ExportSessionJson)>{ lang.t("export_json") }</button>
+                    <SessionImport language=lang on_import=self.callbacks.import.clone() />
+                </div>
+            </>
+        }
+    }
+
+    fn view_resolution(&self) -> Html {
+        html! {
+            <ResolutionLog language=self.settings.language
+                           lines=self.step_lines.clone()
+                           cast=self.step_cast.clone()
+                           pending=self.step_pending.clone()
+                           can_flip={ self.game.board.krarks > 0 }
+                           on_flip=self.callbacks.step_flip.clone()
+                           on_keep=self.callbacks.keep_coin.clone()
+                           on_clear=self.callbacks.clear_resolution_log.clone() />
+        }
     }
 
-    fn remove(&mut self, idx: usize) {
-        let idx = {
-            let filter = self.filter.clone();
-            let entries = self
-                .entries
+    /// Shows how unlikely the chain selected in the history was.
+    fn view_chain_odds(&self) -> Html {
+        let chain = match self.selected_chain {
+            Some(activation) => self
+                .game
+                .history
                 .iter()
-                .enumerate()
-                .filter(|&(_, e)| filter.fit(e))
-                .collect::<Vec<_>>();
-            let &(idx, _) = entries.get(idx).unwrap();
-            idx
+                .filter(|r| r.player.is_none())
+                .group_by_trigger()
+                .find(|chain| chain[0].activation == activation)
+                .unwrap_or_default(),
+            None => return html! {},
         };
-        self.entries.remove(idx);
+        let lang = self.settings.language;
+        match ChainOdds::new(&chain) {
+            Some(odds) => html! {
+                <Modal language=lang
+                       title=lang.t("chain_odds")
+                       on_close=self.callbacks.close_chain.clone()>
+                    <div class="chain-odds">
+                        <p>{ lang.fmt("chain_summary", &[&odds.source, &odds.wins, &odds.flips]) }</p>
+                        <p>{ lang.fmt("exact_sequence", &[&percent(lang, odds.exact)]) }</p>
+                        <p>{ lang.fmt("at_least_as_extreme", &[&percent(lang, odds.at_least)]) }</p>
+                    </div>
+                </Modal>
+            },
+            None => html! {},
+        }
+    }
+
+    /// Every command the palette can run, with its label.
+    fn commands(&self) -> Vec<(Command, String)> {
+        let lang = self.settings.language;
+        let mut commands = vec![
+            (
+                Command::Flip,
+                format!("{}: {}", lang.t("flip"), self.flip_source),
+            ),
+            (Command::EndTurn, lang.t("end_turn").to_string()),
+            (Command::BigIdea, lang.t("big_idea").to_string()),
+            (Command::SaveSession, lang.t("save_session").to_string()),
+            (Command::ClearHistory, lang.t("clear").to_string()),
+            (Command::OpenSettings, lang.t("settings").to_string()),
+            (Command::StartTour, lang.t("tour_show").to_string()),
+        ];
+        commands.extend(self.game.spells.iter().enumerate().map(|(idx, spell)| {
+            (
+                Command::Cast(idx),
+                format!("{}: {}", lang.t("cast"), spell.name),
+            )
+        }));
+        commands.extend(Ritual::iter().map(|ritual| {
+            (
+                Command::CastRitual(ritual),
+                format!("{}: {}", lang.t("cast"), ritual),
+            )
+        }));
+        commands
+    }
+
+    fn view_palette(&self) -> Html {
+        if !self.palette_open {
+            return html! {};
+        }
+        let labels = self
+            .commands()
+            .into_iter()
+            .map(|(_, label)| label)
+            .collect::<Vec<_>>();
+        html! {
+            <CommandPalette language=self.settings.language
+                            commands=labels
+                            on_run=self.callbacks.run_command.clone()
+                            on_close=self.callbacks.close_palette.clone() />
+        }
+    }
+
+    fn view_settings(&self) -> Html {
+        if !self.settings_open {
+            return html! {};
+        }
+        html! {
+            <SettingsPanel settings=self.settings.clone()
+                           on_change=self.callbacks.change_settings.clone()
+                           on_start_tour=self.callbacks.start_tour.clone() />
+        }
+    }
+
+    /// Timings of updates, renders, flips and storage writes so far, to
+    /// spot slow frames on slow devices.
+    fn view_debug(&self) -> Html {
+        let lang = self.settings.language;
+        let ms = |value: f64| format!("{:.2}", value);
+        html! {
+            <div class="debug">
+                <table class="timings">
+                    <thead>
+                        <tr>
+                            <th>{ lang.t("timing_name") }</th>
+                            <th>{ lang.t("timing_count") }</th>
+                            <th>{ lang.t("timing_last") }</th>
+                            <th>{ lang.t("timing_mean") }</th>
+                            <th>{ lang.t("timing_max") }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for perf::timings().into_iter().map(|(name, timing)| html! {
+                            <tr>
+                                <td>{ name }</td>
+                                <td>{ timing.count }</td>
+                                <td>{ ms(timing.last) }</td>
+                                <td>{ ms(timing.mean()) }</td>
+                                <td>{ ms(timing.max) }</td>
+                            </tr>
+                        }) }
+                    </tbody>
+                </table>
+                <button onclick=self.link.callback(|_| Msg::ResetTimings)>{ lang.t("reset_timings") }</button>
+            </div>
+        }
+    }
+
+    fn view_records(&self) -> Html {
+        html! {
+            <RecordsTable language=self.settings.language records=self.records.clone() />
+        }
+    }
+
+    fn view_sessions(&self) -> Html {
+        html! {
+            <SessionDashboard language=self.settings.language
+                              sessions=self.sessions.clone()
+                              on_remove=self.callbacks.remove_session.clone() />
+        }
+    }
+}
+
+/// Shows the chat to someone who scanned a chat join code.
+#[cfg(feature = "networking")]
+fn open_chat_if_joining(settings: &mut Settings) {
+    if chat::relay_from_location().is_some() && !settings.shows(Panel::Chat) {
+        settings.toggle_panel(Panel::Chat);
+    }
+}
+
+#[cfg(not(feature = "networking"))]
+fn open_chat_if_joining(_settings: &mut Settings) {}
+
+/// Loads the stored state, moving sessions saved under the old key into it
+/// and clearing away the template's todo entries. Falls back to a fresh
+/// state, with the reason, when the stored one can't be used.
+fn restore_state(storage: &mut StorageService) -> (PersistedState, Option<StateError>) {
+    if storage.restore::<Text>(TODO_KEY).is_ok() {
+        warn!("discarding todo entries left by an older build");
+        storage.remove(TODO_KEY);
+    }
+    let fresh =
+        |sessions: &[SessionSummary]| PersistedState::capture(vec![Game::default()], 0, sessions);
+    if let Ok(text) = storage.restore::<Text>(STATE_KEY) {
+        return match Json::<Result<PersistedState, _>>::from(Ok(text.clone())) {
+            Json(Ok(state)) => match state.migrate() {
+                Ok(state) => (state, None),
+                Err(error) => (fresh(&[]), Some(error)),
+            },
+            Json(Err(err)) => {
+                error!("stored state unreadable: {:?}", err);
+                let backup: Text = Ok(text);
+                storage.store(STATE_BACKUP_KEY, backup);
+                (fresh(&[]), Some(StateError::Unreadable))
+            }
+        };
+    }
+    let sessions: Vec<SessionSummary> =
+        if let Packed(Some(restored_sessions)) = storage.restore(SESSIONS_KEY) {
+            restored_sessions
+        } else if let Json(Ok(restored_sessions)) = storage.restore(SESSIONS_KEY) {
+            // Sessions saved before the packed format.
+            restored_sessions
+        } else {
+            return (fresh(&[]), None);
+        };
+    let state = fresh(&sessions);
+    storage.store(STATE_KEY, Json(&state));
+    storage.remove(SESSIONS_KEY);
+    (state, None)
+}
+
+/// Formats a probability, switching to "1 in N" once percentages stop being
+/// readable.
+fn percent(lang: Language, p: f64) -> String {
+    if p >= 0.001 {
+        format!("{:.1}%", p * 100.0)
+    } else {
+        lang.fmt("one_in", &[&format!("{:.0}", 1.0 / p)])
     }
 }
@@ -0,0 +1,40 @@
+use serde_derive::{Deserialize, Serialize};
+
+/// Goblin Archaeologist's activations: flip a coin, destroying the target
+/// artifact on a win and sacrificing the Archaeologist on a loss.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Archaeologist {
+    /// Notes naming the artifacts that can still be targeted.
+    pub artifacts: Vec<String>,
+    pub target: Option<usize>,
+    pub sacrificed: bool,
+    pub activations: u32,
+    pub wins: u32,
+    pub losses: u32,
+}
+
+impl Archaeologist {
+    /// Tallies one activation. A win destroys the target and returns its
+    /// note; a loss sacrifices the Archaeologist.
+    pub fn resolve(&mut self, won: bool) -> Option<String> {
+        self.activations += 1;
+        if !won {
+            self.losses += 1;
+            self.sacrificed = true;
+            return None;
+        }
+        self.wins += 1;
+        let target = self.target.take()?;
+        (target < self.artifacts.len()).then(|| self.artifacts.remove(target))
+    }
+
+    /// Forgets the artifact at `idx`, keeping the target on the same note.
+    pub fn remove(&mut self, idx: usize) {
+        self.artifacts.remove(idx);
+        self.target = match self.target {
+            Some(target) if target == idx => None,
+            Some(target) if target > idx => Some(target - 1),
+            target => target,
+        };
+    }
+}
@@ -0,0 +1,106 @@
+use crate::archaeologist::Archaeologist;
+use crate::i18n::Language;
+use crate::name_form::NameForm;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// Goblin Archaeologist's artifact notes, its activation and a tally of
+/// how the flips went. Edits come back as the whole new Archaeologist.
+pub struct ArchaeologistPanel {
+    link: ComponentLink<Self>,
+    props: Props,
+    on_add: Callback<String>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub archaeologist: Archaeologist,
+    pub on_change: Callback<Archaeologist>,
+    pub on_activate: Callback<()>,
+}
+
+pub enum Msg {
+    Add(String),
+    Remove(usize),
+    SetTarget(usize),
+    Return,
+}
+
+impl Component for ArchaeologistPanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        ArchaeologistPanel {
+            on_add: link.callback(Msg::Add),
+            link,
+            props,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let mut archaeologist = self.props.archaeologist.clone();
+        match msg {
+            Msg::Add(name) => archaeologist.artifacts.push(name),
+            Msg::Remove(idx) => archaeologist.remove(idx),
+            Msg::SetTarget(idx) => archaeologist.target = Some(idx),
+            Msg::Return => archaeologist.sacrificed = false,
+        }
+        if archaeologist != self.props.archaeologist {
+            self.props.on_change.emit(archaeologist);
+        }
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        let archaeologist = &self.props.archaeologist;
+        html! {
+            <div class="archaeologist">
+                <ul>
+                    { for archaeologist.artifacts.iter().enumerate().map(|(idx, artifact)| html! {
+                        <li>
+                            <label>
+                                <input type="radio"
+                                       name="archaeologist-target"
+                                       checked=archaeologist.target == Some(idx)
+                                       onclick=self.link.callback(move |_| Msg::SetTarget(idx)) />
+                                { artifact }
+                            </label>
+                            <button class="destroy"
+                                    aria-label=lang.t("remove")
+                                    onclick=self.link.callback(move |_| Msg::Remove(idx))>{ "×" }</button>
+                        </li>
+                    }) }
+                </ul>
+                <NameForm label=lang.t("artifact_name")
+                          button=lang.t("add_artifact")
+                          on_submit=self.on_add.clone() />
+                { if archaeologist.sacrificed {
+                    html! {
+                        <>
+                            <p class="sacrificed">{ lang.t("archaeologist_gone") }</p>
+                            <button onclick=self.link.callback(|_| Msg::Return)>
+                                { lang.t("archaeologist_return") }
+                            </button>
+                        </>
+                    }
+                } else {
+                    html! {
+                        <button class="activate" onclick=self.props.on_activate.reform(|_| ())>
+                            { lang.t("archaeologist_activate") }
+                        </button>
+                    }
+                } }
+                <p class="archaeologist-tally">
+                    { lang.fmt("archaeologist_tally", &[&archaeologist.activations, &archaeologist.wins, &archaeologist.losses]) }
+                </p>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use serde_derive::{Deserialize, Serialize};
+
+/// Sides of the die rolled to visit Attractions.
+pub const ATTRACTION_DIE: u32 = 6;
+
+/// An Unfinity Attraction you have open.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Attraction {
+    pub name: String,
+    /// Lit numbers, indexed from 1 at position 0.
+    pub lit: [bool; ATTRACTION_DIE as usize],
+    pub visits: u32,
+}
+
+impl Attraction {
+    pub fn new(name: String) -> Self {
+        Attraction {
+            name,
+            lit: [false; ATTRACTION_DIE as usize],
+            visits: 0,
+        }
+    }
+
+    pub fn is_lit(&self, number: u32) -> bool {
+        (1..=ATTRACTION_DIE).contains(&number) && self.lit[number as usize - 1]
+    }
+
+    pub fn toggle(&mut self, number: u32) {
+        if (1..=ATTRACTION_DIE).contains(&number) {
+            self.lit[number as usize - 1] ^= true;
+        }
+    }
+}
+
+/// Visits every attraction lit for `roll`, returning the names visited.
+pub fn visit(attractions: &mut [Attraction], roll: u32) -> Vec<String> {
+    attractions
+        .iter_mut()
+        .filter(|a| a.is_lit(roll))
+        .map(|a| {
+            a.visits += 1;
+            a.name.clone()
+        })
+        .collect()
+}
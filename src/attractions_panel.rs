@@ -0,0 +1,99 @@
+use crate::attraction::{Attraction, ATTRACTION_DIE};
+use crate::i18n::Language;
+use crate::name_form::NameForm;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// Open Attractions with their lit numbers, and the roll to visit them.
+/// Each change comes back as the whole new list.
+pub struct AttractionsPanel {
+    link: ComponentLink<Self>,
+    props: Props,
+    on_add: Callback<String>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub attractions: Vec<Attraction>,
+    pub on_change: Callback<Vec<Attraction>>,
+    pub on_roll: Callback<()>,
+}
+
+pub enum Msg {
+    Add(String),
+    Remove(usize),
+    ToggleLit(usize, u32),
+}
+
+impl AttractionsPanel {
+    fn view_attraction(&self, idx: usize, attraction: &Attraction) -> Html {
+        let lang = self.props.language;
+        html! {
+            <li>
+                { &attraction.name }
+                <span class="lit-numbers">
+                    { for (1..=ATTRACTION_DIE).map(|number| html! {
+                        <button class=if attraction.is_lit(number) { "lit" } else { "" }
+                                aria-pressed=attraction.is_lit(number).to_string()
+                                aria-label=lang.fmt("lit_number", &[&number])
+                                onclick=self.link.callback(move |_| Msg::ToggleLit(idx, number))>{ number }</button>
+                    }) }
+                </span>
+                <span class="visits">{ lang.fmt("visits", &[&attraction.visits]) }</span>
+                <button class="destroy"
+                        aria-label=lang.t("remove")
+                        onclick=self.link.callback(move |_| Msg::Remove(idx))>{ "×" }</button>
+            </li>
+        }
+    }
+}
+
+impl Component for AttractionsPanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        AttractionsPanel {
+            on_add: link.callback(Msg::Add),
+            link,
+            props,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let mut attractions = self.props.attractions.clone();
+        match msg {
+            Msg::Add(name) => attractions.push(Attraction::new(name)),
+            Msg::Remove(idx) => {
+                attractions.remove(idx);
+            }
+            Msg::ToggleLit(idx, number) => {
+                if let Some(attraction) = attractions.get_mut(idx) {
+                    attraction.toggle(number);
+                }
+            }
+        }
+        self.props.on_change.emit(attractions);
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <div class="attractions">
+                <ul>
+                    { for self.props.attractions.iter().enumerate().map(|(idx, a)| self.view_attraction(idx, a)) }
+                </ul>
+                <NameForm label=lang.t("attraction_name")
+                          button=lang.t("add_attraction")
+                          on_submit=self.on_add.clone() />
+                <button class="roll-to-visit" onclick=self.props.on_roll.reform(|_| ())>{ lang.t("roll_to_visit") }</button>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Markdown battle reports of the current session, turn by turn, ready to
+//! paste into a Reddit post or a Discord thread.
+
+use crate::clock::format_clock;
+use crate::game::{Game, TurnMark};
+use crate::history::{FlipRecord, RecordStream, StatsAccumulator};
+use crate::i18n::Language;
+use std::fmt::Write;
+
+/// Wins one activation needs before the report calls it out.
+const NOTABLE_WINS: usize = 3;
+
+/// Backslash-escapes characters Markdown would otherwise format.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\`*_#[]<>|~".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Your flips made between two marks.
+fn flips_between(game: &Game, start: TurnMark, end: TurnMark) -> Vec<&FlipRecord> {
+    game.history
+        .iter()
+        .filter(|r| r.player.is_none())
+        .filter(|r| (start.activations..end.activations).contains(&r.activation))
+        .collect()
+}
+
+/// Renders the report as of `now`, in `Date.now()` milliseconds.
+pub fn render(game: &Game, lang: Language, now: f64) -> String {
+    let mut report = format!("# {}\n", lang.fmt("report_title", &[&escape(&game.name)]));
+    let marks = game.turn_marks.iter().copied();
+    let mut start = game.session_start;
+    for (turn, end) in marks.chain(Some(game.mark())).enumerate() {
+        let log = game.table_log.get(start.log..end.log).unwrap_or_default();
+        let flips = flips_between(game, start, end);
+        let storm = game.turn_storms.get(turn).copied().unwrap_or(game.storm);
+        start = end;
+        if turn == game.turn_marks.len() && log.is_empty() && flips.is_empty() && storm == 0 {
+            // Nothing has happened yet in the turn under way.
+            break;
+        }
+
+        let _ = writeln!(report, "\n## {}\n", lang.fmt("report_turn", &[&(turn + 1)]));
+        let wins = flips.iter().filter(|r| r.is_win()).count();
+        let _ = writeln!(
+            report,
+            "{}\n",
+            lang.fmt("report_turn_summary", &[&storm, &flips.len(), &wins])
+        );
+        for line in log {
+            let _ = writeln!(report, "- {}", escape(line));
+        }
+        for group in flips.into_iter().group_by_trigger() {
+            let wins = group.iter().filter(|r| r.is_win()).count();
+            if wins >= NOTABLE_WINS {
+                let source = format!("**{}**", group[0].source);
+                let _ = writeln!(
+                    report,
+                    "- {}",
+                    lang.fmt("report_notable", &[&source, &wins, &group.len()])
+                );
+            }
+        }
+    }
+
+    let stats = StatsAccumulator::new(&game.history);
+    let _ = writeln!(report, "\n## {}\n", lang.t("report_result"));
+    let _ = writeln!(report, "- {}: {}", lang.t("life"), game.board.life);
+    let win_rate = if stats.flips == 0 {
+        0.0
+    } else {
+        stats.wins as f64 / stats.flips as f64
+    };
+    let _ = writeln!(
+        report,
+        "- {}",
+        lang.fmt(
+            "report_flips",
+            &[
+                &stats.flips,
+                &stats.wins,
+                &format!("{:.0}%", win_rate * 100.0)
+            ]
+        )
+    );
+    let _ = writeln!(
+        report,
+        "- {}: {}",
+        lang.t("longest_streak"),
+        stats.longest_streak
+    );
+    if game.pace.started.is_some() {
+        let _ = writeln!(
+            report,
+            "- {}",
+            lang.fmt(
+                "report_pace",
+                &[
+                    &format_clock(game.pace.elapsed(now)),
+                    &format!("{:.1}", game.pace.flips_per_minute(stats.flips, now))
+                ]
+            )
+        );
+    }
+    for opponent in &game.opponents {
+        let _ = writeln!(report, "- {}: {}", escape(&opponent.name), opponent.life);
+    }
+    report
+}
@@ -0,0 +1,118 @@
+//! Bar charts drawn on an `OffscreenCanvas` for data too large for SVG.
+
+use crate::neq_assign::NeqAssign;
+use log::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    HtmlCanvasElement, ImageBitmap, ImageBitmapRenderingContext, OffscreenCanvas,
+    OffscreenCanvasRenderingContext2d,
+};
+use yew::prelude::*;
+
+/// Draws `values` as bars scaled to the largest and returns the bitmap.
+/// Only uses APIs that workers have, so the drawing can move off the main
+/// thread without changes.
+pub fn draw_bars(
+    values: &[f64],
+    width: u32,
+    height: u32,
+    color: &str,
+) -> Result<ImageBitmap, JsValue> {
+    let canvas = OffscreenCanvas::new(width, height)?;
+    let context = canvas
+        .get_context("2d")?
+        .ok_or("no 2d context")?
+        .dyn_into::<OffscreenCanvasRenderingContext2d>()?;
+    context.set_fill_style_str(color);
+    let max = values.iter().cloned().fold(0.0, f64::max);
+    if max > 0.0 {
+        let bar = f64::from(width) / values.len() as f64;
+        for (i, value) in values.iter().enumerate() {
+            let h = f64::from(height) * value / max;
+            context.fill_rect(i as f64 * bar, f64::from(height) - h, bar, h);
+        }
+    }
+    canvas.transfer_to_image_bitmap()
+}
+
+/// Shows a bar chart drawn by [`draw_bars`], handing the finished bitmap to
+/// the page canvas instead of drawing on it directly. Bars take the
+/// canvas's CSS `color`.
+pub struct BitmapChart {
+    props: Props,
+    canvas: NodeRef,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub values: Vec<f64>,
+    pub width: u32,
+    pub height: u32,
+    /// Accessible description of the chart.
+    pub label: String,
+}
+
+impl BitmapChart {
+    fn paint(&self) -> Result<(), JsValue> {
+        let canvas = match self.canvas.cast::<HtmlCanvasElement>() {
+            Some(canvas) => canvas,
+            None => return Ok(()),
+        };
+        let color = web_sys::window()
+            .unwrap()
+            .get_computed_style(&canvas)?
+            .map(|style| style.get_property_value("color"))
+            .transpose()?
+            .unwrap_or_else(|| "black".into());
+        let bitmap = draw_bars(
+            &self.props.values,
+            self.props.width,
+            self.props.height,
+            &color,
+        )?;
+        canvas
+            .get_context("bitmaprenderer")?
+            .ok_or("no bitmaprenderer context")?
+            .dyn_into::<ImageBitmapRenderingContext>()?
+            .transfer_from_image_bitmap(&bitmap);
+        Ok(())
+    }
+}
+
+impl Component for BitmapChart {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        BitmapChart {
+            props,
+            canvas: NodeRef::default(),
+        }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn rendered(&mut self, _first_render: bool) {
+        if let Err(err) = self.paint() {
+            error!("bitmap chart failed: {:?}", err);
+        }
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <canvas class="bitmap-chart"
+                    ref=self.canvas.clone()
+                    role="img"
+                    aria-label=&self.props.label
+                    width=self.props.width
+                    height=self.props.height />
+        }
+    }
+}
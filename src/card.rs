@@ -0,0 +1,99 @@
+use crate::coin_flip::WinCondition;
+use crate::combat::{CombatEffect, Fate, Side};
+use crate::effect::{self, CardEffect};
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Cards that flip coins. Every flip in the history is attributed to one.
+#[derive(
+    Clone, Copy, Debug, Display, EnumIter, EnumString, Eq, Hash, PartialEq, Serialize, Deserialize,
+)]
+pub enum FlipCard {
+    #[strum(serialize = "Krark, the Thumbless")]
+    Krark,
+    #[strum(serialize = "Ral Zarek")]
+    RalZarek,
+    #[strum(serialize = "Zndrsplt, Eye of Wisdom")]
+    Zndrsplt,
+    #[strum(serialize = "Mana Crypt")]
+    ManaCrypt,
+    #[strum(serialize = "Goblin Archaeologist")]
+    GoblinArchaeologist,
+    /// You and the targeted opponent each flip; see `App::mana_clash`.
+    #[strum(serialize = "Mana Clash")]
+    ManaClash,
+    #[strum(serialize = "Karplusan Minotaur")]
+    KarplusanMinotaur,
+    /// Flipped by its controller when creatures block; see `combat`.
+    #[strum(serialize = "Tide of War")]
+    TideOfWar,
+    /// Flipped by whoever takes it in their upkeep; see `risky_move`.
+    #[strum(serialize = "Risky Move")]
+    RiskyMove,
+    /// Any custom card definition.
+    #[strum(serialize = "Homebrew card")]
+    Homebrew,
+}
+
+impl FlipCard {
+    /// Coins flipped by one activation, or `None` for cards that keep
+    /// flipping until a flip is lost.
+    pub fn flips(self) -> Option<usize> {
+        match self {
+            FlipCard::Krark => Some(1),
+            FlipCard::RalZarek => Some(5),
+            FlipCard::Zndrsplt => None,
+            FlipCard::ManaCrypt => Some(1),
+            FlipCard::GoblinArchaeologist => Some(1),
+            // Per player and round.
+            FlipCard::ManaClash => Some(1),
+            FlipCard::KarplusanMinotaur => Some(1),
+            FlipCard::TideOfWar => Some(1),
+            FlipCard::RiskyMove => Some(1),
+            FlipCard::Homebrew => Some(1),
+        }
+    }
+
+    /// Whether the card makes you flip at the beginning of each of your
+    /// upkeeps, which can't be skipped.
+    pub fn flips_in_upkeep(self) -> bool {
+        matches!(self, FlipCard::ManaCrypt | FlipCard::KarplusanMinotaur)
+    }
+
+    /// What counts as winning the card's flips, as printed.
+    pub fn win_condition(self) -> WinCondition {
+        match self {
+            // Tails deals damage.
+            FlipCard::ManaClash => WinCondition::Heads,
+            _ => WinCondition::YouCall,
+        }
+    }
+
+    /// What the card does to creatures in combat, for cards flipped when
+    /// creatures attack or block.
+    pub fn combat(self) -> Option<CombatEffect> {
+        match self {
+            FlipCard::TideOfWar => Some(CombatEffect {
+                won: (Side::Blocking, Fate::Sacrificed),
+                lost: (Side::Blocked, Fate::Sacrificed),
+            }),
+            _ => None,
+        }
+    }
+
+    /// What the card does with the flips it makes.
+    pub fn effect(self) -> &'static dyn CardEffect {
+        match self {
+            FlipCard::Krark => &effect::Krark,
+            FlipCard::RalZarek => &effect::RalZarek,
+            FlipCard::Zndrsplt => &effect::Zndrsplt,
+            FlipCard::ManaCrypt => &effect::ManaCrypt,
+            FlipCard::GoblinArchaeologist => &effect::GoblinArchaeologist,
+            FlipCard::ManaClash => &effect::ManaClash,
+            FlipCard::KarplusanMinotaur => &effect::KarplusanMinotaur,
+            FlipCard::TideOfWar => &effect::TideOfWar,
+            FlipCard::RiskyMove => &effect::RiskyMove,
+            FlipCard::Homebrew => &effect::Homebrew,
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::custom_card::{CardError, CustomCard, Definitions};
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::format::Json;
+use yew::prelude::*;
+
+/// A box to paste custom card definitions into, as one object or a list
+/// of them. Hands on the cards once every one of them checks out.
+pub struct CardImport {
+    link: ComponentLink<Self>,
+    props: Props,
+    json: String,
+    error: Option<CardError>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub on_import: Callback<Vec<CustomCard>>,
+}
+
+pub enum Msg {
+    Update(String),
+    Import,
+}
+
+/// Parses `json` into cards, or says why it could not.
+fn parse(json: &str) -> Result<Vec<CustomCard>, CardError> {
+    let Json(cards) = Json::<Result<Definitions, _>>::from(Ok(json.to_string()));
+    cards
+        .map_err(|_| CardError::Json)
+        .and_then(|cards| cards.into_vec().into_iter().map(CustomCard::new).collect())
+}
+
+impl Component for CardImport {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        CardImport {
+            link,
+            props,
+            json: String::new(),
+            error: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Update(val) => return self.json.neq_assign(val),
+            Msg::Import => match parse(&self.json) {
+                Ok(cards) => {
+                    self.props.on_import.emit(cards);
+                    self.json.clear();
+                    self.error = None;
+                }
+                Err(error) => self.error = Some(error),
+            },
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <>
+                <textarea aria-label=lang.t("custom_card_json")
+                          placeholder=r#"{ "name": "Fiery Gambit", "flips": null, "trigger": "flip_lost", "effect": "life -2" }"#
+                          value=&self.json
+                          oninput=self.link.callback(|e: InputData| Msg::Update(e.value)) />
+                <button onclick=self.link.callback(|_| Msg::Import)>{ lang.t("import_cards") }</button>
+                { match self.error {
+                    Some(error) => html! { <p class="error" role="alert">{ lang.t(error.as_ref()) }</p> },
+                    None => html! {},
+                } }
+            </>
+        }
+    }
+}
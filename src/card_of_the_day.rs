@@ -0,0 +1,158 @@
+//! A coin-flip or dice card from Scryfall to discover each day, fetched at
+//! most once a day and kept in local storage.
+
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use serde_derive::{Deserialize, Serialize};
+use yew::format::{Json, Nothing};
+use yew::prelude::*;
+use yew::services::fetch::{FetchService, FetchTask, Request, Response};
+use yew::services::storage::{Area, StorageService};
+
+const CARD_OF_THE_DAY_KEY: &str = "krarkinator.card_of_the_day";
+/// Scryfall's search for cards that flip coins or roll dice.
+const QUERY: &str =
+    r#"(o:"flip a coin" or o:"flips a coin" or o:"roll a" or o:"rolls a") -is:digital"#;
+
+/// The parts of a Scryfall card shown and tracked.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DailyCard {
+    pub name: String,
+    #[serde(default)]
+    pub type_line: String,
+    /// Missing for double-faced cards, whose faces have their own.
+    #[serde(default)]
+    pub oracle_text: String,
+    pub scryfall_uri: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Cached {
+    /// `YYYY-MM-DD`, in UTC.
+    date: String,
+    card: DailyCard,
+}
+
+pub struct CardOfTheDay {
+    link: ComponentLink<Self>,
+    props: Props,
+    storage: Option<StorageService>,
+    card: Option<DailyCard>,
+    _task: Option<FetchTask>,
+    failed: bool,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub on_track: Callback<DailyCard>,
+}
+
+pub enum Msg {
+    Fetched(Option<DailyCard>),
+    Track,
+}
+
+fn today() -> String {
+    String::from(js_sys::Date::new_0().to_iso_string())[..10].to_string()
+}
+
+impl CardOfTheDay {
+    fn fetch(&mut self) {
+        let url = format!(
+            "https://api.scryfall.com/cards/random?q={}",
+            js_sys::encode_uri_component(QUERY)
+        );
+        let request = match Request::get(url).body(Nothing) {
+            Ok(request) => request,
+            Err(_) => return,
+        };
+        let callback = self
+            .link
+            .callback(|response: Response<Json<Result<DailyCard, _>>>| {
+                let Json(card) = response.into_body();
+                Msg::Fetched(card.ok())
+            });
+        self._task = FetchService::fetch(request, callback).ok();
+        self.failed = self._task.is_none();
+    }
+}
+
+impl Component for CardOfTheDay {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let storage = StorageService::new(Area::Local).ok();
+        let cached = match storage.as_ref().map(|s| s.restore(CARD_OF_THE_DAY_KEY)) {
+            Some(Json(Ok(Cached { date, card }))) if date == today() => Some(card),
+            _ => None,
+        };
+        let mut widget = CardOfTheDay {
+            link,
+            props,
+            storage,
+            card: cached,
+            _task: None,
+            failed: false,
+        };
+        if widget.card.is_none() {
+            widget.fetch();
+        }
+        widget
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Fetched(Some(card)) => {
+                self._task = None;
+                if let Some(storage) = &mut self.storage {
+                    let cached = Cached {
+                        date: today(),
+                        card: card.clone(),
+                    };
+                    storage.store(CARD_OF_THE_DAY_KEY, Json(&cached));
+                }
+                self.card = Some(card);
+            }
+            Msg::Fetched(None) => {
+                self._task = None;
+                self.failed = true;
+            }
+            Msg::Track => {
+                if let Some(card) = &self.card {
+                    self.props.on_track.emit(card.clone());
+                }
+                return false;
+            }
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        let card = match &self.card {
+            Some(card) => card,
+            None if self.failed => {
+                return html! { <p class="card-of-the-day">{ lang.t("card_of_the_day_failed") }</p> };
+            }
+            None => {
+                return html! { <p class="card-of-the-day">{ lang.t("card_of_the_day_loading") }</p> }
+            }
+        };
+        html! {
+            <article class="card-of-the-day">
+                <h3>
+                    <a href=card.scryfall_uri.as_str() target="_blank" rel="noopener">{ &card.name }</a>
+                </h3>
+                <p class="type-line">{ &card.type_line }</p>
+                <p class="oracle-text">{ &card.oracle_text }</p>
+                <button onclick=self.link.callback(|_| Msg::Track)>{ lang.t("track_card") }</button>
+            </article>
+        }
+    }
+}
@@ -0,0 +1,502 @@
+use crate::archaeologist::Archaeologist;
+use crate::attraction::Attraction;
+use crate::coin_flip::{FlipResult, WinCondition};
+use crate::creature::Creature;
+use crate::dice::RollRules;
+use crate::effect::{self, CardEffect};
+use crate::opponent::OPENING_HAND;
+use crate::probability::CastOdds;
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, Display, EnumIter, EnumString};
+
+/// Starting life total in Commander.
+pub const STARTING_LIFE: i32 = 40;
+/// Life paid to activate Aetherflux Reservoir for 50 damage.
+pub const AETHERFLUX_COST: i32 = 50;
+/// Charge counters Vexing Puzzlebox removes to fetch an artifact.
+pub const PUZZLEBOX_GOAL: u32 = 100;
+/// Text keys of Barbarian Class's abilities, one per level.
+pub const BARBARIAN_CLASS_ABILITIES: [&str; 3] = [
+    "barbarian_level_1",
+    "barbarian_level_2",
+    "barbarian_level_3",
+];
+pub const BRAIN_FREEZE: &str = "Brain Freeze";
+/// Cards milled by each Brain Freeze and each of its copies.
+pub const BRAIN_FREEZE_MILL: u32 = 3;
+pub const EMPTY_THE_WARRENS: &str = "Empty the Warrens";
+/// Goblins created by each Empty the Warrens and each of its copies.
+pub const WARRENS_GOBLINS: u32 = 2;
+pub const GRAPESHOT: &str = "Grapeshot";
+/// Spells with storm the cast workflow knows by name.
+pub const STORM_SPELLS: [&str; 3] = [BRAIN_FREEZE, EMPTY_THE_WARRENS, GRAPESHOT];
+
+/// An instant or sorcery registered in the cast workflow.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Spell {
+    pub name: String,
+    pub mana_value: u32,
+    #[serde(default)]
+    pub kind: SpellKind,
+    /// Cards each resolution draws, for cantrips.
+    #[serde(default)]
+    pub draws: u32,
+    /// Whether it has storm, so it is copied once for each spell cast
+    /// before it this turn.
+    #[serde(default)]
+    pub storm: bool,
+}
+
+/// Why a spell was not cast. `as_ref` gives the error's text key.
+#[derive(AsRefStr, Clone, Copy, Debug, PartialEq)]
+pub enum CastError {
+    #[strum(serialize = "cast_cannot_pay")]
+    CannotPay,
+}
+
+/// Card type of a spell in the cast workflow. `as_ref` gives the type's
+/// text key.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumIter,
+    EnumString,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub enum SpellKind {
+    #[default]
+    #[strum(serialize = "instant")]
+    Instant,
+    #[strum(serialize = "sorcery")]
+    Sorcery,
+}
+
+/// The spells a cost reducer applies to. `as_ref` gives the filter's text
+/// key.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumIter,
+    EnumString,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub enum SpellFilter {
+    #[default]
+    #[strum(serialize = "filter_any")]
+    Any,
+    #[strum(serialize = "filter_instant")]
+    Instant,
+    #[strum(serialize = "filter_sorcery")]
+    Sorcery,
+}
+
+impl SpellFilter {
+    pub fn matches(self, kind: SpellKind) -> bool {
+        match self {
+            SpellFilter::Any => true,
+            SpellFilter::Instant => kind == SpellKind::Instant,
+            SpellFilter::Sorcery => kind == SpellKind::Sorcery,
+        }
+    }
+}
+
+/// A permanent such as Ruby Medallion or Goblin Electromancer that makes
+/// spells cost less.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CostReducer {
+    pub name: String,
+    pub amount: u32,
+    pub applies_to: SpellFilter,
+}
+
+/// The permanents that matter when a spell is cast.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Board {
+    /// Copies of Krark, the Thumbless on the battlefield.
+    pub krarks: usize,
+    /// Copies of Krark's Thumb on the battlefield.
+    pub thumbs: usize,
+    /// Permanents that add one mana whenever a spell is cast or copied.
+    pub payoffs: u32,
+    pub life: i32,
+    /// Whether Aetherflux Reservoir is on the battlefield.
+    pub aetherflux: bool,
+    pub tokens: Vec<Tokens>,
+    /// Mana floating in the pool.
+    pub mana: u32,
+    pub reducers: Vec<CostReducer>,
+    /// Cards in your hand.
+    pub hand: u32,
+    /// Cards drawn this game after the opening hand.
+    #[serde(default)]
+    pub drawn: u32,
+    /// Cards in your library when the game began, if set.
+    #[serde(default)]
+    pub library: Option<u32>,
+    /// The loop behind a declared infinite mana combo. While set, the pool
+    /// is unbounded and spells cost nothing from it.
+    pub infinite_mana: Option<String>,
+    pub creatures: Vec<Creature>,
+    /// Permanents like Niv-Mizzet, Parun that deal 1 damage whenever you
+    /// draw a card.
+    pub draw_pingers: u32,
+    pub attractions: Vec<Attraction>,
+    /// Charge counters on Vexing Puzzlebox, if it is on the battlefield.
+    pub puzzlebox: Option<u32>,
+    /// Level of Barbarian Class, if it is on the battlefield.
+    pub barbarian_class: Option<u32>,
+    pub archaeologist: Archaeologist,
+    /// Times Okaun, Eye of Chaos has doubled this turn, if it is on the
+    /// battlefield.
+    #[serde(default)]
+    pub okaun: Option<u32>,
+}
+
+/// A stack of identical tokens on the battlefield.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Tokens {
+    pub name: String,
+    pub count: u32,
+    /// Whether they only last until end of turn.
+    #[serde(default)]
+    pub temporary: bool,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board {
+            krarks: 1,
+            thumbs: 0,
+            payoffs: 0,
+            life: STARTING_LIFE,
+            aetherflux: false,
+            tokens: Vec::new(),
+            mana: 0,
+            reducers: Vec::new(),
+            hand: OPENING_HAND,
+            drawn: 0,
+            library: None,
+            infinite_mana: None,
+            creatures: Vec::new(),
+            draw_pingers: 0,
+            attractions: Vec::new(),
+            puzzlebox: None,
+            barbarian_class: None,
+            archaeologist: Archaeologist::default(),
+            okaun: None,
+        }
+    }
+}
+
+impl Board {
+    /// Cards in play whose cast and end of turn hooks apply.
+    pub fn effects(&self) -> Vec<&'static dyn CardEffect> {
+        let mut effects: Vec<&'static dyn CardEffect> = Vec::new();
+        if self.krarks > 0 {
+            effects.push(&effect::Krark);
+        }
+        if self.aetherflux {
+            effects.push(&effect::AetherfluxReservoir);
+        }
+        if self.okaun.is_some() {
+            effects.push(&effect::Okaun);
+        }
+        effects
+    }
+
+    /// Mana actually paid for `spell` after every applicable cost reducer.
+    pub fn cost_of(&self, spell: &Spell) -> u32 {
+        let reduction: u32 = self
+            .reducers
+            .iter()
+            .filter(|r| r.applies_to.matches(spell.kind))
+            .map(|r| r.amount)
+            .sum();
+        spell.mana_value.saturating_sub(reduction)
+    }
+
+    /// Whether the pool can pay for `spell`.
+    pub fn can_pay(&self, spell: &Spell) -> bool {
+        self.infinite_mana.is_some() || self.cost_of(spell) <= self.mana
+    }
+
+    /// Pays for `spell` from the pool and returns the mana paid. A pool
+    /// that can't pay is left alone.
+    pub fn pay_for(&mut self, spell: &Spell) -> Result<u32, CastError> {
+        if self.infinite_mana.is_some() {
+            return Ok(0);
+        }
+        let cost = self.cost_of(spell);
+        if cost > self.mana {
+            return Err(CastError::CannotPay);
+        }
+        self.mana -= cost;
+        Ok(cost)
+    }
+
+    /// Adds the mana payoffs make for a cast spell and its copies, plus
+    /// `produced` for each resolution of a ritual. Returns the mana added.
+    pub fn collect_mana(&mut self, outcome: &CastOutcome, produced: u32) -> u32 {
+        let gained = self.payoffs * (1 + outcome.copies()) + produced * outcome.resolutions();
+        self.mana += gained;
+        gained
+    }
+
+    /// Draws `cards` into your hand.
+    pub fn draw(&mut self, cards: u32) {
+        self.hand += cards;
+        self.drawn += cards;
+    }
+
+    /// Cards left in your library after this game's draws, given how many
+    /// it began with.
+    pub fn library_left(&self, size: Option<u32>) -> Option<u32> {
+        size.map(|size| size.saturating_sub(self.drawn))
+    }
+
+    /// Adds `count` tokens to the stack called `name`, starting one if
+    /// needed.
+    pub fn create_tokens(&mut self, name: &str, count: u32) {
+        self.add_tokens(name, count, false);
+    }
+
+    /// Adds `count` tokens that last until end of turn, kept apart from
+    /// lasting ones of the same name.
+    pub fn create_temporary_tokens(&mut self, name: &str, count: u32) {
+        self.add_tokens(name, count, true);
+    }
+
+    fn add_tokens(&mut self, name: &str, count: u32, temporary: bool) {
+        match self
+            .tokens
+            .iter_mut()
+            .find(|t| t.name == name && t.temporary == temporary)
+        {
+            Some(tokens) => tokens.count += count,
+            None => self.tokens.push(Tokens {
+                name: name.to_string(),
+                count,
+                temporary,
+            }),
+        }
+    }
+
+    /// The storm count a payoff cast now would see, or `None` when
+    /// infinite mana lets spells be recast as often as needed.
+    pub fn effective_storm(&self, storm: u32) -> Option<u32> {
+        match self.infinite_mana {
+            Some(_) => None,
+            None => Some(storm),
+        }
+    }
+
+    /// Applies the triggers that care about rolling dice.
+    pub fn on_roll(&mut self, total: i64) {
+        if let Some(charge) = &mut self.puzzlebox {
+            *charge += total.max(0) as u32;
+        }
+        for wyll in self.creatures.iter_mut().filter(|c| c.is_wyll()) {
+            wyll.counters += 1;
+        }
+    }
+
+    /// How dice are rolled with this board. Barbarian Class from level 1
+    /// on and each Wyll roll an extra die and ignore the lowest.
+    pub fn roll_rules(&self) -> RollRules {
+        let wylls = self.creatures.iter().filter(|c| c.is_wyll()).count() as u32;
+        RollRules {
+            advantage: self.barbarian_class.map_or(0, |_| 1) + wylls,
+        }
+    }
+
+    /// Whether Aetherflux Reservoir can be activated without paying life
+    /// down to zero.
+    pub fn aetherflux_ready(&self) -> bool {
+        self.aetherflux && self.life > AETHERFLUX_COST
+    }
+}
+
+/// Rituals with quick-cast buttons. Display gives the card name.
+#[derive(Clone, Copy, Debug, Display, EnumIter, PartialEq, Serialize, Deserialize)]
+pub enum Ritual {
+    #[strum(serialize = "Jeska's Will")]
+    JeskasWill,
+    #[strum(serialize = "Mana Geyser")]
+    ManaGeyser,
+    #[strum(serialize = "Seething Song")]
+    SeethingSong,
+}
+
+impl Ritual {
+    pub fn spell(self) -> Spell {
+        let (mana_value, kind) = match self {
+            Ritual::JeskasWill => (3, SpellKind::Sorcery),
+            Ritual::ManaGeyser => (5, SpellKind::Sorcery),
+            Ritual::SeethingSong => (3, SpellKind::Instant),
+        };
+        Spell {
+            name: self.to_string(),
+            mana_value,
+            kind,
+            draws: 0,
+            storm: false,
+        }
+    }
+
+    /// Whether the mana made depends on the table: cards in an opponent's
+    /// hand for Jeska's Will, tapped lands for Mana Geyser.
+    pub fn is_variable(self) -> bool {
+        self != Ritual::SeethingSong
+    }
+
+    /// Mana one resolution adds, given the table-dependent count `x`.
+    pub fn mana(self, x: u32) -> u32 {
+        match self {
+            Ritual::SeethingSong => 5,
+            _ => x,
+        }
+    }
+}
+
+/// How many times a cast spell resolves once storm and Krark triggers
+/// are done with it.
+#[derive(Serialize, Deserialize)]
+pub struct CastOutcome {
+    pub storm_copies: u32,
+    pub krark_copies: u32,
+    /// Whether a lost Krark flip returned the original to its owner's hand.
+    pub bounced: bool,
+}
+
+impl CastOutcome {
+    /// `storm` counts the spells cast before this one this turn, which
+    /// copy `spell` only if it has storm; `flips` are its Krark triggers,
+    /// won as `win` says.
+    pub fn new(spell: &Spell, storm: u32, flips: &[FlipResult], win: WinCondition) -> Self {
+        let wins = flips.iter().filter(|f| win.wins(f)).count();
+        CastOutcome {
+            storm_copies: if spell.storm { storm } else { 0 },
+            krark_copies: wins as u32,
+            bounced: wins < flips.len(),
+        }
+    }
+
+    /// Copies made by storm and by Krark.
+    pub fn copies(&self) -> u32 {
+        self.storm_copies + self.krark_copies
+    }
+
+    /// The original, unless bounced, plus every copy.
+    pub fn resolutions(&self) -> u32 {
+        self.copies() + u32::from(!self.bounced)
+    }
+}
+
+impl Spell {
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name.trim().eq_ignore_ascii_case(name)
+    }
+
+    /// Cards a cantrip draws over every resolution of one cast. Storm only
+    /// adds resolutions if the spell has it.
+    pub fn cards_drawn(&self, outcome: &CastOutcome) -> u32 {
+        self.draws * outcome.resolutions()
+    }
+
+    /// Whether `name` is one of [`STORM_SPELLS`].
+    pub fn has_storm_by_name(name: &str) -> bool {
+        STORM_SPELLS
+            .iter()
+            .any(|storm| name.trim().eq_ignore_ascii_case(storm))
+    }
+}
+
+/// Expected outcome of casting a spell once.
+pub struct SpellEv {
+    pub copies: f64,
+    pub mana: f64,
+    pub storm: f64,
+}
+
+impl Spell {
+    pub fn expected_value(&self, board: &Board) -> SpellEv {
+        let odds = CastOdds::new(board.krarks, board.thumbs);
+        let copies = odds.expected_copies;
+        SpellEv {
+            copies,
+            // Payoffs trigger on the cast and on every copy.
+            mana: f64::from(board.payoffs) * (1.0 + copies) - f64::from(board.cost_of(self)),
+            // A bounced spell can be cast again, adding another storm.
+            storm: 1.0 + odds.bounce,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spell(name: &str, mana_value: u32) -> Spell {
+        Spell {
+            name: name.into(),
+            mana_value,
+            kind: SpellKind::Instant,
+            draws: 0,
+            storm: Spell::has_storm_by_name(name),
+        }
+    }
+
+    #[test]
+    fn only_storm_spells_are_copied_by_storm() {
+        let flips = [FlipResult::Heads, FlipResult::Tails];
+        let win = WinCondition::YouCall;
+        let grapeshot = CastOutcome::new(&spell(GRAPESHOT, 2), 4, &flips, win);
+        assert_eq!((grapeshot.storm_copies, grapeshot.copies()), (4, 5));
+        let opt = CastOutcome::new(&spell("Opt", 1), 4, &flips, win);
+        assert_eq!((opt.storm_copies, opt.copies()), (0, 1));
+        assert_eq!(opt.resolutions(), 1);
+    }
+
+    #[test]
+    fn cantrips_draw_per_resolution_not_per_storm() {
+        let opt = Spell {
+            draws: 1,
+            ..spell("Opt", 1)
+        };
+        let flips = [FlipResult::Heads, FlipResult::Heads];
+        let outcome = CastOutcome::new(&opt, 6, &flips, WinCondition::YouCall);
+        assert_eq!(opt.cards_drawn(&outcome), 3);
+        let bounced = CastOutcome::new(&opt, 6, &[FlipResult::Tails], WinCondition::YouCall);
+        assert_eq!(opt.cards_drawn(&bounced), 0);
+    }
+
+    #[test]
+    fn refuses_a_spell_the_pool_cannot_pay_for() {
+        let mut board = Board {
+            mana: 1,
+            ..Board::default()
+        };
+        assert_eq!(
+            board.pay_for(&spell(GRAPESHOT, 2)),
+            Err(CastError::CannotPay)
+        );
+        assert_eq!(board.mana, 1);
+        assert_eq!(board.pay_for(&spell("Opt", 1)), Ok(1));
+        assert_eq!(board.mana, 0);
+
+        board.infinite_mana = Some("Dramatic Scepter".into());
+        assert_eq!(board.pay_for(&spell(GRAPESHOT, 2)), Ok(0));
+    }
+}
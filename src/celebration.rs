@@ -0,0 +1,122 @@
+use crate::i18n::Language;
+use std::time::Duration;
+use yew::prelude::*;
+use yew::services::timeout::{TimeoutService, TimeoutTask};
+
+/// How long a celebration lasts.
+const DURATION: Duration = Duration::from_millis(1600);
+
+/// Coins in the burst.
+const COINS: usize = 36;
+
+/// Class on `<body>` that shakes the page while a celebration plays.
+const SHAKE_CLASS: &str = "celebration-shake";
+
+/// A burst of coins and a shaking screen for a long streak. With reduced
+/// motion only the banner shows.
+pub struct Celebration {
+    link: ComponentLink<Self>,
+    props: Props,
+    /// Ends the celebration under way.
+    timeout: Option<TimeoutTask>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    /// Celebrations so far. A new one starts whenever it goes up.
+    pub bursts: usize,
+    /// The streak being celebrated.
+    pub streak: usize,
+    pub reduced_motion: bool,
+}
+
+pub enum Msg {
+    Done,
+}
+
+fn set_shaking(shaking: bool) {
+    let body = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.body());
+    if let Some(body) = body {
+        let classes = body.class_list();
+        let _ = if shaking {
+            classes.add_1(SHAKE_CLASS)
+        } else {
+            classes.remove_1(SHAKE_CLASS)
+        };
+    }
+}
+
+/// Where coin `i` flies to, how far it spins and how late it leaves.
+/// Golden-angle steps spread the coins evenly without looking regular.
+fn trajectory(i: usize) -> String {
+    let angle = i as f64 * 137.508_f64.to_radians();
+    let distance = 30.0 + (i * 7 % 11) as f64 * 4.0;
+    format!(
+        "--dx: {:.1}vmax; --dy: {:.1}vmax; --spin: {}deg; animation-delay: {}ms",
+        angle.cos() * distance,
+        angle.sin() * distance,
+        360 + i * 97 % 720,
+        i * 13 % 150
+    )
+}
+
+impl Component for Celebration {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Celebration {
+            link,
+            props,
+            timeout: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Done => {
+                self.timeout = None;
+                set_shaking(false);
+            }
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if props == self.props {
+            return false;
+        }
+        if props.bursts > self.props.bursts {
+            let done = self.link.callback(|_| Msg::Done);
+            self.timeout = Some(TimeoutService::spawn(DURATION, done));
+            set_shaking(!props.reduced_motion);
+        }
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        if self.timeout.is_none() {
+            return html! {};
+        }
+        let lang = self.props.language;
+        let coins = if self.props.reduced_motion { 0 } else { COINS };
+        html! {
+            <div class="celebration">
+                { for (0..coins).map(|i| html! {
+                    <span class="celebration-coin" aria-hidden="true" style=trajectory(i) />
+                }) }
+                <p class="celebration-banner">
+                    { lang.fmt("celebrate_streak", &[&self.props.streak]) }
+                </p>
+            </div>
+        }
+    }
+
+    fn destroy(&mut self) {
+        set_shaking(false);
+    }
+}
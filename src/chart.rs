@@ -0,0 +1,69 @@
+use crate::export::{download_png, download_svg};
+use crate::neq_assign::NeqAssign;
+use log::*;
+use yew::prelude::*;
+
+/// Wraps a rendered SVG chart with buttons to download it as SVG or PNG.
+pub struct Chart {
+    link: ComponentLink<Self>,
+    props: Props,
+    node: NodeRef,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    /// File name for downloads, without an extension.
+    pub name: String,
+    pub children: Children,
+}
+
+pub enum Msg {
+    DownloadSvg,
+    DownloadPng,
+}
+
+impl Component for Chart {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Chart {
+            link,
+            props,
+            node: NodeRef::default(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let svg = self
+            .node
+            .cast::<web_sys::Element>()
+            .and_then(|wrapper| wrapper.query_selector("svg").ok().flatten());
+        if let Some(svg) = svg {
+            let result = match msg {
+                Msg::DownloadSvg => download_svg(&svg, &self.props.name),
+                Msg::DownloadPng => download_png(&svg, &self.props.name),
+            };
+            if let Err(err) = result {
+                error!("chart export failed: {:?}", err);
+            }
+        }
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div class="chart" ref=self.node.clone()>
+                { self.props.children.clone() }
+                <div class="chart-export">
+                    <button onclick=self.link.callback(|_| Msg::DownloadSvg)>{ "SVG" }</button>
+                    <button onclick=self.link.callback(|_| Msg::DownloadPng)>{ "PNG" }</button>
+                </div>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Chat between remote players, for agreeing on trigger order and
+//! concessions without switching apps. Messages go through a WebSocket
+//! relay that passes every text frame on to everyone else connected.
+
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::qr_image::QrImage;
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::AsRefStr;
+use yew::format::Json;
+use yew::prelude::*;
+use yew::services::websocket::{WebSocketService, WebSocketStatus, WebSocketTask};
+
+/// Messages kept on screen; older ones scroll away.
+const MAX_MESSAGES: usize = 200;
+
+const JOIN_PREFIX: &str = "#chat=";
+
+/// Link that opens this page with the chat pointed at `server`, for
+/// tablemates to scan.
+fn join_link(server: &str) -> Option<String> {
+    let location = web_sys::window()?.location();
+    Some(format!(
+        "{}{}{}{}",
+        location.origin().ok()?,
+        location.pathname().ok()?,
+        JOIN_PREFIX,
+        js_sys::encode_uri_component(server)
+    ))
+}
+
+/// The relay this page was opened to join, if it was opened from a join
+/// link.
+pub fn relay_from_location() -> Option<String> {
+    let hash = web_sys::window()?.location().hash().ok()?;
+    let encoded = hash.strip_prefix(JOIN_PREFIX)?;
+    Some(js_sys::decode_uri_component(encoded).ok()?.into())
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub from: String,
+    pub text: String,
+}
+
+/// State of the connection. `as_ref` gives its text key.
+#[derive(AsRefStr, Clone, Copy, PartialEq)]
+enum Status {
+    #[strum(serialize = "chat_disconnected")]
+    Disconnected,
+    #[strum(serialize = "chat_connecting")]
+    Connecting,
+    #[strum(serialize = "chat_connected")]
+    Connected,
+    #[strum(serialize = "chat_failed")]
+    Failed,
+}
+
+pub struct Chat {
+    link: ComponentLink<Self>,
+    props: Props,
+    server: String,
+    name: String,
+    draft: String,
+    messages: Vec<ChatMessage>,
+    socket: Option<WebSocketTask>,
+    status: Status,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+}
+
+pub enum Msg {
+    UpdateServer(String),
+    UpdateName(String),
+    UpdateDraft(String),
+    Connect,
+    Disconnect,
+    Status(WebSocketStatus),
+    /// A message from the relay, or `None` if it was not one of ours.
+    Received(Option<ChatMessage>),
+    Send,
+    Nope,
+}
+
+impl Chat {
+    fn push(&mut self, message: ChatMessage) {
+        self.messages.push(message);
+        let excess = self.messages.len().saturating_sub(MAX_MESSAGES);
+        self.messages.drain(..excess);
+    }
+}
+
+impl Component for Chat {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Chat {
+            link,
+            props,
+            server: relay_from_location().unwrap_or_default(),
+            name: "".into(),
+            draft: "".into(),
+            messages: Vec::new(),
+            socket: None,
+            status: Status::Disconnected,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateServer(val) => self.server = val,
+            Msg::UpdateName(val) => self.name = val,
+            Msg::UpdateDraft(val) => self.draft = val,
+            Msg::Connect => {
+                let received = self
+                    .link
+                    .callback(|Json(message): Json<Result<ChatMessage, _>>| {
+                        Msg::Received(message.ok())
+                    });
+                let status = self.link.callback(Msg::Status);
+                match WebSocketService::connect_text(self.server.trim(), received, status) {
+                    Ok(socket) => {
+                        self.socket = Some(socket);
+                        self.status = Status::Connecting;
+                    }
+                    Err(_) => self.status = Status::Failed,
+                }
+            }
+            Msg::Disconnect => {
+                self.socket = None;
+                self.status = Status::Disconnected;
+            }
+            Msg::Status(status) => {
+                self.status = match status {
+                    WebSocketStatus::Opened => Status::Connected,
+                    WebSocketStatus::Closed => Status::Disconnected,
+                    WebSocketStatus::Error => Status::Failed,
+                };
+                if self.status != Status::Connected {
+                    self.socket = None;
+                }
+            }
+            Msg::Received(Some(message)) => self.push(message),
+            Msg::Received(None) => return false,
+            Msg::Send => {
+                let text = self.draft.trim();
+                let socket = match &mut self.socket {
+                    Some(socket) if !text.is_empty() => socket,
+                    _ => return false,
+                };
+                let message = ChatMessage {
+                    from: self.name.trim().to_string(),
+                    text: text.to_string(),
+                };
+                socket.send(Json(&message));
+                self.draft.clear();
+                self.push(message);
+            }
+            Msg::Nope => return false,
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        let connected = self.status == Status::Connected;
+        html! {
+            <div class="chat">
+                <label>
+                    { lang.t("chat_server") }
+                    <input type="url"
+                           placeholder="wss://"
+                           value=&self.server
+                           disabled=self.socket.is_some()
+                           oninput=self.link.callback(|e: InputData| Msg::UpdateServer(e.value)) />
+                </label>
+                <label>
+                    { lang.t("chat_name") }
+                    <input value=&self.name
+                           oninput=self.link.callback(|e: InputData| Msg::UpdateName(e.value)) />
+                </label>
+                { if self.socket.is_some() {
+                    html! { <button onclick=self.link.callback(|_| Msg::Disconnect)>{ lang.t("chat_disconnect") }</button> }
+                } else {
+                    html! { <button onclick=self.link.callback(|_| Msg::Connect)>{ lang.t("chat_connect") }</button> }
+                } }
+                <span class="chat-status" role="status">{ lang.t(self.status.as_ref()) }</span>
+                { match join_link(self.server.trim()) {
+                    Some(link) if connected => html! {
+                        <details class="chat-join">
+                            <summary>{ lang.t("chat_join_code") }</summary>
+                            <QrImage language=lang text=link />
+                        </details>
+                    },
+                    _ => html! {},
+                } }
+                <ol class="chat-messages" aria-live="polite">
+                    { for self.messages.iter().map(|message| html! {
+                        <li><strong>{ &message.from }</strong>{ ": " }{ &message.text }</li>
+                    }) }
+                </ol>
+                <input aria-label=lang.t("chat_message")
+                       value=&self.draft
+                       disabled=!connected
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateDraft(e.value))
+                       onkeypress=self.link.callback(|e: KeyboardEvent| {
+                           if e.key() == "Enter" { Msg::Send } else { Msg::Nope }
+                       }) />
+                <button disabled=!connected onclick=self.link.callback(|_| Msg::Send)>{ lang.t("chat_send") }</button>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! What resets when your turn ends. Every tracker with state that only
+//! lasts the turn lists it here, so ending a turn clears it all the same
+//! way, and a table can keep whatever its cards say lasts.
+
+use crate::game::Game;
+use serde_derive::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::{AsRefStr, EnumIter};
+
+/// One thing that resets at end of turn. `as_ref` gives its text key.
+#[derive(AsRefStr, Clone, Copy, Debug, EnumIter, PartialEq, Serialize, Deserialize)]
+pub enum Reset {
+    #[strum(serialize = "reset_storm")]
+    Storm,
+    /// The spells cast this turn and what each did to the pool.
+    #[strum(serialize = "reset_turn_casts")]
+    TurnCasts,
+    /// Mana left in the pool, unless something like Upwelling keeps it.
+    #[strum(serialize = "reset_floating_mana")]
+    FloatingMana,
+    /// A declared infinite mana combo.
+    #[strum(serialize = "reset_infinite_mana")]
+    InfiniteMana,
+    /// Prowess and magecraft pumps. Counters stay.
+    #[strum(serialize = "reset_pumps")]
+    Pumps,
+    /// Tokens made to last until end of turn.
+    #[strum(serialize = "reset_temporary_tokens")]
+    TemporaryTokens,
+    /// Celebr-8000 bonuses rolled this turn.
+    #[strum(serialize = "reset_celebr_bonuses")]
+    CelebrBonuses,
+    /// Upkeep flips, which come due again.
+    #[strum(serialize = "reset_upkeep_flips")]
+    UpkeepFlips,
+}
+
+impl Reset {
+    pub fn apply(self, game: &mut Game) {
+        match self {
+            Reset::Storm => {
+                game.storm = 0;
+                game.copies = 0;
+            }
+            Reset::TurnCasts => game.turn_casts.clear(),
+            Reset::FloatingMana => game.board.mana = 0,
+            Reset::InfiniteMana => game.board.infinite_mana = None,
+            Reset::Pumps => {
+                for creature in &mut game.board.creatures {
+                    creature.pumps = 0;
+                }
+            }
+            Reset::TemporaryTokens => game.board.tokens.retain(|t| !t.temporary),
+            Reset::CelebrBonuses => game.celebr_bonuses = None,
+            Reset::UpkeepFlips => game.new_upkeep(),
+        }
+    }
+}
+
+/// Ends the turn for `game`, resetting everything but what `kept` lists.
+pub fn run(game: &mut Game, kept: &[Reset]) {
+    game.turn_storms.push(game.storm);
+    for reset in Reset::iter().filter(|reset| !kept.contains(reset)) {
+        reset.apply(game);
+    }
+}
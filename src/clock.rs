@@ -0,0 +1,73 @@
+//! Per-player time banks for timed games, run like a chess clock: only the
+//! active player's bank runs down.
+
+/// Milliseconds in a minute, the unit time banks are configured in.
+pub const MINUTE: u64 = 60_000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TurnClock {
+    /// Milliseconds left to each player, in turn order.
+    pub banks: Vec<u64>,
+    pub active: usize,
+    /// Milliseconds the current turn has taken so far.
+    pub turn_elapsed: u64,
+    pub running: bool,
+}
+
+impl TurnClock {
+    /// A stopped clock giving each of `players` a bank of `bank`
+    /// milliseconds, with the first player to act.
+    pub fn new(players: usize, bank: u64) -> Self {
+        TurnClock {
+            banks: vec![bank; players],
+            active: 0,
+            turn_elapsed: 0,
+            running: false,
+        }
+    }
+
+    /// Charges `millis` to the active player, if the clock is running.
+    pub fn elapse(&mut self, millis: u64) {
+        if !self.running {
+            return;
+        }
+        if let Some(bank) = self.banks.get_mut(self.active) {
+            *bank = bank.saturating_sub(millis);
+        }
+        self.turn_elapsed += millis;
+    }
+
+    /// Hands the clock to the next player in turn order.
+    pub fn pass(&mut self) {
+        if !self.banks.is_empty() {
+            self.active = (self.active + 1) % self.banks.len();
+        }
+        self.turn_elapsed = 0;
+    }
+
+    /// Starts a turn for `player`, or for the first player if there is no
+    /// such player.
+    pub fn hand_to(&mut self, player: usize) {
+        self.active = if player < self.banks.len() { player } else { 0 };
+        self.turn_elapsed = 0;
+    }
+
+    /// Follows players joining or leaving. New players start with `bank`.
+    pub fn resize(&mut self, players: usize, bank: u64) {
+        self.banks.resize(players, bank);
+        if self.active >= players {
+            self.active = 0;
+        }
+    }
+
+    /// Whether `player` has run out of time.
+    pub fn flagged(&self, player: usize) -> bool {
+        self.banks.get(player) == Some(&0)
+    }
+}
+
+/// Milliseconds as `m:ss`, rounded down to the second.
+pub fn format_clock(millis: u64) -> String {
+    let seconds = millis / 1000;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
@@ -1,16 +1,327 @@
+use crate::card::FlipCard;
 use crate::coin_flip::FlipResult::{Both, Heads, Tails};
+use crate::history::FlipRecord;
 use core::fmt;
 use rand::distributions::Bernoulli;
-use rand::prelude::{Distribution, ThreadRng};
-use wasm_bindgen::prelude::*;
+use rand::prelude::{Distribution, Rng};
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, Display, EnumIter, EnumString};
 
 pub struct Coin;
 
+/// The side called before a flip.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Call {
+    #[default]
+    Heads,
+    Tails,
+}
+
+impl Call {
+    /// Whether `result` wins for this call. With a Krark's Thumb a mixed
+    /// result lets the caller keep the called side.
+    pub fn wins(self, result: &FlipResult) -> bool {
+        matches!(
+            (self, result),
+            (_, Both) | (Call::Heads, Heads) | (Call::Tails, Tails)
+        )
+    }
+}
+
+/// What counts as winning a card's flips, which differs from card to card.
+/// `as_ref` gives the condition's text key.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumIter,
+    EnumString,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WinCondition {
+    /// You call heads or tails.
+    #[default]
+    #[strum(serialize = "win_you_call")]
+    YouCall,
+    /// The coin must come up heads, as for Mana Clash.
+    #[strum(serialize = "win_heads")]
+    Heads,
+    /// An opponent calls and you win when they call wrong.
+    #[strum(serialize = "win_opponent_calls")]
+    OpponentCalls,
+}
+
+impl WinCondition {
+    /// The side that wins. Calls are taken to be heads, which a fair coin
+    /// makes no different from tails.
+    pub fn side(self) -> Call {
+        match self {
+            WinCondition::YouCall | WinCondition::Heads => Call::Heads,
+            WinCondition::OpponentCalls => Call::Tails,
+        }
+    }
+
+    /// Whether `result` wins. A Krark's Thumb lets you keep the winning
+    /// side of a mixed result whoever calls.
+    pub fn wins(self, result: &FlipResult) -> bool {
+        self.side().wins(result)
+    }
+}
+
+/// Why an engine setting was rejected. `as_ref` gives the error's text key.
+#[derive(AsRefStr, Clone, Copy, Debug, PartialEq)]
+pub enum ConfigError {
+    #[strum(serialize = "config_not_a_number")]
+    NotANumber,
+    #[strum(serialize = "config_negative")]
+    Negative,
+    #[strum(serialize = "config_no_flips")]
+    NoFlips,
+    #[strum(serialize = "config_cap_below_one")]
+    CapBelowOne,
+    #[strum(serialize = "config_no_trials")]
+    NoTrials,
+}
+
+/// Parses a count typed into a form, such as thumbs or trials.
+pub fn parse_count(text: &str) -> Result<usize, ConfigError> {
+    let text = text.trim();
+    text.parse().map_err(|_| match text.parse::<i64>() {
+        Ok(n) if n < 0 => ConfigError::Negative,
+        _ => ConfigError::NotANumber,
+    })
+}
+
+/// How a batch of coins is flipped, built up from the defaults:
+/// `FlipConfig::new().thumbs(2).flips(5).call(Call::Heads).cap(1000)`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FlipConfig {
+    thumbs: usize,
+    /// Flips to make, or `None` to keep flipping until one is lost.
+    flips: Option<usize>,
+    call: Call,
+    /// Most flips made, however many are asked for.
+    cap: usize,
+}
+
+impl Default for FlipConfig {
+    fn default() -> Self {
+        FlipConfig {
+            thumbs: 0,
+            flips: Some(1),
+            call: Call::Heads,
+            cap: usize::MAX,
+        }
+    }
+}
+
+impl FlipConfig {
+    /// One flip, calling heads, without Krark's Thumb.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Krark's Thumbs in play.
+    pub fn thumbs(mut self, thumbs: usize) -> Self {
+        self.thumbs = thumbs;
+        self
+    }
+
+    pub fn flips(mut self, flips: usize) -> Self {
+        self.flips = Some(flips);
+        self
+    }
+
+    /// Keeps flipping until a flip is lost, as Zndrsplt does.
+    pub fn until_loss(mut self) -> Self {
+        self.flips = None;
+        self
+    }
+
+    pub fn call(mut self, call: Call) -> Self {
+        self.call = call;
+        self
+    }
+
+    pub fn cap(mut self, cap: usize) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Rejects settings that would flip nothing.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.flips == Some(0) {
+            return Err(ConfigError::NoFlips);
+        }
+        if self.cap < 1 {
+            return Err(ConfigError::CapBelowOne);
+        }
+        Ok(())
+    }
+
+    pub fn flip<R: Rng>(&self, rng: &mut R) -> Vec<FlipResult> {
+        let flips = Coin::results(rng, self.thumbs).take(self.cap);
+        match self.flips {
+            Some(n) => flips.take(n).collect(),
+            None => flips.take_until_loss_calling(self.call).collect(),
+        }
+    }
+
+    /// Flips as [`FlipConfig::flip`] does, but keeps every coin tossed so
+    /// the ones Krark's Thumb discarded can be shown.
+    pub fn toss<R: Rng>(&self, rng: &mut R) -> Vec<Toss> {
+        let tosses = Coin::tosses(rng, self.thumbs).take(self.cap);
+        match self.flips {
+            Some(n) => tosses.take(n).collect(),
+            None => {
+                let mut chain = Vec::new();
+                for toss in tosses {
+                    let lost = !self.call.wins(&toss.result());
+                    chain.push(toss);
+                    if lost {
+                        break;
+                    }
+                }
+                chain
+            }
+        }
+    }
+}
+
+/// Most coins kept for one flip. Past this many, a flip that doesn't show
+/// both sides is rarer than f64 precision, so the rest change nothing.
+const MAX_TOSSED_COINS: usize = 64;
+
+/// Every coin tossed for one flip. Each Krark's Thumb doubles the coins,
+/// and the caller keeps one of them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toss {
+    /// The side each coin landed on.
+    pub coins: Vec<Call>,
+}
+
+impl Toss {
+    /// The flip these coins make: `Both` if the caller had a choice.
+    pub fn result(&self) -> FlipResult {
+        let heads = self.coins.contains(&Call::Heads);
+        let tails = self.coins.contains(&Call::Tails);
+        match (heads, tails) {
+            (true, true) => Both,
+            (true, false) => Heads,
+            _ => Tails,
+        }
+    }
+
+    /// The coin kept when calling `call`: the first to land that way, or
+    /// the first coin if none did. The others are discarded.
+    pub fn kept(&self, call: Call) -> usize {
+        self.coins
+            .iter()
+            .position(|&coin| coin == call)
+            .unwrap_or(0)
+    }
+}
+
+/// Combinators for runs of flips, so features can describe a chain instead
+/// of looping over it.
+pub trait FlipStream: Iterator<Item = FlipResult> + Sized {
+    /// Flips up to and including the first one lost when calling heads.
+    fn take_until_loss(self) -> TakeUntilLoss<Self> {
+        self.take_until_loss_calling(Call::Heads)
+    }
+
+    /// Flips up to and including the first one lost for `call`.
+    fn take_until_loss_calling(self, call: Call) -> TakeUntilLoss<Self> {
+        TakeUntilLoss {
+            flips: self,
+            call,
+            done: false,
+        }
+    }
+
+    /// Tags each flip with the card and activation that caused it, and
+    /// what won for that card.
+    fn with_provenance(
+        self,
+        source: FlipCard,
+        win: WinCondition,
+        activation: usize,
+        thumbs: usize,
+    ) -> WithProvenance<Self> {
+        WithProvenance {
+            flips: self,
+            source,
+            win,
+            activation,
+            thumbs,
+        }
+    }
+}
+
+impl<I: Iterator<Item = FlipResult>> FlipStream for I {}
+
+/// See [`FlipStream::take_until_loss`].
+pub struct TakeUntilLoss<I> {
+    flips: I,
+    call: Call,
+    done: bool,
+}
+
+impl<I: Iterator<Item = FlipResult>> Iterator for TakeUntilLoss<I> {
+    type Item = FlipResult;
+
+    fn next(&mut self) -> Option<FlipResult> {
+        if self.done {
+            return None;
+        }
+        let flip = self.flips.next()?;
+        self.done = !self.call.wins(&flip);
+        Some(flip)
+    }
+}
+
+/// See [`FlipStream::with_provenance`].
+pub struct WithProvenance<I> {
+    flips: I,
+    source: FlipCard,
+    win: WinCondition,
+    activation: usize,
+    thumbs: usize,
+}
+
+impl<I: Iterator<Item = FlipResult>> Iterator for WithProvenance<I> {
+    type Item = FlipRecord;
+
+    fn next(&mut self) -> Option<FlipRecord> {
+        let result = self.flips.next()?;
+        Some(FlipRecord {
+            source: self.source,
+            activation: self.activation,
+            thumbs: self.thumbs,
+            player: None,
+            win: self.win,
+            result,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.flips.size_hint()
+    }
+}
+
 impl Coin {
-    pub fn flip(
-        mut rng: &mut ThreadRng,
+    /// An endless run of flips, each keeping the best of the coins that
+    /// `thumb_count` Krark's Thumbs allow.
+    fn results<R: Rng>(
+        mut rng: &mut R,
         thumb_count: usize,
-        times: usize,
     ) -> impl Iterator<Item = FlipResult> + '_ {
         std::iter::from_fn(move || {
             let coins_per_flip = 2_usize.saturating_pow(thumb_count as u32);
@@ -37,17 +348,108 @@ impl Coin {
                 Some(Tails)
             }
         })
-        .take(times)
+    }
+
+    /// An endless run of flips, each tossing every coin that
+    /// `thumb_count` Krark's Thumbs allow.
+    fn tosses<R: Rng>(rng: &mut R, thumb_count: usize) -> impl Iterator<Item = Toss> + '_ {
+        let coins = 2_usize
+            .saturating_pow(thumb_count as u32)
+            .min(MAX_TOSSED_COINS);
+        std::iter::from_fn(move || {
+            let coins = (0..coins)
+                .map(|_| {
+                    if rng.gen_bool(0.5) {
+                        Call::Heads
+                    } else {
+                        Call::Tails
+                    }
+                })
+                .collect();
+            Some(Toss { coins })
+        })
+    }
+
+    /// Chance that a single flip is won when `thumb_count` Krark's Thumbs
+    /// let the caller keep the best of several coins.
+    pub fn win_chance(thumb_count: usize) -> f64 {
+        let coins_per_flip = 2_usize.saturating_pow(thumb_count as u32);
+        // Past 64 coins the chance of losing is below f64 precision anyway.
+        1.0 - 0.5_f64.powi(coins_per_flip.min(64) as i32)
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Hash)]
+/// Words of random bits drawn from the generator at a time.
+const BIT_BLOCK: usize = 64;
+
+/// Flips coins from a buffer of random bits, one bit per coin, so long
+/// simulations call the generator once per few thousand coins instead of
+/// once per coin. Gives different flips than [`FlipConfig::flip`] for the
+/// same seed.
+pub struct CoinBits<'a, R> {
+    rng: &'a mut R,
+    block: [u64; BIT_BLOCK],
+    /// Bits of `block` already used.
+    used: usize,
+}
+
+impl<'a, R: Rng> CoinBits<'a, R> {
+    pub fn new(rng: &'a mut R) -> Self {
+        CoinBits {
+            rng,
+            block: [0; BIT_BLOCK],
+            used: BIT_BLOCK * 64,
+        }
+    }
+
+    fn coin(&mut self) -> bool {
+        if self.used == BIT_BLOCK * 64 {
+            self.rng.fill(&mut self.block[..]);
+            self.used = 0;
+        }
+        let heads = self.block[self.used / 64] >> (self.used % 64) & 1 == 1;
+        self.used += 1;
+        heads
+    }
+
+    /// One flip with `thumb_count` Krark's Thumbs, as [`FlipConfig::flip`]
+    /// makes them.
+    pub fn flip(&mut self, thumb_count: usize) -> FlipResult {
+        let coins_per_flip = 2_usize.saturating_pow(thumb_count as u32);
+        let (mut seen_heads, mut seen_tails) = (false, false);
+        for _ in 0..coins_per_flip {
+            if self.coin() {
+                seen_heads = true;
+            } else {
+                seen_tails = true;
+            }
+            if seen_heads && seen_tails {
+                return Both;
+            }
+        }
+        if seen_heads {
+            Heads
+        } else {
+            Tails
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum FlipResult {
     Heads,
     Tails,
     Both,
 }
 
+impl FlipResult {
+    /// Whether the flip counts as won when calling heads. With a Krark's Thumb
+    /// a mixed result lets the caller keep the heads coin.
+    pub fn is_win(&self) -> bool {
+        matches!(self, Heads | Both)
+    }
+}
+
 impl fmt::Display for FlipResult {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self)
@@ -60,3 +462,49 @@ pub enum FlipStrategy {
 }
 
 pub struct GameContext {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    fn flips(seed: u64, thumbs: usize, n: usize) -> Vec<FlipResult> {
+        let mut rng = ChaCha12Rng::seed_from_u64(seed);
+        let mut coins = CoinBits::new(&mut rng);
+        (0..n).map(|_| coins.flip(thumbs)).collect()
+    }
+
+    #[test]
+    fn coin_bits_repeat_for_a_seed() {
+        assert_eq!(flips(3, 1, 500), flips(3, 1, 500));
+        assert_ne!(flips(3, 1, 500), flips(4, 1, 500));
+    }
+
+    #[test]
+    fn coin_bits_without_thumbs_never_give_both() {
+        let flips = flips(1, 0, 10_000);
+        assert!(!flips.contains(&Both));
+        let heads = flips.iter().filter(|&f| *f == Heads).count();
+        assert!((heads as f64 / 10_000.0 - 0.5).abs() < 0.02, "{}", heads);
+    }
+
+    #[test]
+    fn coin_bits_win_as_often_as_a_thumb_allows() {
+        // Enough flips to run through several blocks of bits.
+        for thumbs in 0..4 {
+            let flips = flips(2, thumbs, 20_000);
+            let won = flips.iter().filter(|f| f.is_win()).count() as f64 / 20_000.0;
+            let expected = Coin::win_chance(thumbs);
+            assert!((won - expected).abs() < 0.015, "{} thumbs: {}", thumbs, won);
+        }
+    }
+
+    #[test]
+    fn coin_bits_give_both_only_with_a_thumb() {
+        let flips = flips(5, 1, 1_000);
+        assert!(flips.contains(&Both));
+        assert!(flips.contains(&Heads));
+        assert!(flips.contains(&Tails));
+    }
+}
@@ -0,0 +1,264 @@
+//! Coin faces for flip results: built-in skins, or pictures the user
+//! uploaded, kept in the settings as data URLs.
+
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use serde_derive::{Deserialize, Serialize};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use strum_macros::{AsRefStr, Display, EnumIter, EnumString};
+use yew::prelude::*;
+use yew::services::reader::{File, FileData, ReaderService, ReaderTask};
+
+/// Largest picture accepted for a face. Settings live in local storage,
+/// which only holds a few megabytes.
+const MAX_IMAGE_BYTES: usize = 256 * 1024;
+
+/// `as_ref` gives the skin's text key.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumIter,
+    EnumString,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub enum CoinSkin {
+    /// The win and loss arrows, shown only with outcome icons on.
+    #[default]
+    #[strum(serialize = "skin_arrows")]
+    Arrows,
+    #[strum(serialize = "skin_classic")]
+    Classic,
+    #[strum(serialize = "skin_thumbs")]
+    Thumbs,
+    #[strum(serialize = "skin_crown")]
+    Crown,
+    /// The user's uploaded pictures, falling back to Classic for a face
+    /// without one.
+    #[strum(serialize = "skin_custom")]
+    Custom,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CoinFaces {
+    pub skin: CoinSkin,
+    /// Uploaded pictures as data URLs.
+    pub heads: Option<String>,
+    pub tails: Option<String>,
+}
+
+pub enum Face<'a> {
+    Text(&'static str),
+    Image(&'a str),
+}
+
+impl CoinFaces {
+    /// What a heads or tails result shows, or `None` for the plain
+    /// arrows.
+    pub fn face(&self, heads: bool) -> Option<Face<'_>> {
+        let text = |h, t| Some(Face::Text(if heads { h } else { t }));
+        match self.skin {
+            CoinSkin::Arrows => None,
+            CoinSkin::Classic => text("H", "T"),
+            CoinSkin::Thumbs => text("👍", "👎"),
+            CoinSkin::Crown => text("👑", "💀"),
+            CoinSkin::Custom => {
+                let image = if heads { &self.heads } else { &self.tails };
+                match image {
+                    Some(url) => Some(Face::Image(url)),
+                    None => text("H", "T"),
+                }
+            }
+        }
+    }
+
+    /// The face for a won (heads) or lost flip.
+    pub fn view(&self, heads: bool) -> Html {
+        let class = if heads {
+            "coin-face win"
+        } else {
+            "coin-face loss"
+        };
+        match self.face(heads) {
+            None => outcome_icon(heads),
+            Some(Face::Text(text)) => {
+                html! { <span class=class aria-hidden="true">{ text }</span> }
+            }
+            Some(Face::Image(url)) => html! { <img class=class src=url alt="" /> },
+        }
+    }
+}
+
+/// Shape marking a won or lost flip, shown when outcome icons are enabled.
+pub fn outcome_icon(win: bool) -> Html {
+    let (class, icon) = if win {
+        ("outcome-icon win", "▲")
+    } else {
+        ("outcome-icon loss", "▼")
+    };
+    html! { <span class=class aria-hidden="true">{ icon }</span> }
+}
+
+/// Picks a skin and uploads custom faces.
+pub struct CoinSkinPicker {
+    link: ComponentLink<Self>,
+    props: Props,
+    reader: ReaderService,
+    _task: Option<ReaderTask>,
+    /// MIME type of the picture being read.
+    uploading: String,
+    too_large: bool,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub faces: CoinFaces,
+    pub on_change: Callback<CoinFaces>,
+}
+
+pub enum Msg {
+    SetSkin(CoinSkin),
+    Upload(bool, File),
+    Uploaded(bool, FileData),
+    Clear(bool),
+    Nope,
+}
+
+impl CoinSkinPicker {
+    fn edit(&self, update: impl FnOnce(&mut CoinFaces)) {
+        let mut faces = self.props.faces.clone();
+        update(&mut faces);
+        self.props.on_change.emit(faces);
+    }
+
+    fn view_upload(&self, heads: bool) -> Html {
+        let lang = self.props.language;
+        let (label, image) = if heads {
+            ("heads", &self.props.faces.heads)
+        } else {
+            ("tails", &self.props.faces.tails)
+        };
+        html! {
+            <div class="coin-upload">
+                <label>
+                    { lang.t(label) }
+                    <input type="file" accept="image/*"
+                           onchange=self.link.callback(move |e: ChangeData| match e {
+                               ChangeData::Files(files) => files
+                                   .get(0)
+                                   .map(|file| Msg::Upload(heads, file))
+                                   .unwrap_or(Msg::Nope),
+                               _ => Msg::Nope,
+                           }) />
+                </label>
+                { for image.iter().map(|url| html! {
+                    <>
+                        <img class="coin-face" src=url alt=lang.t(label) />
+                        <button onclick=self.link.callback(move |_| Msg::Clear(heads))>{ lang.t("remove") }</button>
+                    </>
+                }) }
+            </div>
+        }
+    }
+}
+
+impl Component for CoinSkinPicker {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        CoinSkinPicker {
+            link,
+            props,
+            reader: ReaderService::new(),
+            _task: None,
+            uploading: String::new(),
+            too_large: false,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::SetSkin(skin) => self.edit(|faces| faces.skin = skin),
+            Msg::Upload(heads, file) => {
+                self.too_large = file.size() > MAX_IMAGE_BYTES as f64;
+                if self.too_large {
+                    return true;
+                }
+                self.uploading = file.type_();
+                let callback = self.link.callback(move |data| Msg::Uploaded(heads, data));
+                self._task = self.reader.read_file(file, callback).ok();
+            }
+            Msg::Uploaded(heads, data) => {
+                self._task = None;
+                // btoa takes one character per byte.
+                let binary = data
+                    .content
+                    .iter()
+                    .map(|&b| char::from(b))
+                    .collect::<String>();
+                let encoded = match web_sys::window().map(|w| w.btoa(&binary)) {
+                    Some(Ok(encoded)) => encoded,
+                    _ => return false,
+                };
+                let url = format!("data:{};base64,{}", self.uploading, encoded);
+                self.edit(|faces| {
+                    faces.skin = CoinSkin::Custom;
+                    if heads {
+                        faces.heads = Some(url);
+                    } else {
+                        faces.tails = Some(url);
+                    }
+                });
+            }
+            Msg::Clear(heads) => self.edit(|faces| {
+                if heads {
+                    faces.heads = None;
+                } else {
+                    faces.tails = None;
+                }
+            }),
+            Msg::Nope => return false,
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        let skin = self.props.faces.skin;
+        html! {
+            <fieldset class="coin-skin">
+                <legend>{ lang.t("coin_skin") }</legend>
+                <select onchange=self.link.callback(|e: ChangeData| match e {
+                    ChangeData::Select(select) => CoinSkin::from_str(&select.value())
+                        .map(Msg::SetSkin)
+                        .unwrap_or(Msg::Nope),
+                    _ => Msg::Nope,
+                })>
+                    { for CoinSkin::iter().map(|s| html! {
+                        <option value=s selected=s == skin>{ lang.t(s.as_ref()) }</option>
+                    }) }
+                </select>
+                { self.view_upload(true) }
+                { self.view_upload(false) }
+                { if self.too_large {
+                    html! { <p class="error" role="alert">{ lang.t("coin_image_too_large") }</p> }
+                } else {
+                    html! {}
+                } }
+            </fieldset>
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Flips made in combat, such as Tide of War's, whose outcome lands on
+//! the creatures of the attacking or blocking player. Whoever controls
+//! the card flips, which need not be you.
+
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumIter};
+
+/// Creatures an outcome lands on. `as_ref` gives the text key of the
+/// sentence saying what happened to them.
+#[derive(AsRefStr, Clone, Copy, Debug, PartialEq)]
+pub enum Side {
+    /// The blocking creatures, controlled by the blocking player.
+    #[strum(serialize = "combat_blocking")]
+    Blocking,
+    /// The blocked creatures, controlled by the attacking player.
+    #[strum(serialize = "combat_blocked")]
+    Blocked,
+}
+
+/// What happens to the creatures. `as_ref` gives its text key.
+#[derive(AsRefStr, Clone, Copy, Debug, PartialEq)]
+pub enum Fate {
+    #[strum(serialize = "fate_sacrificed")]
+    Sacrificed,
+    #[strum(serialize = "fate_destroyed")]
+    Destroyed,
+    #[strum(serialize = "fate_returned")]
+    Returned,
+}
+
+/// What a combat card does for a won and a lost flip.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CombatEffect {
+    pub won: (Side, Fate),
+    pub lost: (Side, Fate),
+}
+
+impl CombatEffect {
+    pub fn outcome(&self, won: bool) -> (Side, Fate) {
+        if won {
+            self.won
+        } else {
+            self.lost
+        }
+    }
+}
+
+/// Parts played in a combat flip. `as_ref` gives the role's text key.
+#[derive(AsRefStr, Clone, Copy, Debug, EnumIter, PartialEq)]
+pub enum Role {
+    #[strum(serialize = "combat_flipper")]
+    Flipper,
+    #[strum(serialize = "combat_attacker")]
+    Attacker,
+    #[strum(serialize = "combat_blocker")]
+    Blocker,
+}
+
+/// Who takes part in a combat flip. Players are numbered 0 for you, then
+/// the opponents in seating order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Combat {
+    /// Controller of the card, who flips.
+    pub flipper: usize,
+    pub attacker: usize,
+    pub blocker: usize,
+}
+
+impl Combat {
+    pub fn player(&self, role: Role) -> usize {
+        match role {
+            Role::Flipper => self.flipper,
+            Role::Attacker => self.attacker,
+            Role::Blocker => self.blocker,
+        }
+    }
+
+    pub fn set_player(&mut self, role: Role, player: usize) {
+        match role {
+            Role::Flipper => self.flipper = player,
+            Role::Attacker => self.attacker = player,
+            Role::Blocker => self.blocker = player,
+        }
+    }
+
+    /// Controller of the creatures on `side`.
+    pub fn controller(&self, side: Side) -> usize {
+        match side {
+            Side::Blocking => self.blocker,
+            Side::Blocked => self.attacker,
+        }
+    }
+
+    /// Whether every player taken part is still at the table.
+    pub fn seated(&self, players: usize) -> bool {
+        [self.flipper, self.attacker, self.blocker]
+            .iter()
+            .all(|&player| player < players)
+    }
+}
@@ -0,0 +1,141 @@
+use crate::i18n::Language;
+use crate::modal::Modal;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// Scores how well `query` matches `label` as a case-insensitive
+/// subsequence, or `None` if it does not match. Consecutive and early
+/// matches score higher.
+pub fn fuzzy_score(query: &str, label: &str) -> Option<i32> {
+    let label = label.to_lowercase();
+    let mut chars = label.char_indices();
+    let mut score = 0;
+    let mut last = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let (idx, _) = chars.find(|&(_, c)| c == q)?;
+        score += match last {
+            Some(prev) if prev + 1 == idx => 3,
+            _ => 1,
+        };
+        if idx == 0 {
+            score += 2;
+        }
+        last = Some(idx);
+    }
+    Some(score * 100 - label.len() as i32)
+}
+
+/// A searchable list of every action in the app, opened with Ctrl+K.
+pub struct CommandPalette {
+    link: ComponentLink<Self>,
+    props: Props,
+    query: String,
+    selected: usize,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    /// Labels of the available commands; the palette reports the index of
+    /// the one chosen.
+    pub commands: Vec<String>,
+    pub on_run: Callback<usize>,
+    pub on_close: Callback<()>,
+}
+
+pub enum Msg {
+    UpdateQuery(String),
+    KeyDown(KeyboardEvent),
+    Run(usize),
+}
+
+impl CommandPalette {
+    /// Indices of the commands matching the query, best first.
+    fn matches(&self) -> Vec<usize> {
+        let mut scored = self
+            .props
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, label)| fuzzy_score(&self.query, label).map(|s| (s, idx)))
+            .collect::<Vec<_>>();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        scored.into_iter().map(|(_, idx)| idx).collect()
+    }
+}
+
+impl Component for CommandPalette {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        CommandPalette {
+            link,
+            props,
+            query: "".into(),
+            selected: 0,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateQuery(query) => {
+                self.query = query;
+                self.selected = 0;
+            }
+            Msg::KeyDown(event) => {
+                let matches = self.matches();
+                match event.key().as_str() {
+                    "ArrowDown" if self.selected + 1 < matches.len() => self.selected += 1,
+                    "ArrowUp" => self.selected = self.selected.saturating_sub(1),
+                    "Enter" => {
+                        if let Some(&idx) = matches.get(self.selected) {
+                            self.props.on_run.emit(idx);
+                        }
+                        return false;
+                    }
+                    _ => return false,
+                }
+                event.prevent_default();
+            }
+            Msg::Run(idx) => {
+                self.props.on_run.emit(idx);
+                return false;
+            }
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <Modal language=lang title=lang.t("commands") on_close=self.props.on_close.clone()>
+                <input class="command-search"
+                       autofocus=true
+                       placeholder=lang.t("command_search")
+                       aria-label=lang.t("command_search")
+                       value=&self.query
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateQuery(e.value))
+                       onkeydown=self.link.callback(Msg::KeyDown) />
+                <ul class="commands" role="listbox">
+                    { for self.matches().into_iter().enumerate().map(|(pos, idx)| {
+                        let selected = pos == self.selected;
+                        html! {
+                            <li role="option" aria-selected=selected.to_string()>
+                                <button class=if selected { "selected" } else { "" }
+                                        tabindex="-1"
+                                        onclick=self.link.callback(move |_| Msg::Run(idx))>
+                                    { &self.props.commands[idx] }
+                                </button>
+                            </li>
+                        }
+                    }) }
+                </ul>
+            </Modal>
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::coin_flip::{parse_count, ConfigError, FlipConfig};
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// The main buttons: flip, fast-forward, end turn, undo and the rest.
+/// Children go first, for choosing what to flip for.
+pub struct ControlPanel {
+    link: ComponentLink<Self>,
+    props: Props,
+    fast_forward: String,
+    fast_forward_error: Option<ConfigError>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    /// Most activations one fast-forward runs.
+    pub max_times: usize,
+    pub can_undo: bool,
+    pub listening: bool,
+    pub settings_open: bool,
+    #[prop_or_default]
+    pub children: Children,
+    /// Flips this many times in a row.
+    pub on_flip: Callback<usize>,
+    pub on_end_turn: Callback<()>,
+    pub on_undo: Callback<()>,
+    pub on_toggle_voice: Callback<()>,
+    pub on_save: Callback<()>,
+    pub on_clear: Callback<()>,
+    pub on_toggle_settings: Callback<()>,
+}
+
+pub enum Msg {
+    UpdateFastForward(String),
+}
+
+impl Component for ControlPanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        ControlPanel {
+            link,
+            props,
+            fast_forward: "10".into(),
+            fast_forward_error: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateFastForward(val) => {
+                self.fast_forward_error = parse_count(&val)
+                    .and_then(|times| FlipConfig::new().flips(times).validate())
+                    .err();
+                self.fast_forward.neq_assign(val)
+            }
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let props = &self.props;
+        let lang = props.language;
+        let times = parse_count(&self.fast_forward)
+            .unwrap_or(0)
+            .min(props.max_times);
+        html! {
+            <div class="controls">
+                { props.children.clone() }
+                <button class="flip" onclick=props.on_flip.reform(|_| 1)>{ lang.t("flip") }</button>
+                <input class="fast-forward" type="number" min="1"
+                       aria-label=lang.t("fast_forward")
+                       value=&self.fast_forward
+                       aria-invalid=self.fast_forward_error.is_some().to_string()
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateFastForward(e.value)) />
+                { for self.fast_forward_error.map(|error| html! {
+                    <p class="error" role="alert">{ lang.t(error.as_ref()) }</p>
+                }) }
+                <button class="fast-forward" onclick=props.on_flip.reform(move |_| times)>
+                    { lang.fmt("flip_times", &[&times]) }
+                </button>
+                <button class="end-turn" onclick=props.on_end_turn.reform(|_| ())>{ lang.t("end_turn") }</button>
+                <button class="undo"
+                        disabled=!props.can_undo
+                        onclick=props.on_undo.reform(|_| ())>{ lang.t("undo") }</button>
+                <button class="voice"
+                        aria-pressed=props.listening.to_string()
+                        onclick=props.on_toggle_voice.reform(|_| ())>{ lang.t("voice_commands") }</button>
+                <button class="save" onclick=props.on_save.reform(|_| ())>{ lang.t("save_session") }</button>
+                <button class="clear" onclick=props.on_clear.reform(|_| ())>{ lang.t("clear") }</button>
+                <button class="open-settings"
+                        aria-expanded=props.settings_open.to_string()
+                        onclick=props.on_toggle_settings.reform(|_| ())>{ lang.t("settings") }</button>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::coin_flip::{parse_count, ConfigError};
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// A labelled number box for a count, handing on each count that parses
+/// and saying why one was turned down until the next one does.
+pub struct CountInput {
+    link: ComponentLink<Self>,
+    props: Props,
+    error: Option<ConfigError>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub label: String,
+    pub value: usize,
+    pub on_change: Callback<usize>,
+}
+
+impl Component for CountInput {
+    /// The text typed.
+    type Message = String;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        CountInput {
+            link,
+            props,
+            error: None,
+        }
+    }
+
+    fn update(&mut self, text: Self::Message) -> ShouldRender {
+        match parse_count(&text) {
+            Ok(count) => {
+                self.props.on_change.emit(count);
+                self.error.take().is_some()
+            }
+            Err(error) => self.error.neq_assign(Some(error)),
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <>
+                <label>
+                    { &self.props.label }
+                    <input type="number" min="0"
+                           value=self.props.value
+                           aria-invalid=self.error.is_some().to_string()
+                           oninput=self.link.callback(|e: InputData| e.value) />
+                </label>
+                { for self.error.map(|error| html! {
+                    <p class="error" role="alert">{ lang.t(error.as_ref()) }</p>
+                }) }
+            </>
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use yew::prelude::*;
+
+/// A labelled count with buttons to step it down and up.
+pub struct Counter {
+    link: ComponentLink<Self>,
+    props: Props,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub label: String,
+    pub value: i64,
+    /// Accessible names of the step buttons.
+    pub decrement_label: String,
+    pub increment_label: String,
+    /// Receives the step, -1 or 1.
+    pub on_step: Callback<i32>,
+}
+
+impl Component for Counter {
+    /// The step taken, handed on to whatever `on_step` is by then.
+    type Message = i32;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Counter { link, props }
+    }
+
+    fn update(&mut self, step: Self::Message) -> ShouldRender {
+        self.props.on_step.emit(step);
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Counters in lists get a new `on_step` on every render of the
+        // parent, so only the data decides whether to render again.
+        let changed = self.props.label != props.label
+            || self.props.value != props.value
+            || self.props.decrement_label != props.decrement_label
+            || self.props.increment_label != props.increment_label;
+        self.props = props;
+        changed
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <span class="counter">
+                <button aria-label=&self.props.decrement_label
+                        onclick=self.link.callback(|_| -1)>{ "−" }</button>
+                <span class="counter-value">{ self.props.value }</span>
+                <button aria-label=&self.props.increment_label
+                        onclick=self.link.callback(|_| 1)>{ "+" }</button>
+                { &self.props.label }
+            </span>
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::cast::CastOutcome;
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, Display, EnumIter, EnumString};
+
+/// What pumps a creature. `as_ref` gives the trigger's text key.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumIter,
+    EnumString,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub enum PumpTrigger {
+    /// Once per noncreature spell cast. Copies are not cast.
+    #[default]
+    #[strum(serialize = "prowess")]
+    Prowess,
+    /// Once per instant or sorcery cast or copied.
+    #[strum(serialize = "magecraft")]
+    Magecraft,
+}
+
+/// Grows with a +1/+1 counter whenever you roll one or more dice, and
+/// gives every roll advantage.
+pub const WYLL: &str = "Wyll, Blade of Frontiers";
+
+/// A creature that grows as spells are cast this turn.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Creature {
+    pub name: String,
+    pub power: i32,
+    pub toughness: i32,
+    pub trigger: PumpTrigger,
+    /// Power and toughness gained per trigger.
+    pub bonus: (i32, i32),
+    /// Triggers resolved this turn.
+    pub pumps: u32,
+    /// +1/+1 counters, which stay after the turn ends.
+    #[serde(default)]
+    pub counters: u32,
+}
+
+impl Creature {
+    /// Counts the triggers from one cast spell and its copies.
+    pub fn on_cast(&mut self, outcome: &CastOutcome) {
+        self.pumps += match self.trigger {
+            PumpTrigger::Prowess => 1,
+            PumpTrigger::Magecraft => 1 + outcome.copies(),
+        };
+    }
+
+    /// Power and toughness after counters and this turn's pumps.
+    pub fn current(&self) -> (i32, i32) {
+        let pumps = self.pumps as i32;
+        let counters = self.counters as i32;
+        (
+            self.power + counters + self.bonus.0 * pumps,
+            self.toughness + counters + self.bonus.1 * pumps,
+        )
+    }
+
+    /// Whether this is Wyll, Blade of Frontiers.
+    pub fn is_wyll(&self) -> bool {
+        self.name.eq_ignore_ascii_case(WYLL)
+    }
+}
+
+/// Parses power and toughness written as `2/3` or `+1/+1`.
+pub fn parse_pt(text: &str) -> Option<(i32, i32)> {
+    let (power, toughness) = text.split_once('/')?;
+    let parse = |s: &str| s.trim().trim_start_matches('+').parse().ok();
+    Some((parse(power)?, parse(toughness)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cast::{Spell, SpellKind};
+    use crate::coin_flip::{FlipResult, WinCondition};
+
+    fn creature(trigger: PumpTrigger) -> Creature {
+        Creature {
+            name: "Sprite Dragon".into(),
+            power: 1,
+            toughness: 1,
+            trigger,
+            bonus: (1, 1),
+            pumps: 0,
+            counters: 0,
+        }
+    }
+
+    fn spell(name: &str, storm: bool) -> Spell {
+        Spell {
+            name: name.into(),
+            mana_value: 1,
+            kind: SpellKind::Instant,
+            draws: 0,
+            storm,
+        }
+    }
+
+    #[test]
+    fn magecraft_counts_only_the_copies_made() {
+        let flips = [FlipResult::Heads, FlipResult::Tails];
+        let win = WinCondition::YouCall;
+        let mut magecraft = creature(PumpTrigger::Magecraft);
+        magecraft.on_cast(&CastOutcome::new(&spell("Opt", false), 5, &flips, win));
+        assert_eq!(magecraft.pumps, 2);
+        magecraft.on_cast(&CastOutcome::new(&spell("Grapeshot", true), 5, &flips, win));
+        assert_eq!(magecraft.pumps, 2 + 7);
+        assert_eq!(magecraft.current(), (10, 10));
+
+        let mut prowess = creature(PumpTrigger::Prowess);
+        prowess.on_cast(&CastOutcome::new(&spell("Grapeshot", true), 5, &flips, win));
+        assert_eq!(prowess.pumps, 1);
+    }
+}
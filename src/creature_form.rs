@@ -0,0 +1,113 @@
+use crate::creature::{parse_pt, Creature, PumpTrigger};
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+/// The form adding a creature pumped by prowess or magecraft. Power and
+/// toughness that do not parse count as 0/0, and the pump as +1/+1.
+pub struct CreatureForm {
+    link: ComponentLink<Self>,
+    props: Props,
+    name: String,
+    pt: String,
+    bonus: String,
+    trigger: PumpTrigger,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub on_add: Callback<Creature>,
+}
+
+pub enum Msg {
+    UpdateName(String),
+    UpdatePt(String),
+    UpdateBonus(String),
+    SetTrigger(PumpTrigger),
+    Add,
+    Nope,
+}
+
+impl Component for CreatureForm {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        CreatureForm {
+            link,
+            props,
+            name: String::new(),
+            pt: String::new(),
+            bonus: "+1/+1".into(),
+            trigger: PumpTrigger::default(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateName(val) => self.name.neq_assign(val),
+            Msg::UpdatePt(val) => self.pt.neq_assign(val),
+            Msg::UpdateBonus(val) => self.bonus.neq_assign(val),
+            Msg::SetTrigger(trigger) => self.trigger.neq_assign(trigger),
+            Msg::Add => {
+                let name = self.name.trim();
+                if name.is_empty() {
+                    return false;
+                }
+                let (power, toughness) = parse_pt(&self.pt).unwrap_or((0, 0));
+                self.props.on_add.emit(Creature {
+                    name: name.to_string(),
+                    power,
+                    toughness,
+                    trigger: self.trigger,
+                    bonus: parse_pt(&self.bonus).unwrap_or((1, 1)),
+                    pumps: 0,
+                    counters: 0,
+                });
+                self.name.clear();
+                self.pt.clear();
+                true
+            }
+            Msg::Nope => false,
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <div class="creature-form">
+                <input placeholder=lang.t("creature_name")
+                       aria-label=lang.t("creature_name")
+                       value=&self.name
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateName(e.value)) />
+                <input class="creature-pt" placeholder="2/2"
+                       aria-label=lang.t("creature_pt")
+                       value=&self.pt
+                       oninput=self.link.callback(|e: InputData| Msg::UpdatePt(e.value)) />
+                <select aria-label=lang.t("creature_trigger")
+                        onchange=self.link.callback(|e: ChangeData| match e {
+                    ChangeData::Select(select) => PumpTrigger::from_str(&select.value())
+                        .map(Msg::SetTrigger)
+                        .unwrap_or(Msg::Nope),
+                    _ => Msg::Nope,
+                })>
+                    { for PumpTrigger::iter().map(|trigger| html! {
+                        <option value=trigger selected=trigger == self.trigger>{ lang.t(trigger.as_ref()) }</option>
+                    }) }
+                </select>
+                <input class="creature-pt"
+                       aria-label=lang.t("creature_bonus")
+                       value=&self.bonus
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateBonus(e.value)) />
+                <button onclick=self.link.callback(|_| Msg::Add)>{ lang.t("add_creature") }</button>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::creature::Creature;
+use crate::creature_form::CreatureForm;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// Creatures pumped by prowess or magecraft, at their size after this
+/// turn's casts. Adding or removing one hands back the whole new list.
+pub struct CreatureList {
+    link: ComponentLink<Self>,
+    props: Props,
+    on_add: Callback<Creature>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub creatures: Vec<Creature>,
+    pub on_change: Callback<Vec<Creature>>,
+}
+
+pub enum Msg {
+    Add(Creature),
+    Remove(usize),
+}
+
+impl CreatureList {
+    fn view_creature(&self, idx: usize, creature: &Creature) -> Html {
+        let lang = self.props.language;
+        let (power, toughness) = creature.current();
+        html! {
+            <li>
+                <span class="creature-pt">{ format!("{}/{}", power, toughness) }</span>
+                { &creature.name }
+                { if creature.bonus != (0, 0) {
+                    html! {
+                        <span class="creature-pumps">
+                            { lang.fmt("creature_pumps", &[&lang.t(creature.trigger.as_ref()), &creature.pumps]) }
+                        </span>
+                    }
+                } else {
+                    html! {}
+                } }
+                { if creature.counters > 0 {
+                    html! {
+                        <span class="creature-counters">
+                            { lang.fmt("creature_counters", &[&creature.counters]) }
+                        </span>
+                    }
+                } else {
+                    html! {}
+                } }
+                <button class="destroy"
+                        aria-label=lang.t("remove")
+                        onclick=self.link.callback(move |_| Msg::Remove(idx))>{ "×" }</button>
+            </li>
+        }
+    }
+}
+
+impl Component for CreatureList {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        CreatureList {
+            on_add: link.callback(Msg::Add),
+            link,
+            props,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let mut creatures = self.props.creatures.clone();
+        match msg {
+            Msg::Add(creature) => creatures.push(creature),
+            Msg::Remove(idx) => {
+                creatures.remove(idx);
+            }
+        }
+        self.props.on_change.emit(creatures);
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div class="creatures">
+                <ul>
+                    { for self.props.creatures.iter().enumerate().map(|(idx, creature)| self.view_creature(idx, creature)) }
+                </ul>
+                <CreatureForm language=self.props.language on_add=self.on_add.clone() />
+            </div>
+        }
+    }
+}
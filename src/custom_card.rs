@@ -0,0 +1,277 @@
+//! Homebrew and newly printed flip cards, defined in JSON such as
+//!
+//! ```json
+//! { "name": "Fiery Gambit", "flips": null, "trigger": "flip_lost", "effect": "life -2, hand +1" }
+//! ```
+//!
+//! `flips` is the coins one activation flips, or `null` to flip until a
+//! flip is lost. An optional `win` of `you_call`, `heads` or
+//! `opponent_calls` says what wins a flip. `effect` lists changes to `life`, `hand`, `mana` or
+//! `tokens:<name>`, separated by commas.
+
+use crate::cast::{Board, CastOutcome};
+use crate::coin_flip::WinCondition;
+use crate::effect::{CardEffect, Note};
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::AsRefStr;
+
+/// When a custom card's effect happens. `as_ref` gives the trigger's text
+/// key.
+#[derive(AsRefStr, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    /// For each flip the card wins.
+    #[strum(serialize = "trigger_flip_won")]
+    FlipWon,
+    /// For each flip the card loses.
+    #[strum(serialize = "trigger_flip_lost")]
+    FlipLost,
+    /// For each spell cast.
+    #[strum(serialize = "trigger_cast")]
+    Cast,
+    #[strum(serialize = "trigger_turn_end")]
+    TurnEnd,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CardDefinition {
+    pub name: String,
+    #[serde(default = "one_flip")]
+    pub flips: Option<usize>,
+    #[serde(default)]
+    pub win: WinCondition,
+    pub trigger: Trigger,
+    pub effect: String,
+}
+
+fn one_flip() -> Option<usize> {
+    Some(1)
+}
+
+/// A pasted definition or list of them.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum Definitions {
+    One(CardDefinition),
+    Many(Vec<CardDefinition>),
+}
+
+impl Definitions {
+    pub fn into_vec(self) -> Vec<CardDefinition> {
+        match self {
+            Definitions::One(card) => vec![card],
+            Definitions::Many(cards) => cards,
+        }
+    }
+}
+
+/// Why a definition was rejected. `as_ref` gives the error's text key.
+#[derive(AsRefStr, Clone, Copy, Debug, PartialEq)]
+pub enum CardError {
+    #[strum(serialize = "card_json")]
+    Json,
+    #[strum(serialize = "card_no_name")]
+    NoName,
+    #[strum(serialize = "card_no_flips")]
+    NoFlips,
+    #[strum(serialize = "card_effect_syntax")]
+    EffectSyntax,
+    #[strum(serialize = "card_effect_target")]
+    EffectTarget,
+}
+
+/// What an effect changes.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Target {
+    Life,
+    Hand,
+    Mana,
+    Tokens(String),
+}
+
+/// One term of an effect: `target` goes up or down by `amount`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub target: Target,
+    pub amount: i64,
+}
+
+impl Change {
+    fn apply(&self, board: &mut Board) {
+        let step = |value: u32| (i64::from(value) + self.amount).max(0) as u32;
+        match &self.target {
+            Target::Life => board.life += self.amount as i32,
+            Target::Hand => board.hand = step(board.hand),
+            Target::Mana => board.mana = step(board.mana),
+            Target::Tokens(name) => match board.tokens.iter_mut().find(|t| &t.name == name) {
+                Some(tokens) => tokens.count = step(tokens.count),
+                None => board.create_tokens(name, step(0)),
+            },
+        }
+    }
+}
+
+/// Parses an effect such as `life -2, tokens:Goblin +1`.
+pub fn parse_effect(effect: &str) -> Result<Vec<Change>, CardError> {
+    effect
+        .split(',')
+        .map(|term| {
+            let (target, amount) = term
+                .trim()
+                .rsplit_once(' ')
+                .ok_or(CardError::EffectSyntax)?;
+            let amount = amount
+                .trim()
+                .parse::<i64>()
+                .map_err(|_| CardError::EffectSyntax)?;
+            let target = match target.trim() {
+                "life" => Target::Life,
+                "hand" => Target::Hand,
+                "mana" => Target::Mana,
+                target => match target.strip_prefix("tokens:") {
+                    Some(name) if !name.trim().is_empty() => Target::Tokens(name.trim().into()),
+                    _ => return Err(CardError::EffectTarget),
+                },
+            };
+            Ok(Change { target, amount })
+        })
+        .collect()
+}
+
+/// A definition in play, with its effect parsed once when it is loaded
+/// rather than every time it fires.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomCard {
+    pub definition: CardDefinition,
+    changes: Vec<Change>,
+}
+
+impl CustomCard {
+    /// Loads `definition`, or says why it can't be used.
+    pub fn new(definition: CardDefinition) -> Result<Self, CardError> {
+        if definition.name.trim().is_empty() {
+            return Err(CardError::NoName);
+        }
+        if definition.flips == Some(0) {
+            return Err(CardError::NoFlips);
+        }
+        let changes = parse_effect(&definition.effect)?;
+        Ok(CustomCard {
+            definition,
+            changes,
+        })
+    }
+
+    fn fire(&self, trigger: Trigger, board: &mut Board) -> Option<Note> {
+        if trigger != self.definition.trigger {
+            return None;
+        }
+        for change in &self.changes {
+            change.apply(board);
+        }
+        Some(Note {
+            key: "custom_card_effect",
+            args: vec![self.definition.name.clone(), self.definition.effect.clone()],
+        })
+    }
+}
+
+impl CardEffect for CustomCard {
+    fn on_cast(&self, board: &mut Board, _storm: u32, _outcome: &CastOutcome) -> Option<Note> {
+        self.fire(Trigger::Cast, board)
+    }
+
+    fn on_flip_won(&self, board: &mut Board) -> Option<Note> {
+        self.fire(Trigger::FlipWon, board)
+    }
+
+    fn on_flip_lost(&self, board: &mut Board) -> Option<Note> {
+        self.fire(Trigger::FlipLost, board)
+    }
+
+    fn on_turn_end(&self, board: &mut Board) -> Option<Note> {
+        self.fire(Trigger::TurnEnd, board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(trigger: Trigger, effect: &str) -> CardDefinition {
+        CardDefinition {
+            name: "Fiery Gambit".into(),
+            flips: None,
+            win: WinCondition::YouCall,
+            trigger,
+            effect: effect.into(),
+        }
+    }
+
+    #[test]
+    fn parses_each_term_of_an_effect() {
+        let changes = parse_effect("life -2, hand +1,mana 3 , tokens:Goblin Shaman +2").unwrap();
+        let targets: Vec<_> = changes
+            .iter()
+            .map(|c| (c.target.clone(), c.amount))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                (Target::Life, -2),
+                (Target::Hand, 1),
+                (Target::Mana, 3),
+                (Target::Tokens("Goblin Shaman".into()), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_effects_it_cannot_read() {
+        assert_eq!(parse_effect(""), Err(CardError::EffectSyntax));
+        assert_eq!(parse_effect("life"), Err(CardError::EffectSyntax));
+        assert_eq!(parse_effect("life two"), Err(CardError::EffectSyntax));
+        assert_eq!(parse_effect("life -2,"), Err(CardError::EffectSyntax));
+        assert_eq!(parse_effect("poison +1"), Err(CardError::EffectTarget));
+        assert_eq!(parse_effect("tokens: +1"), Err(CardError::EffectTarget));
+    }
+
+    #[test]
+    fn rejects_definitions_it_cannot_use() {
+        let unnamed = CardDefinition {
+            name: " ".into(),
+            ..definition(Trigger::FlipWon, "life +1")
+        };
+        assert_eq!(CustomCard::new(unnamed), Err(CardError::NoName));
+        let no_flips = CardDefinition {
+            flips: Some(0),
+            ..definition(Trigger::FlipWon, "life +1")
+        };
+        assert_eq!(CustomCard::new(no_flips), Err(CardError::NoFlips));
+        let bad_effect = definition(Trigger::FlipWon, "life");
+        assert_eq!(CustomCard::new(bad_effect), Err(CardError::EffectSyntax));
+    }
+
+    #[test]
+    fn fires_only_on_its_trigger() {
+        let card = CustomCard::new(definition(
+            Trigger::FlipLost,
+            "life -2, hand -9, tokens:Goblin +1",
+        ))
+        .unwrap();
+        let mut board = Board::default();
+        assert!(card.on_flip_won(&mut board).is_none());
+        assert_eq!(board, Board::default());
+
+        let note = card.on_flip_lost(&mut board).unwrap();
+        assert_eq!(
+            note.args,
+            vec!["Fiery Gambit", "life -2, hand -9, tokens:Goblin +1"]
+        );
+        assert_eq!(board.life, Board::default().life - 2);
+        // Counts stop at zero.
+        assert_eq!(board.hand, 0);
+        card.on_flip_lost(&mut board);
+        assert_eq!(board.tokens[0].count, 2);
+    }
+}
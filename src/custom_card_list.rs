@@ -0,0 +1,77 @@
+use crate::card_import::CardImport;
+use crate::custom_card::CustomCard;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// Loaded custom cards with a button to flip for each, and a box to
+/// paste more definitions into. Importing or removing cards hands back
+/// the whole new list.
+pub struct CustomCardList {
+    link: ComponentLink<Self>,
+    props: Props,
+    on_import: Callback<Vec<CustomCard>>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub cards: Vec<CustomCard>,
+    pub on_change: Callback<Vec<CustomCard>>,
+    pub on_flip: Callback<usize>,
+}
+
+pub enum Msg {
+    Import(Vec<CustomCard>),
+    Remove(usize),
+}
+
+impl Component for CustomCardList {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        CustomCardList {
+            on_import: link.callback(Msg::Import),
+            link,
+            props,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let mut cards = self.props.cards.clone();
+        match msg {
+            Msg::Import(imported) => cards.extend(imported),
+            Msg::Remove(idx) => {
+                cards.remove(idx);
+            }
+        }
+        self.props.on_change.emit(cards);
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <div class="custom-cards">
+                <ul>
+                    { for self.props.cards.iter().map(|card| &card.definition).enumerate().map(|(idx, card)| html! {
+                        <li>
+                            <span class="custom-card-name">{ &card.name }</span>
+                            <span class="custom-card-effect">{ format!("{} {}", card.effect, lang.t(card.trigger.as_ref())) }</span>
+                            <button onclick=self.props.on_flip.reform(move |_| idx)>{ lang.t("flip") }</button>
+                            <button class="destroy"
+                                    aria-label=lang.t("remove")
+                                    onclick=self.link.callback(move |_| Msg::Remove(idx))>{ "×" }</button>
+                        </li>
+                    }) }
+                </ul>
+                <CardImport language=lang on_import=self.on_import.clone() />
+            </div>
+        }
+    }
+}
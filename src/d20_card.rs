@@ -0,0 +1,89 @@
+use crate::dice::D20_BANDS;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// The name of a "roll a d20" card and the text of its modes.
+#[derive(Clone, Default)]
+pub struct D20Modes {
+    pub card: String,
+    /// Mode text for each of [`D20_BANDS`].
+    pub modes: [String; 3],
+}
+
+/// A "roll a d20" card: the text of each mode, with the mode rolled last
+/// highlighted. Rolling hands the card's text on to be rolled for.
+pub struct D20Card {
+    link: ComponentLink<Self>,
+    props: Props,
+    text: D20Modes,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    /// Band of the last roll.
+    pub rolled: Option<usize>,
+    pub on_roll: Callback<D20Modes>,
+}
+
+pub enum Msg {
+    UpdateCard(String),
+    UpdateMode(usize, String),
+    Roll,
+}
+
+impl Component for D20Card {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        D20Card {
+            link,
+            props,
+            text: D20Modes::default(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateCard(val) => self.text.card.neq_assign(val),
+            Msg::UpdateMode(band, val) => self.text.modes[band].neq_assign(val),
+            Msg::Roll => {
+                self.props.on_roll.emit(self.text.clone());
+                false
+            }
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <div class="d20-card">
+                <input placeholder=lang.t("d20_card")
+                       aria-label=lang.t("d20_card")
+                       value=&self.text.card
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateCard(e.value)) />
+                <ol>
+                    { for D20_BANDS.iter().enumerate().map(|(band, &(low, high))| {
+                        let range = if low == high { low.to_string() } else { format!("{}–{}", low, high) };
+                        html! {
+                            <li class=if self.props.rolled == Some(band) { "rolled" } else { "" }>
+                                <label>
+                                    { range }
+                                    <input value=&self.text.modes[band]
+                                           oninput=self.link.callback(move |e: InputData| Msg::UpdateMode(band, e.value)) />
+                                </label>
+                            </li>
+                        }
+                    }) }
+                </ol>
+                <button onclick=self.link.callback(|_| Msg::Roll)>{ lang.t("roll_d20") }</button>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! Day and night. Once a daybound card has made it day, each turn can
+//! change it depending on how many spells the previous turn's active
+//! player cast.
+
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::AsRefStr;
+
+/// Spells in one turn that make a night turn into day.
+pub const SPELLS_FOR_DAY: u32 = 2;
+
+/// `as_ref` gives the text key.
+#[derive(AsRefStr, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum DayNight {
+    Day,
+    Night,
+}
+
+impl DayNight {
+    /// What it is as the next turn begins, after the active player cast
+    /// `spells` spells.
+    pub fn next(self, spells: u32) -> Self {
+        match self {
+            DayNight::Day if spells == 0 => DayNight::Night,
+            DayNight::Night if spells >= SPELLS_FOR_DAY => DayNight::Day,
+            same => same,
+        }
+    }
+
+    /// The manual override's order: neither, day, night, then neither again.
+    pub fn cycle(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(DayNight::Day),
+            Some(DayNight::Day) => Some(DayNight::Night),
+            Some(DayNight::Night) => None,
+        }
+    }
+}
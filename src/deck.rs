@@ -0,0 +1,120 @@
+//! Decklists pasted in the usual text form, one card per line with its
+//! count:
+//!
+//! ```text
+//! 1 Krark, the Thumbless
+//! 4x Krark's Thumb
+//! Sol Ring
+//! ```
+//!
+//! A missing count means one copy. Set codes as Arena exports them, such
+//! as `(M21) 159`, are dropped, and everything from a `Sideboard` or
+//! `Maybeboard` heading on is left out.
+
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::AsRefStr;
+
+/// Headings that start the cards that are not in the deck.
+const OUTSIDE: &[&str] = &["sideboard", "maybeboard"];
+/// Headings that are skipped.
+const HEADINGS: &[&str] = &["deck", "commander", "companion", "mainboard"];
+
+/// Why a decklist was rejected. `as_ref` gives the error's text key.
+#[derive(AsRefStr, Clone, Copy, Debug, PartialEq)]
+pub enum DeckError {
+    #[strum(serialize = "deck_empty")]
+    Empty,
+    #[strum(serialize = "deck_bad_count")]
+    BadCount,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DeckEntry {
+    pub name: String,
+    pub count: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Decklist {
+    pub cards: Vec<DeckEntry>,
+}
+
+/// Splits `4x Krark's Thumb` into its count and name. Lines without a
+/// count are one copy.
+pub fn parse_line(line: &str) -> Result<(u32, &str), DeckError> {
+    let line = line.trim();
+    let (first, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let digits = first
+        .strip_suffix(|c| c == 'x' || c == 'X')
+        .unwrap_or(first);
+    if rest.is_empty() || !digits.starts_with(|c: char| c.is_ascii_digit()) {
+        return Ok((1, line));
+    }
+    match digits.parse() {
+        Ok(0) | Err(_) => Err(DeckError::BadCount),
+        Ok(count) => Ok((count, rest.trim())),
+    }
+}
+
+/// Drops an Arena set code and collector number from a card name.
+fn strip_set(name: &str) -> &str {
+    match name.find(" (") {
+        Some(idx) if name.ends_with(|c: char| c.is_ascii_alphanumeric()) => &name[..idx],
+        _ => name,
+    }
+}
+
+impl Decklist {
+    pub fn parse(text: &str) -> Result<Self, DeckError> {
+        let mut deck = Decklist::default();
+        for line in text.lines().map(str::trim) {
+            let heading = line.trim_end_matches(':').to_lowercase();
+            if OUTSIDE.contains(&heading.as_str()) {
+                break;
+            }
+            if line.is_empty()
+                || line.starts_with("//")
+                || line.starts_with('#')
+                || HEADINGS.contains(&heading.as_str())
+            {
+                continue;
+            }
+            let (count, name) = parse_line(line)?;
+            deck.add(strip_set(name), count);
+        }
+        if deck.cards.is_empty() {
+            return Err(DeckError::Empty);
+        }
+        Ok(deck)
+    }
+
+    fn add(&mut self, name: &str, count: u32) {
+        match self.find(name) {
+            Some(idx) => self.cards[idx].count += count,
+            None => self.cards.push(DeckEntry {
+                name: name.to_string(),
+                count,
+            }),
+        }
+    }
+
+    /// The entry for `name`, ignoring case.
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.cards
+            .iter()
+            .position(|entry| entry.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn size(&self) -> u32 {
+        self.cards.iter().map(|entry| entry.count).sum()
+    }
+
+    /// Every card in the deck as the index of its entry, one per copy.
+    pub fn library(&self) -> Vec<usize> {
+        self.cards
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, entry)| std::iter::repeat_n(idx, entry.count as usize))
+            .collect()
+    }
+}
@@ -0,0 +1,73 @@
+use crate::deck::{DeckError, Decklist};
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// A box to paste a decklist into, one card per line with its count.
+pub struct DeckImport {
+    link: ComponentLink<Self>,
+    props: Props,
+    text: String,
+    error: Option<DeckError>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub on_import: Callback<Decklist>,
+}
+
+pub enum Msg {
+    Update(String),
+    Import,
+}
+
+impl Component for DeckImport {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        DeckImport {
+            link,
+            props,
+            text: String::new(),
+            error: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Update(val) => return self.text.neq_assign(val),
+            Msg::Import => match Decklist::parse(&self.text) {
+                Ok(deck) => {
+                    self.props.on_import.emit(deck);
+                    self.text.clear();
+                    self.error = None;
+                }
+                Err(error) => self.error = Some(error),
+            },
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <>
+                <textarea aria-label=lang.t("decklist")
+                          placeholder="1 Krark, the Thumbless\n4 Krark's Thumb"
+                          value=&self.text
+                          oninput=self.link.callback(|e: InputData| Msg::Update(e.value)) />
+                <button onclick=self.link.callback(|_| Msg::Import)>{ lang.t("import_deck") }</button>
+                { match self.error {
+                    Some(error) => html! { <p class="error" role="alert">{ lang.t(error.as_ref()) }</p> },
+                    None => html! {},
+                } }
+            </>
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//! Dice expressions such as `2d6+3`, `4d20kh1` and `d100`.
+
+use rand::Rng;
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::AsRefStr;
+
+/// Most dice a single expression may roll.
+const MAX_DICE: u32 = 1000;
+
+/// Text keys of Celebr-8000's bonuses, one per face of a six-sided die.
+pub const CELEBR_8000_BONUSES: [&str; 6] = [
+    "bonus_pump",
+    "bonus_first_strike",
+    "bonus_vigilance",
+    "bonus_lifelink",
+    "bonus_indestructible",
+    "bonus_double_strike",
+];
+
+/// Faces whose Celebr-8000 bonus applies for two d6 results: each face
+/// rolled, or every face on doubles.
+pub fn celebr_8000(first: u32, second: u32) -> Vec<u32> {
+    if first == second {
+        (1..=6).collect()
+    } else {
+        let mut faces = vec![first.min(second), first.max(second)];
+        faces.retain(|face| (1..=6).contains(face));
+        faces
+    }
+}
+
+/// Result bands of a "roll a d20" card: 1–9, 10–19 and 20.
+pub const D20_BANDS: [(u32, u32); 3] = [(1, 9), (10, 19), (20, 20)];
+
+/// Index into [`D20_BANDS`] for a d20 result. Totals pushed past either end
+/// by modifiers count as the nearest band.
+pub fn d20_band(total: i64) -> usize {
+    D20_BANDS
+        .iter()
+        .position(|&(_, high)| total <= i64::from(high))
+        .unwrap_or(D20_BANDS.len() - 1)
+}
+
+/// Replacement effects that change how dice are rolled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RollRules {
+    /// Extra dice rolled for each group, with that many of the lowest
+    /// ignored before anything else is counted.
+    pub advantage: u32,
+}
+
+/// Why an expression could not be parsed. `as_ref` gives the error's text
+/// key.
+#[derive(AsRefStr, Clone, Copy, Debug, PartialEq)]
+pub enum DiceError {
+    #[strum(serialize = "dice_empty")]
+    Empty,
+    #[strum(serialize = "dice_syntax")]
+    Syntax,
+    #[strum(serialize = "dice_too_many")]
+    TooMany,
+}
+
+/// Which dice of a group count towards the total.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Keep {
+    All,
+    Highest(u32),
+    Lowest(u32),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+enum Term {
+    Dice { count: u32, sides: u32, keep: Keep },
+    Constant(i64),
+}
+
+/// A parsed expression: dice groups and constants added or subtracted.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DiceExpr {
+    terms: Vec<(i64, Term)>,
+}
+
+/// One die as rolled.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DieRoll {
+    pub sides: u32,
+    pub value: u32,
+    /// Whether the die counts towards the total; `kh`/`kl` and advantage
+    /// drop the rest.
+    pub kept: bool,
+    /// Whether the die's group is subtracted.
+    pub negative: bool,
+}
+
+/// The outcome of rolling an expression.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Roll {
+    pub expression: String,
+    pub dice: Vec<DieRoll>,
+    /// Sum of the constants in the expression.
+    pub modifier: i64,
+    pub total: i64,
+}
+
+impl DiceExpr {
+    /// A single die with `sides` sides.
+    pub fn die(sides: u32) -> Self {
+        DiceExpr {
+            terms: vec![(
+                1,
+                Term::Dice {
+                    count: 1,
+                    sides,
+                    keep: Keep::All,
+                },
+            )],
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, DiceError> {
+        let text = text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_lowercase();
+        if text.is_empty() {
+            return Err(DiceError::Empty);
+        }
+        let mut terms = Vec::new();
+        let mut rest = text.as_str();
+        let mut sign = 1;
+        loop {
+            let end = rest.find(['+', '-']).unwrap_or(rest.len());
+            terms.push((sign, parse_term(&rest[..end])?));
+            if end == rest.len() {
+                break;
+            }
+            sign = if rest[end..].starts_with('-') { -1 } else { 1 };
+            rest = &rest[end + 1..];
+        }
+        let dice: u32 = terms
+            .iter()
+            .map(|(_, term)| match term {
+                Term::Dice { count, .. } => *count,
+                Term::Constant(_) => 0,
+            })
+            .fold(0, u32::saturating_add);
+        if dice > MAX_DICE {
+            return Err(DiceError::TooMany);
+        }
+        Ok(DiceExpr { terms })
+    }
+
+    pub fn roll<R: Rng>(&self, rng: &mut R, rules: RollRules) -> Roll {
+        let mut dice = Vec::new();
+        let mut modifier = 0;
+        let mut total = 0;
+        for (sign, term) in &self.terms {
+            match *term {
+                Term::Constant(value) => {
+                    modifier += sign * value;
+                    total += sign * value;
+                }
+                Term::Dice { count, sides, keep } => {
+                    let values = (0..count + rules.advantage)
+                        .map(|_| rng.gen_range(1..=sides))
+                        .collect::<Vec<_>>();
+                    let ignored = kept_dice(&values, Keep::Lowest(rules.advantage));
+                    let counted = values
+                        .iter()
+                        .zip(&ignored)
+                        .filter(|(_, &ignored)| !ignored)
+                        .map(|(&value, _)| value)
+                        .collect::<Vec<_>>();
+                    let mut kept = kept_dice(&counted, keep).into_iter();
+                    for (value, ignored) in values.into_iter().zip(ignored) {
+                        let kept = !ignored && kept.next().unwrap_or(false);
+                        if kept {
+                            total += sign * i64::from(value);
+                        }
+                        dice.push(DieRoll {
+                            sides,
+                            value,
+                            kept,
+                            negative: *sign < 0,
+                        });
+                    }
+                }
+            }
+        }
+        Roll {
+            expression: self.to_string(),
+            dice,
+            modifier,
+            total,
+        }
+    }
+}
+
+impl std::fmt::Display for DiceExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (idx, (sign, term)) in self.terms.iter().enumerate() {
+            match (idx, sign) {
+                (0, 1) => {}
+                (_, 1) => write!(f, "+")?,
+                _ => write!(f, "-")?,
+            }
+            match term {
+                Term::Constant(value) => write!(f, "{}", value)?,
+                Term::Dice { count, sides, keep } => {
+                    write!(f, "{}d{}", count, sides)?;
+                    match keep {
+                        Keep::All => {}
+                        Keep::Highest(n) => write!(f, "kh{}", n)?,
+                        Keep::Lowest(n) => write!(f, "kl{}", n)?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_term(term: &str) -> Result<Term, DiceError> {
+    let number = |s: &str| s.parse::<u32>().map_err(|_| DiceError::Syntax);
+    let (count, rest) = match term.split_once('d') {
+        Some(split) => split,
+        None => return number(term).map(|value| Term::Constant(i64::from(value))),
+    };
+    let count = if count.is_empty() { 1 } else { number(count)? };
+    let (sides, keep) = match rest.find('k') {
+        Some(idx) => {
+            let (sides, keep) = rest.split_at(idx);
+            let keep = if let Some(n) = keep.strip_prefix("kh") {
+                Keep::Highest(number(n)?)
+            } else if let Some(n) = keep.strip_prefix("kl") {
+                Keep::Lowest(number(n)?)
+            } else {
+                return Err(DiceError::Syntax);
+            };
+            (sides, keep)
+        }
+        None => (rest, Keep::All),
+    };
+    let sides = number(sides)?;
+    if count == 0 || sides == 0 {
+        return Err(DiceError::Syntax);
+    }
+    Ok(Term::Dice { count, sides, keep })
+}
+
+/// Marks which of `values` count towards the total under `keep`.
+fn kept_dice(values: &[u32], keep: Keep) -> Vec<bool> {
+    let (n, highest) = match keep {
+        Keep::All => return vec![true; values.len()],
+        Keep::Highest(n) => (n as usize, true),
+        Keep::Lowest(n) => (n as usize, false),
+    };
+    let mut order = (0..values.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&idx| values[idx]);
+    if highest {
+        order.reverse();
+    }
+    let mut kept = vec![false; values.len()];
+    for idx in order.into_iter().take(n) {
+        kept[idx] = true;
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    fn roll(text: &str) -> Roll {
+        let mut rng = ChaCha12Rng::seed_from_u64(7);
+        DiceExpr::parse(text)
+            .unwrap()
+            .roll(&mut rng, RollRules::default())
+    }
+
+    #[test]
+    fn parses_and_prints_expressions() {
+        for text in ["2d6+3", "4d20kh1", "1d100", "3d8kl2-1d4-2"] {
+            assert_eq!(DiceExpr::parse(text).unwrap().to_string(), text);
+        }
+        assert_eq!(DiceExpr::parse(" D100 ").unwrap().to_string(), "1d100");
+    }
+
+    #[test]
+    fn rejects_empty_and_malformed_expressions() {
+        assert_eq!(DiceExpr::parse("  "), Err(DiceError::Empty));
+        for text in ["0d6", "2d0", "d", "2d6kx1", "2d6+", "two"] {
+            assert_eq!(DiceExpr::parse(text), Err(DiceError::Syntax), "{}", text);
+        }
+    }
+
+    #[test]
+    fn limits_dice_over_the_whole_expression() {
+        assert!(DiceExpr::parse("1000d6").is_ok());
+        assert_eq!(DiceExpr::parse("1001d6"), Err(DiceError::TooMany));
+        assert_eq!(DiceExpr::parse("500d6+501d6"), Err(DiceError::TooMany));
+        assert_eq!(DiceExpr::parse("4294967295d6+1d6"), Err(DiceError::TooMany));
+    }
+
+    #[test]
+    fn keeping_more_dice_than_rolled_keeps_them_all() {
+        let roll = roll("4d6kh5");
+        assert_eq!(roll.dice.len(), 4);
+        assert!(roll.dice.iter().all(|die| die.kept));
+        let sum = roll
+            .dice
+            .iter()
+            .map(|die| i64::from(die.value))
+            .sum::<i64>();
+        assert_eq!(roll.total, sum);
+    }
+
+    #[test]
+    fn keeps_the_highest_or_lowest() {
+        let highest = roll("4d20kh1");
+        let kept = highest
+            .dice
+            .iter()
+            .filter(|die| die.kept)
+            .collect::<Vec<_>>();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(
+            kept[0].value,
+            highest.dice.iter().map(|die| die.value).max().unwrap()
+        );
+        let lowest = roll("4d20kl1");
+        assert_eq!(
+            lowest.total,
+            i64::from(lowest.dice.iter().map(|die| die.value).min().unwrap())
+        );
+    }
+
+    #[test]
+    fn subtracts_negative_groups_and_constants() {
+        let roll = roll("1d1-1d1-3");
+        assert_eq!(roll.modifier, -3);
+        assert_eq!(roll.total, -3);
+        assert!(roll.dice[1].negative);
+    }
+
+    #[test]
+    fn advantage_ignores_the_lowest_extra_dice() {
+        let mut rng = ChaCha12Rng::seed_from_u64(7);
+        let rules = RollRules { advantage: 1 };
+        let roll = DiceExpr::parse("1d20").unwrap().roll(&mut rng, rules);
+        assert_eq!(roll.dice.len(), 2);
+        let max = roll.dice.iter().map(|die| die.value).max().unwrap();
+        assert_eq!(roll.total, i64::from(max));
+    }
+}
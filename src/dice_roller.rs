@@ -0,0 +1,111 @@
+use crate::dice::{DiceError, DiceExpr, Roll};
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// Takes typed dice expressions and shows each die of the latest roll.
+/// The expressions are rolled by the owner, with the game's random
+/// numbers, so they can be replayed from its seed.
+pub struct DiceRoller {
+    link: ComponentLink<Self>,
+    props: Props,
+    expression: String,
+    error: Option<DiceError>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    /// The latest roll from any dice action, shown below the input.
+    pub last_roll: Option<Roll>,
+    /// Asks for an expression to be rolled.
+    pub on_roll: Callback<DiceExpr>,
+}
+
+pub enum Msg {
+    UpdateExpression(String),
+    Roll,
+    Nope,
+}
+
+impl DiceRoller {
+    fn view_roll(&self, roll: &Roll) -> Html {
+        let lang = self.props.language;
+        html! {
+            <div class="dice-result" aria-live="polite">
+                <span class="dice-expression">{ &roll.expression }</span>
+                <ul class="dice-breakdown">
+                    { for roll.dice.iter().map(|die| {
+                        let class = if die.kept { "die" } else { "die dropped" };
+                        html! {
+                            <li class=class title=format!("d{}", die.sides)>
+                                { if die.negative { "−" } else { "" } }{ die.value }
+                            </li>
+                        }
+                    }) }
+                    { if roll.modifier != 0 {
+                        html! { <li class="modifier">{ format!("{:+}", roll.modifier) }</li> }
+                    } else {
+                        html! {}
+                    } }
+                </ul>
+                <span class="dice-total">{ lang.fmt("dice_total", &[&roll.total]) }</span>
+            </div>
+        }
+    }
+}
+
+impl Component for DiceRoller {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        DiceRoller {
+            link,
+            props,
+            expression: "1d20".into(),
+            error: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateExpression(val) => self.expression = val,
+            Msg::Roll => match DiceExpr::parse(&self.expression) {
+                Ok(expr) => {
+                    self.error = None;
+                    self.props.on_roll.emit(expr);
+                }
+                Err(error) => self.error = Some(error),
+            },
+            Msg::Nope => return false,
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <div class="dice">
+                <input class="dice-input"
+                       aria-label=lang.t("dice_expression")
+                       placeholder="2d6+3"
+                       value=&self.expression
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateExpression(e.value))
+                       onkeypress=self.link.callback(|e: KeyboardEvent| {
+                           if e.key() == "Enter" { Msg::Roll } else { Msg::Nope }
+                       }) />
+                <button onclick=self.link.callback(|_| Msg::Roll)>{ lang.t("roll") }</button>
+                { match self.error {
+                    Some(error) => html! { <p class="error" role="alert">{ lang.t(error.as_ref()) }</p> },
+                    None => html! {},
+                } }
+                { self.props.last_roll.as_ref().map(|roll| self.view_roll(roll)).unwrap_or_default() }
+            </div>
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::coin_flip::parse_count;
+use crate::deck::Decklist;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::probability::hit_at_least_one;
+use yew::prelude::*;
+
+/// Works out the chance of drawing at least one copy of a card, starting
+/// from the imported decklist's counts when there is one.
+pub struct DrawOdds {
+    link: ComponentLink<Self>,
+    props: Props,
+    deck_size: String,
+    copies: String,
+    draws: String,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub deck: Option<Decklist>,
+}
+
+pub enum Msg {
+    UpdateDeckSize(String),
+    UpdateCopies(String),
+    UpdateDraws(String),
+    /// Fills in the copies of the decklist entry at this index.
+    PickCard(usize),
+    Nope,
+}
+
+impl DrawOdds {
+    fn view_cards(&self) -> Html {
+        let lang = self.props.language;
+        let deck = match &self.props.deck {
+            Some(deck) => deck,
+            None => return html! {},
+        };
+        html! {
+            <label>
+                { lang.t("draw_odds_card") }
+                <select onchange=self.link.callback(|e: ChangeData| match e {
+                    ChangeData::Select(select) => select
+                        .value()
+                        .parse()
+                        .map(Msg::PickCard)
+                        .unwrap_or(Msg::Nope),
+                    _ => Msg::Nope,
+                })>
+                    <option value="" selected=true>{ "—" }</option>
+                    { for deck.cards.iter().enumerate().map(|(idx, entry)| html! {
+                        <option value=idx>{ format!("{} ×{}", entry.name, entry.count) }</option>
+                    }) }
+                </select>
+            </label>
+        }
+    }
+
+    fn view_odds(&self) -> Html {
+        let lang = self.props.language;
+        let counts = (
+            parse_count(&self.deck_size),
+            parse_count(&self.copies),
+            parse_count(&self.draws),
+        );
+        let odds = match counts {
+            (Ok(deck_size), Ok(copies), Ok(draws)) => hit_at_least_one(deck_size, copies, draws),
+            (Err(error), _, _) | (_, Err(error), _) | (_, _, Err(error)) => {
+                return html! { <p class="error" role="alert">{ lang.t(error.as_ref()) }</p> };
+            }
+        };
+        match odds {
+            Some(odds) => html! {
+                <p class="draw-odds-result" aria-live="polite">
+                    { lang.fmt("draw_odds_result", &[&format!("{:.1}%", odds * 100.0)]) }
+                </p>
+            },
+            None => html! { <p class="error" role="alert">{ lang.t("draw_odds_too_many") }</p> },
+        }
+    }
+}
+
+impl Component for DrawOdds {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let deck_size = props.deck.as_ref().map_or(99, Decklist::size);
+        DrawOdds {
+            link,
+            props,
+            deck_size: deck_size.to_string(),
+            copies: "1".into(),
+            draws: "7".into(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateDeckSize(val) => self.deck_size.neq_assign(val),
+            Msg::UpdateCopies(val) => self.copies.neq_assign(val),
+            Msg::UpdateDraws(val) => self.draws.neq_assign(val),
+            Msg::PickCard(idx) => match self.props.deck.as_ref().and_then(|d| d.cards.get(idx)) {
+                Some(entry) => self.copies.neq_assign(entry.count.to_string()),
+                None => false,
+            },
+            Msg::Nope => false,
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props.deck != props.deck {
+            if let Some(deck) = &props.deck {
+                self.deck_size = deck.size().to_string();
+            }
+        }
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <div class="draw-odds">
+                { self.view_cards() }
+                <label>
+                    { lang.t("deck_size_label") }
+                    <input type="number" min="1"
+                           value=&self.deck_size
+                           oninput=self.link.callback(|e: InputData| Msg::UpdateDeckSize(e.value)) />
+                </label>
+                <label>
+                    { lang.t("copies") }
+                    <input type="number" min="0"
+                           value=&self.copies
+                           oninput=self.link.callback(|e: InputData| Msg::UpdateCopies(e.value)) />
+                </label>
+                <label>
+                    { lang.t("draws") }
+                    <input type="number" min="0"
+                           value=&self.draws
+                           oninput=self.link.callback(|e: InputData| Msg::UpdateDraws(e.value)) />
+                </label>
+                { self.view_odds() }
+            </div>
+        }
+    }
+}
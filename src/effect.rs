@@ -0,0 +1,181 @@
+//! What cards do when spells are cast, coins are flipped and turns end.
+//! Each card is its own struct, so a new card only needs an impl here.
+
+use crate::cast::{Board, CastOutcome};
+
+/// Damage Mana Crypt deals for a lost flip.
+pub const MANA_CRYPT_DAMAGE: i32 = 3;
+
+/// Power and toughness of Okaun, Eye of Chaos before any doubling.
+pub const OKAUN_BASE: u64 = 3;
+
+/// Okaun's power after doubling `doublings` times, unless it no longer
+/// fits in a number.
+pub fn okaun_power(doublings: u32) -> Option<u64> {
+    if doublings >= u64::BITS {
+        return None;
+    }
+    OKAUN_BASE.checked_mul(1 << doublings)
+}
+
+/// Okaun's power and toughness, or how it got there once it no longer fits
+/// in a number.
+pub fn okaun_size(doublings: u32) -> String {
+    match okaun_power(doublings) {
+        Some(power) => format!("{}/{}", power, power),
+        None => format!("{} × 2^{}", OKAUN_BASE, doublings),
+    }
+}
+
+/// Something a card did that the table log should show, as a text key and
+/// the values for its placeholders.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Note {
+    pub key: &'static str,
+    pub args: Vec<String>,
+}
+
+impl Note {
+    pub fn new(key: &'static str) -> Self {
+        Note {
+            key,
+            args: Vec::new(),
+        }
+    }
+}
+
+/// Hooks a card can implement. Every hook does nothing by default.
+pub trait CardEffect {
+    /// The `storm`th spell of the turn was cast. Copies are not cast, so
+    /// they do not count.
+    fn on_cast(&self, _board: &mut Board, _storm: u32, _outcome: &CastOutcome) -> Option<Note> {
+        None
+    }
+
+    /// This card flipped a coin and won.
+    fn on_flip_won(&self, _board: &mut Board) -> Option<Note> {
+        None
+    }
+
+    /// This card flipped a coin and lost.
+    fn on_flip_lost(&self, _board: &mut Board) -> Option<Note> {
+        None
+    }
+
+    fn on_turn_end(&self, _board: &mut Board) -> Option<Note> {
+        None
+    }
+}
+
+/// Copies a spell for each won flip and returns it for each lost one,
+/// both of which [`CastOutcome`] already counts.
+pub struct Krark;
+
+impl CardEffect for Krark {}
+
+/// Stands in for custom cards in the history. Their definitions supply
+/// the effects.
+pub struct Homebrew;
+
+impl CardEffect for Homebrew {}
+
+/// Extra turns for won flips are read off the history.
+pub struct RalZarek;
+
+impl CardEffect for RalZarek {}
+
+pub struct Zndrsplt;
+
+impl CardEffect for Zndrsplt {
+    fn on_flip_won(&self, board: &mut Board) -> Option<Note> {
+        board.draw(1);
+        None
+    }
+}
+
+pub struct ManaCrypt;
+
+impl CardEffect for ManaCrypt {
+    fn on_flip_lost(&self, board: &mut Board) -> Option<Note> {
+        board.life -= MANA_CRYPT_DAMAGE;
+        Some(Note {
+            key: "mana_crypt_damage",
+            args: vec![MANA_CRYPT_DAMAGE.to_string()],
+        })
+    }
+}
+
+pub struct GoblinArchaeologist;
+
+impl CardEffect for GoblinArchaeologist {
+    fn on_flip_won(&self, board: &mut Board) -> Option<Note> {
+        Some(match board.archaeologist.resolve(true) {
+            Some(artifact) => Note {
+                key: "archaeologist_destroyed",
+                args: vec![artifact],
+            },
+            None => Note::new("archaeologist_won"),
+        })
+    }
+
+    fn on_flip_lost(&self, board: &mut Board) -> Option<Note> {
+        board.archaeologist.resolve(false);
+        Some(Note::new("archaeologist_sacrificed"))
+    }
+}
+
+/// Deals 1 damage to you for each of your coins that comes up tails. The
+/// opponent's coins are handled where their flips are recorded.
+pub struct ManaClash;
+
+impl CardEffect for ManaClash {
+    fn on_flip_lost(&self, board: &mut Board) -> Option<Note> {
+        board.life -= 1;
+        None
+    }
+}
+
+/// Damage goes to any target, so it is only noted.
+pub struct KarplusanMinotaur;
+
+impl CardEffect for KarplusanMinotaur {
+    fn on_flip_won(&self, _board: &mut Board) -> Option<Note> {
+        Some(Note::new("karplusan_won"))
+    }
+
+    fn on_flip_lost(&self, _board: &mut Board) -> Option<Note> {
+        Some(Note::new("karplusan_lost"))
+    }
+}
+
+/// Its flips land on creatures in combat, which are not tracked, so they
+/// are logged where the flips are recorded.
+pub struct TideOfWar;
+
+impl CardEffect for TideOfWar {}
+
+/// Changes who controls what rather than the board, which is tracked in
+/// `risky_move`.
+pub struct RiskyMove;
+
+impl CardEffect for RiskyMove {}
+
+/// Doubles for each flip you win, which is counted where flips are
+/// recorded, until the turn ends.
+pub struct Okaun;
+
+impl CardEffect for Okaun {
+    fn on_turn_end(&self, board: &mut Board) -> Option<Note> {
+        board.okaun = Some(0);
+        None
+    }
+}
+
+pub struct AetherfluxReservoir;
+
+impl CardEffect for AetherfluxReservoir {
+    fn on_cast(&self, board: &mut Board, storm: u32, _outcome: &CastOutcome) -> Option<Note> {
+        board.life += storm as i32;
+        None
+    }
+}
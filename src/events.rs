@@ -0,0 +1,64 @@
+//! Flip, roll and cast events, published to whichever modules subscribed
+//! so they don't have to be wired into the app's update loop one by one.
+
+use crate::cast::{CastOutcome, Spell};
+use crate::dice::Roll;
+use crate::history::FlipRecord;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Something the engine did.
+pub enum EngineEvent<'a> {
+    /// The flips of one activation, already in the history.
+    Flipped(&'a [FlipRecord]),
+    Rolled(&'a Roll),
+    /// A spell was cast through the cast workflow. Its Krark flips follow
+    /// as their own event.
+    Cast {
+        spell: &'a Spell,
+        outcome: &'a CastOutcome,
+    },
+}
+
+pub trait Subscriber {
+    fn notify(&mut self, event: &EngineEvent);
+}
+
+/// Lets the owner of a subscriber keep reading it after handing a clone to
+/// the bus.
+impl<T: Subscriber> Subscriber for Rc<RefCell<T>> {
+    fn notify(&mut self, event: &EngineEvent) {
+        self.borrow_mut().notify(event);
+    }
+}
+
+/// Handle for undoing a [`EventBus::subscribe`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SubscriptionId(usize);
+
+#[derive(Default)]
+pub struct EventBus {
+    /// Indexed by subscription id; unsubscribed slots are left empty so ids
+    /// stay valid.
+    subscribers: Vec<Option<Box<dyn Subscriber>>>,
+}
+
+impl EventBus {
+    pub fn subscribe(&mut self, subscriber: Box<dyn Subscriber>) -> SubscriptionId {
+        self.subscribers.push(Some(subscriber));
+        SubscriptionId(self.subscribers.len() - 1)
+    }
+
+    pub fn unsubscribe(&mut self, id: SubscriptionId) {
+        if let Some(slot) = self.subscribers.get_mut(id.0) {
+            *slot = None;
+        }
+    }
+
+    /// Notifies every subscriber, oldest first.
+    pub fn publish(&mut self, event: &EngineEvent) {
+        for subscriber in self.subscribers.iter_mut().flatten() {
+            subscriber.notify(event);
+        }
+    }
+}
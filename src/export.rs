@@ -0,0 +1,112 @@
+//! Browser downloads for exported data and charts.
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    Blob, BlobPropertyBag, CanvasRenderingContext2d, Element, HtmlAnchorElement, HtmlCanvasElement,
+    HtmlImageElement, Url, XmlSerializer,
+};
+
+fn document() -> web_sys::Document {
+    web_sys::window().unwrap().document().unwrap()
+}
+
+/// Saves `href` under `filename` by clicking a temporary anchor.
+fn download_href(href: &str, filename: &str) -> Result<(), JsValue> {
+    let anchor = document()
+        .create_element("a")?
+        .dyn_into::<HtmlAnchorElement>()?;
+    anchor.set_href(href);
+    anchor.set_download(filename);
+    anchor.click();
+    Ok(())
+}
+
+/// Offers `contents` to the user as a file download.
+pub fn download(filename: &str, mime: &str, contents: &str) -> Result<(), JsValue> {
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let options = BlobPropertyBag::new();
+    options.set_type(mime);
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+    download_href(&url, filename)?;
+    Url::revoke_object_url(&url)
+}
+
+/// Presentation properties copied inline so exports look like the page
+/// without the stylesheet.
+const INLINED_STYLES: &[&str] = &["fill", "stroke", "stroke-width", "font-size", "font-family"];
+
+fn inline_styles(source: &Element, target: &Element) -> Result<(), JsValue> {
+    let window = web_sys::window().unwrap();
+    if let Some(computed) = window.get_computed_style(source)? {
+        let style = INLINED_STYLES
+            .iter()
+            .filter_map(|name| {
+                let value = computed.get_property_value(name).ok()?;
+                Some(format!("{}:{};", name, value))
+            })
+            .collect::<String>();
+        target.set_attribute("style", &style)?;
+    }
+    Ok(())
+}
+
+fn serialize_svg(svg: &Element) -> Result<String, JsValue> {
+    let copy = svg.clone_node_with_deep(true)?.dyn_into::<Element>()?;
+    // Standalone SVG files need the namespace declared on the root.
+    copy.set_attribute("xmlns", "http://www.w3.org/2000/svg")?;
+    let sources = svg.query_selector_all("*")?;
+    let targets = copy.query_selector_all("*")?;
+    for i in 0..sources.length() {
+        if let (Some(source), Some(target)) = (sources.item(i), targets.item(i)) {
+            inline_styles(source.unchecked_ref(), target.unchecked_ref())?;
+        }
+    }
+    XmlSerializer::new()?.serialize_to_string(&copy)
+}
+
+/// Downloads a rendered `<svg>` element as an SVG file.
+pub fn download_svg(svg: &Element, name: &str) -> Result<(), JsValue> {
+    download(
+        &format!("{}.svg", name),
+        "image/svg+xml",
+        &serialize_svg(svg)?,
+    )
+}
+
+/// Rasterizes a rendered `<svg>` element through a canvas and downloads it
+/// as a PNG. The download starts once the browser has decoded the image.
+pub fn download_png(svg: &Element, name: &str) -> Result<(), JsValue> {
+    let width = svg.client_width().max(1) as u32;
+    let height = svg.client_height().max(1) as u32;
+    rasterize(&serialize_svg(svg)?, width, height, name)
+}
+
+/// Draws standalone SVG `source` onto a `width` by `height` canvas and
+/// downloads it as a PNG.
+pub fn rasterize(source: &str, width: u32, height: u32, name: &str) -> Result<(), JsValue> {
+    let image = HtmlImageElement::new()?;
+    let filename = format!("{}.png", name);
+    let loaded = image.clone();
+    let onload = Closure::once(move || -> Result<(), JsValue> {
+        let canvas = document()
+            .create_element("canvas")?
+            .dyn_into::<HtmlCanvasElement>()?;
+        canvas.set_width(width);
+        canvas.set_height(height);
+        let context = canvas
+            .get_context("2d")?
+            .unwrap()
+            .dyn_into::<CanvasRenderingContext2d>()?;
+        context.draw_image_with_html_image_element(&loaded, 0.0, 0.0)?;
+        download_href(&canvas.to_data_url()?, &filename)
+    });
+    image.set_onload(Some(onload.as_ref().unchecked_ref()));
+    onload.forget();
+    image.set_src(&format!(
+        "data:image/svg+xml;charset=utf-8,{}",
+        js_sys::encode_uri_component(source)
+    ));
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use crate::coin_flip::{Call, Toss};
+use crate::coin_skin::CoinFaces;
+use crate::history::FlipRecord;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// The flip history, newest activation marked, with the coins Krark's
+/// Thumb tossed for your latest flips above it.
+pub struct FlipLog {
+    props: Props,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub history: Vec<FlipRecord>,
+    /// Activations so far, the latest being one less.
+    pub activations: usize,
+    /// Activation whose chain is picked out.
+    pub selected: Option<usize>,
+    pub faces: CoinFaces,
+    pub tosses: Vec<Toss>,
+    /// The side the latest tosses were called for.
+    pub toss_call: Call,
+    /// Picks out the chain of the given activation.
+    pub on_select: Callback<usize>,
+}
+
+impl FlipLog {
+    /// Every coin behind your latest flips when Krark's Thumb tossed more
+    /// than one, marking the one kept.
+    fn view_tosses(&self) -> Html {
+        if self.props.tosses.iter().all(|toss| toss.coins.len() < 2) {
+            return html! {};
+        }
+        let lang = self.props.language;
+        html! {
+            <ol class="tosses" aria-label=lang.t("thumb_tosses")>
+                { for self.props.tosses.iter().map(|toss| {
+                    let kept = toss.kept(self.props.toss_call);
+                    html! {
+                        <li>
+                            { for toss.coins.iter().enumerate().map(|(idx, &coin)| {
+                                let (class, state) = if idx == kept {
+                                    ("kept", "toss_kept")
+                                } else {
+                                    ("discarded", "toss_discarded")
+                                };
+                                let side = if coin == Call::Heads { "heads" } else { "tails" };
+                                html! {
+                                    <span class=class title=lang.t(state)>{ lang.t(side) }</span>
+                                }
+                            }) }
+                        </li>
+                    }
+                }) }
+            </ol>
+        }
+    }
+
+    fn view_record(&self, (idx, record): (usize, &FlipRecord)) -> Html {
+        let lang = self.props.language;
+        let activation = record.activation;
+        let source = match &record.player {
+            Some(player) => format!("{} ({})", record.source, player),
+            None => record.source.to_string(),
+        };
+        let mut class = vec![];
+        if self.props.selected == Some(activation) {
+            class.push("selected");
+        }
+        if activation + 1 == self.props.activations {
+            class.push("latest");
+        }
+        html! {
+            <li id=format!("flip-{}", idx) class=class>
+                <button class="history-entry"
+                        onclick=self.props.on_select.reform(move |_| activation)>
+                    { self.props.faces.view(record.is_win()) }
+                    { format!("{}: {}", source, lang.flip(&record.result)) }
+                </button>
+            </li>
+        }
+    }
+}
+
+impl Component for FlipLog {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+        FlipLog { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <>
+                { self.view_tosses() }
+                <ol class="item-list">
+                    { for self.props.history.iter().enumerate().map(|entry| self.view_record(entry)) }
+                </ol>
+            </>
+        }
+    }
+}
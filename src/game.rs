@@ -0,0 +1,429 @@
+//! Everything that belongs to one game at the table, so several can be
+//! tracked side by side.
+
+use crate::card::FlipCard;
+use crate::cast::{Board, Spell, SpellKind};
+use crate::combat::{Combat, Role};
+use crate::day_night::DayNight;
+use crate::dice::Roll;
+use crate::history::FlipRecord;
+use crate::opponent::{Designation, Opponent, PlayerCounters};
+use crate::pace::Pace;
+use crate::packed::{self, Layout};
+use crate::risky_move::RiskyMove;
+use crate::trigger::{Trigger, TriggerRecord};
+use serde_derive::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::{AsRefStr, EnumIter};
+
+/// A card the player keeps an eye on, switched on while it is in play.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrackedCard {
+    pub name: String,
+    pub text: String,
+    pub active: bool,
+}
+
+/// Designations exactly one player holds at a time, taken from whoever had
+/// them. `as_ref` gives the designation's text key.
+#[derive(AsRefStr, Clone, Copy, Debug, EnumIter, PartialEq, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum TableDesignation {
+    Monarch,
+    Initiative,
+}
+
+/// A permanent of yours that must flip in each of your upkeeps, such as
+/// Mana Crypt.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UpkeepFlip {
+    pub card: FlipCard,
+    /// Whether it has flipped since the last turn ended.
+    pub flipped: bool,
+}
+
+impl UpkeepFlip {
+    pub fn new(card: FlipCard) -> Self {
+        UpkeepFlip {
+            card,
+            flipped: false,
+        }
+    }
+}
+
+/// How far the table log and activations had got at some point, so a
+/// session can be split into turns.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TurnMark {
+    /// Table log entries written by then.
+    pub log: usize,
+    /// Activations made by then.
+    pub activations: usize,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Game {
+    pub name: String,
+    pub board: Board,
+    pub history: Vec<FlipRecord>,
+    /// Activations so far, which numbers the next one.
+    pub activations: usize,
+    pub spells: Vec<Spell>,
+    pub storm: u32,
+    pub turn_storms: Vec<u32>,
+    /// Spells cast this turn with the change each made to the mana pool.
+    pub turn_casts: Vec<(String, i64)>,
+    pub opponents: Vec<Opponent>,
+    /// Opponent targeted by spells cast through the cast workflow.
+    pub target: Option<usize>,
+    /// Table events such as wheels, newest last.
+    pub table_log: Vec<String>,
+    pub last_roll: Option<Roll>,
+    /// Faces whose Celebr-8000 bonus applies this turn.
+    pub celebr_bonuses: Option<Vec<u32>>,
+    /// Who won the roll for first player. Players are numbered 0 for you,
+    /// then the opponents in seating order.
+    pub first_player: Option<usize>,
+    /// Players holding each table designation someone holds.
+    #[serde(default)]
+    pub held: Vec<(TableDesignation, usize)>,
+    /// Your poison, energy and experience. Opponents keep their own.
+    #[serde(default)]
+    pub counters: PlayerCounters,
+    /// Your designations, such as the city's blessing.
+    #[serde(default)]
+    pub designations: Vec<Designation>,
+    #[serde(default)]
+    pub tracked_cards: Vec<TrackedCard>,
+    /// Day or night, or neither before a daybound card has been seen.
+    #[serde(default)]
+    pub day_night: Option<DayNight>,
+    /// Where the current session began.
+    #[serde(default)]
+    pub session_start: TurnMark,
+    /// Where each turn of the current session ended.
+    #[serde(default)]
+    pub turn_marks: Vec<TurnMark>,
+    /// Who flips for combat cards like Tide of War and whose creatures
+    /// are in the fight.
+    #[serde(default)]
+    pub combat: Combat,
+    /// Risky Move, while it is on the battlefield.
+    #[serde(default)]
+    pub risky_move: Option<RiskyMove>,
+    /// Your permanents that must flip in your upkeep.
+    #[serde(default)]
+    pub upkeep_flips: Vec<UpkeepFlip>,
+    /// How long the current session and each of its turns have taken.
+    #[serde(default)]
+    pub pace: Pace,
+    /// Seed of the random numbers behind the current session's flips and
+    /// rolls, so they can be replayed.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// How far into the seed's random numbers the game has got, so they
+    /// carry on from there rather than repeat.
+    #[serde(default)]
+    pub word_pos: u128,
+    /// Every Krark trigger resolved this session, in order.
+    #[serde(default)]
+    pub triggers: Vec<TriggerRecord>,
+    /// Copies made this turn, by storm or Krark. They trigger magecraft
+    /// like the spells cast, but add nothing to storm.
+    #[serde(default)]
+    pub copies: u32,
+}
+
+/// Layout 1 of a spell, from before it said whether it has storm.
+#[derive(Deserialize, Serialize)]
+struct SpellV1 {
+    name: String,
+    mana_value: u32,
+    kind: SpellKind,
+    draws: u32,
+}
+
+impl From<SpellV1> for Spell {
+    fn from(old: SpellV1) -> Self {
+        Spell {
+            storm: Spell::has_storm_by_name(&old.name),
+            name: old.name,
+            mana_value: old.mana_value,
+            kind: old.kind,
+            draws: old.draws,
+        }
+    }
+}
+
+/// Layout 1 of a game, from before spells said whether they have storm.
+#[derive(Deserialize, Serialize)]
+struct GameV1 {
+    name: String,
+    board: Board,
+    history: Vec<FlipRecord>,
+    activations: usize,
+    spells: Vec<SpellV1>,
+    storm: u32,
+    turn_storms: Vec<u32>,
+    turn_casts: Vec<(String, i64)>,
+    opponents: Vec<Opponent>,
+    target: Option<usize>,
+    table_log: Vec<String>,
+    last_roll: Option<Roll>,
+    celebr_bonuses: Option<Vec<u32>>,
+    first_player: Option<usize>,
+    held: Vec<(TableDesignation, usize)>,
+    counters: PlayerCounters,
+    designations: Vec<Designation>,
+    tracked_cards: Vec<TrackedCard>,
+    day_night: Option<DayNight>,
+    session_start: TurnMark,
+    turn_marks: Vec<TurnMark>,
+    combat: Combat,
+    risky_move: Option<RiskyMove>,
+    upkeep_flips: Vec<UpkeepFlip>,
+    pace: Pace,
+    seed: Option<u64>,
+    word_pos: u128,
+    triggers: Vec<TriggerRecord>,
+    copies: u32,
+}
+
+impl From<GameV1> for Game {
+    fn from(old: GameV1) -> Self {
+        Game {
+            name: old.name,
+            board: old.board,
+            history: old.history,
+            activations: old.activations,
+            spells: old.spells.into_iter().map(Into::into).collect(),
+            storm: old.storm,
+            turn_storms: old.turn_storms,
+            turn_casts: old.turn_casts,
+            opponents: old.opponents,
+            target: old.target,
+            table_log: old.table_log,
+            last_roll: old.last_roll,
+            celebr_bonuses: old.celebr_bonuses,
+            first_player: old.first_player,
+            held: old.held,
+            counters: old.counters,
+            designations: old.designations,
+            tracked_cards: old.tracked_cards,
+            day_night: old.day_night,
+            session_start: old.session_start,
+            turn_marks: old.turn_marks,
+            combat: old.combat,
+            risky_move: old.risky_move,
+            upkeep_flips: old.upkeep_flips,
+            pace: old.pace,
+            seed: old.seed,
+            word_pos: old.word_pos,
+            triggers: old.triggers,
+            copies: old.copies,
+        }
+    }
+}
+
+/// Adding a field to a game, or to anything in it, needs a new layout.
+impl Layout for Vec<Game> {
+    const LAYOUT: u32 = 2;
+
+    fn upgrade(layout: u32, bytes: &[u8]) -> Option<Self> {
+        match layout {
+            1 => packed::exact::<Vec<GameV1>>(bytes)
+                .map(|old| old.into_iter().map(Into::into).collect()),
+            _ => None,
+        }
+    }
+}
+
+impl Game {
+    pub fn new(name: String) -> Self {
+        Game {
+            name,
+            ..Game::default()
+        }
+    }
+
+    /// Keeps a resolved trigger for exports. The first trigger of a cast
+    /// starts a new one.
+    pub fn log_trigger(&mut self, number: usize, activation: usize, trigger: Trigger) {
+        let last = self.triggers.last().map(|record| record.cast);
+        let cast = match last {
+            Some(cast) if number > 1 => cast,
+            Some(cast) => cast + 1,
+            None => 0,
+        };
+        self.triggers.push(TriggerRecord {
+            cast,
+            number,
+            activation,
+            trigger,
+        });
+    }
+
+    pub fn mark(&self) -> TurnMark {
+        TurnMark {
+            log: self.table_log.len(),
+            activations: self.activations,
+        }
+    }
+
+    /// Puts `lines` at the top of the table log, ahead of every turn. They
+    /// belong to the current session only if it began with the log.
+    pub fn prepend_log(&mut self, lines: Vec<String>) {
+        let shift = lines.len();
+        self.table_log.splice(0..0, lines);
+        if self.session_start.log > 0 {
+            self.session_start.log += shift;
+        }
+        for mark in &mut self.turn_marks {
+            mark.log += shift;
+        }
+    }
+
+    /// Counts a flip you made for `card` towards its upkeep flip, if one
+    /// is still due.
+    pub fn flipped_in_upkeep(&mut self, card: FlipCard) {
+        let due = self
+            .upkeep_flips
+            .iter_mut()
+            .find(|upkeep| upkeep.card == card && !upkeep.flipped);
+        if let Some(upkeep) = due {
+            upkeep.flipped = true;
+        }
+    }
+
+    /// Names of the mandatory flips not made this turn, including a Risky
+    /// Move flip that is due.
+    pub fn unresolved_upkeep_flips(&self) -> Vec<String> {
+        let mut unresolved = self
+            .upkeep_flips
+            .iter()
+            .filter(|upkeep| !upkeep.flipped)
+            .map(|upkeep| upkeep.card.to_string())
+            .collect::<Vec<_>>();
+        if self
+            .risky_move
+            .as_ref()
+            .is_some_and(|r| r.taken_from.is_some())
+        {
+            unresolved.push(FlipCard::RiskyMove.to_string());
+        }
+        unresolved
+    }
+
+    /// Makes every upkeep flip due again for the next turn.
+    pub fn new_upkeep(&mut self) {
+        for upkeep in &mut self.upkeep_flips {
+            upkeep.flipped = false;
+        }
+    }
+
+    pub fn holder(&self, designation: TableDesignation) -> Option<usize> {
+        self.held
+            .iter()
+            .find(|&&(d, _)| d == designation)
+            .map(|&(_, player)| player)
+    }
+
+    /// Gives `designation` to `player`, or to nobody.
+    pub fn set_holder(&mut self, designation: TableDesignation, player: Option<usize>) {
+        self.held.retain(|&(d, _)| d != designation);
+        if let Some(player) = player {
+            self.held.push((designation, player));
+        }
+    }
+
+    /// Removes an opponent, keeping everything that refers to players by
+    /// number pointing at the same players.
+    pub fn remove_opponent(&mut self, idx: usize) {
+        self.opponents.remove(idx);
+        self.target = match self.target {
+            Some(target) if target == idx => None,
+            Some(target) if target > idx => Some(target - 1),
+            target => target,
+        };
+        let player = idx + 1;
+        let renumber = |p: usize| match p {
+            p if p == player => None,
+            p if p > player => Some(p - 1),
+            p => Some(p),
+        };
+        self.first_player = self.first_player.and_then(renumber);
+        self.held = self
+            .held
+            .iter()
+            .filter_map(|&(d, p)| renumber(p).map(|p| (d, p)))
+            .collect();
+        for role in Role::iter() {
+            let player = renumber(self.combat.player(role)).unwrap_or(0);
+            self.combat.set_player(role, player);
+        }
+        if let Some(risky_move) = &mut self.risky_move {
+            risky_move.renumber(renumber);
+        }
+        self.pace.renumber(renumber);
+    }
+
+    /// Moves the opponent at `seats[i]` to seat `i`.
+    pub fn reseat(&mut self, seats: &[usize]) {
+        self.opponents = seats.iter().map(|&i| self.opponents[i].clone()).collect();
+        let renumber = |p: usize| match p {
+            0 => 0,
+            p => seats
+                .iter()
+                .position(|&i| i + 1 == p)
+                .map_or(0, |seat| seat + 1),
+        };
+        self.target = self.target.and_then(|t| seats.iter().position(|&i| i == t));
+        self.first_player = self.first_player.map(renumber);
+        for (_, player) in &mut self.held {
+            *player = renumber(*player);
+        }
+        for role in Role::iter() {
+            let player = renumber(self.combat.player(role));
+            self.combat.set_player(role, player);
+        }
+        if let Some(risky_move) = &mut self.risky_move {
+            risky_move.renumber(|p| Some(renumber(p)));
+        }
+        self.pace.renumber(|p| Some(renumber(p)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packed::{pack, unpack};
+
+    impl Layout for Vec<GameV1> {
+        const LAYOUT: u32 = 1;
+    }
+
+    fn spell_v1(name: &str) -> SpellV1 {
+        SpellV1 {
+            name: name.into(),
+            mana_value: 2,
+            kind: SpellKind::Instant,
+            draws: 1,
+        }
+    }
+
+    #[test]
+    fn reads_games_from_before_spells_had_storm() {
+        let mut game = Game::new("Old".into());
+        game.board.krarks = 2;
+        game.word_pos = 7;
+        let mut old: GameV1 = packed::exact(&bincode::serialize(&game).unwrap()).unwrap();
+        old.spells = vec![spell_v1("Brain Freeze"), spell_v1("Opt")];
+
+        let games = unpack::<Vec<Game>>(&pack(&vec![old]).unwrap()).unwrap();
+        assert_eq!(games[0].name, "Old");
+        assert_eq!(games[0].board.krarks, 2);
+        assert_eq!(games[0].word_pos, 7);
+        let storm: Vec<_> = games[0].spells.iter().map(|s| (s.draws, s.storm)).collect();
+        assert_eq!(storm, vec![(1, true), (1, false)]);
+    }
+}
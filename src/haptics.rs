@@ -0,0 +1,45 @@
+//! Vibration for flip results, so they can be felt without looking at the
+//! phone. Browsers without the Vibration API ignore it.
+
+use wasm_bindgen::JsValue;
+
+/// Vibration and pause lengths in milliseconds, alternating.
+const WON: &[u32] = &[60];
+const LOST: &[u32] = &[20, 60, 20];
+const STREAK: &[u32] = &[80, 50, 80, 50, 250];
+
+/// Pause between the buzzes of one activation's flips.
+const GAP: u32 = 150;
+
+/// Flips buzzed out one by one; longer activations are summed up by
+/// their last flips.
+const MAX_FLIPS: usize = 8;
+
+fn vibrate(pattern: &[u32]) {
+    if let Some(window) = web_sys::window() {
+        let pattern = pattern
+            .iter()
+            .map(|&ms| JsValue::from(ms))
+            .collect::<js_sys::Array>();
+        window.navigator().vibrate_with_pattern(&pattern);
+    }
+}
+
+/// One buzz per flip, a long one for each win and a double tap for each
+/// loss.
+pub fn flips(outcomes: &[bool]) {
+    let skip = outcomes.len().saturating_sub(MAX_FLIPS);
+    let mut pattern = Vec::new();
+    for &won in &outcomes[skip..] {
+        if !pattern.is_empty() {
+            pattern.push(GAP);
+        }
+        pattern.extend_from_slice(if won { WON } else { LOST });
+    }
+    vibrate(&pattern);
+}
+
+/// A streak milestone.
+pub fn streak() {
+    vibrate(STREAK);
+}
@@ -0,0 +1,115 @@
+use crate::chart::Chart;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// Width of one bin's bar, in SVG user units.
+const BAR_WIDTH: usize = 12;
+/// Height of the tallest bar, in SVG user units.
+const HEIGHT: usize = 80;
+
+/// Counts per bin drawn as SVG bars, with the exact count of the bin under
+/// the pointer spelled out below.
+pub struct Histogram {
+    link: ComponentLink<Self>,
+    props: Props,
+    hovered: Option<usize>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    /// File name for downloads, without an extension.
+    pub name: String,
+    pub label: String,
+    pub bins: Vec<usize>,
+    /// What each bin stands for, in the same order.
+    pub bin_labels: Vec<String>,
+}
+
+pub enum Msg {
+    Hover(Option<usize>),
+}
+
+impl Histogram {
+    /// A bin's label with how many trials fell in it.
+    fn describe(&self, bin: usize) -> String {
+        let count = self.props.bins.get(bin).cloned().unwrap_or(0);
+        let total = self.props.bins.iter().sum::<usize>().max(1);
+        let label = self.props.bin_labels.get(bin).cloned().unwrap_or_default();
+        self.props.language.fmt(
+            "histogram_bin",
+            &[
+                &label,
+                &count,
+                &format!("{:.2}%", count as f64 / total as f64 * 100.0),
+            ],
+        )
+    }
+}
+
+impl Component for Histogram {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Histogram {
+            link,
+            props,
+            hovered: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Hover(bin) => self.hovered.neq_assign(bin),
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let bins = &self.props.bins;
+        if bins.is_empty() {
+            return html! {};
+        }
+        let width = bins.len() * BAR_WIDTH;
+        let max = bins.iter().cloned().max().unwrap_or(0).max(1) as f64;
+        html! {
+            <figure class="histogram">
+                <figcaption>{ &self.props.label }</figcaption>
+                <Chart name=self.props.name.clone()>
+                    <svg role="img"
+                         aria-label=&self.props.label
+                         width=width
+                         height=HEIGHT
+                         viewBox=format!("0 0 {} {}", width, HEIGHT)
+                         onmouseleave=self.link.callback(|_| Msg::Hover(None))>
+                        { for bins.iter().enumerate().map(|(bin, &count)| {
+                            let height = count as f64 / max * HEIGHT as f64;
+                            // The whole column takes the pointer, so empty
+                            // bins can be hovered too.
+                            html! {
+                                <g class=if self.hovered == Some(bin) { "hovered" } else { "" }
+                                   onmouseenter=self.link.callback(move |_| Msg::Hover(Some(bin)))>
+                                    <title>{ self.describe(bin) }</title>
+                                    <rect class="bin" x=bin * BAR_WIDTH y=0 width=BAR_WIDTH height=HEIGHT />
+                                    <rect class="bar"
+                                          x=bin * BAR_WIDTH
+                                          y=format!("{:.1}", HEIGHT as f64 - height)
+                                          width=BAR_WIDTH - 1
+                                          height=format!("{:.1}", height) />
+                                </g>
+                            }
+                        }) }
+                    </svg>
+                </Chart>
+                <p class="histogram-readout" aria-live="polite">
+                    { for self.hovered.map(|bin| self.describe(bin)) }
+                </p>
+            </figure>
+        }
+    }
+}
@@ -0,0 +1,324 @@
+use crate::card::FlipCard;
+use crate::coin_flip::{FlipResult, WinCondition};
+use crate::events::{EngineEvent, Subscriber};
+use crate::packed::Layout;
+use serde_derive::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use strum_macros::{AsRefStr, EnumIter};
+
+/// A single flip together with the card that caused it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FlipRecord {
+    pub source: FlipCard,
+    /// Flips sharing an activation were produced by the same trigger,
+    /// ability or cast.
+    pub activation: usize,
+    /// Krark's Thumbs in play when the flip was made.
+    pub thumbs: usize,
+    /// Opponent who flipped, for cards such as Mana Clash where several
+    /// players flip, or `None` for you.
+    #[serde(default)]
+    pub player: Option<String>,
+    /// What counted as a win for the card.
+    #[serde(default)]
+    pub win: WinCondition,
+    pub result: FlipResult,
+}
+
+impl Layout for Vec<FlipRecord> {
+    const LAYOUT: u32 = 1;
+}
+
+impl FlipRecord {
+    pub fn is_win(&self) -> bool {
+        self.win.wins(&self.result)
+    }
+}
+
+/// Combinators for runs of flip records.
+pub trait RecordStream<'a>: Iterator<Item = &'a FlipRecord> + Sized {
+    /// Collects consecutive records of the same activation into one group
+    /// per trigger, ability or cast.
+    fn group_by_trigger(self) -> GroupByTrigger<'a, Self> {
+        GroupByTrigger {
+            records: self.peekable(),
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a FlipRecord>> RecordStream<'a> for I {}
+
+/// See [`RecordStream::group_by_trigger`].
+pub struct GroupByTrigger<'a, I: Iterator<Item = &'a FlipRecord>> {
+    records: Peekable<I>,
+}
+
+impl<'a, I: Iterator<Item = &'a FlipRecord>> Iterator for GroupByTrigger<'a, I> {
+    type Item = Vec<&'a FlipRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.records.next()?;
+        let mut group = vec![first];
+        while let Some(record) = self.records.next_if(|r| r.activation == first.activation) {
+            group.push(record);
+        }
+        Some(group)
+    }
+}
+
+/// Removes all but the last `keep` items, returning the removed ones
+/// oldest first.
+pub fn prune<T>(items: &mut Vec<T>, keep: usize) -> Vec<T> {
+    let excess = items.len().saturating_sub(keep);
+    items.drain(..excess).collect()
+}
+
+/// `text` as a quoted CSV field, with quotes inside doubled as RFC 4180
+/// asks.
+pub fn csv_field(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+/// One line per flip, with a header row.
+pub fn to_csv(history: &[FlipRecord]) -> String {
+    let mut csv = String::from("source,activation,thumbs,player,result\n");
+    for record in history {
+        csv += &format!(
+            "{},{},{},{},{}\n",
+            csv_field(&record.source.to_string()),
+            record.activation,
+            record.thumbs,
+            csv_field(record.player.as_deref().unwrap_or_default()),
+            record.result
+        );
+    }
+    csv
+}
+
+/// Flip totals for one source card.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardStats {
+    pub source: FlipCard,
+    pub activations: usize,
+    pub flips: usize,
+    pub wins: usize,
+}
+
+impl CardStats {
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.flips as f64
+    }
+
+    pub fn wins_per_activation(&self) -> f64 {
+        self.wins as f64 / self.activations as f64
+    }
+}
+
+/// Flip totals for one player, `None` being you.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub player: Option<String>,
+    pub flips: usize,
+    pub wins: usize,
+}
+
+impl PlayerStats {
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.flips as f64
+    }
+}
+
+/// Columns of the per-card table. `as_ref` gives the column's text key.
+#[derive(AsRefStr, Clone, Copy, EnumIter, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+pub enum StatsColumn {
+    Card,
+    Activations,
+    Flips,
+    WinRate,
+    WinsPerActivation,
+}
+
+impl StatsColumn {
+    fn compare(self, a: &CardStats, b: &CardStats) -> Ordering {
+        match self {
+            StatsColumn::Card => a.source.to_string().cmp(&b.source.to_string()),
+            StatsColumn::Activations => a.activations.cmp(&b.activations),
+            StatsColumn::Flips => a.flips.cmp(&b.flips),
+            StatsColumn::WinRate => a.win_rate().total_cmp(&b.win_rate()),
+            StatsColumn::WinsPerActivation => {
+                a.wins_per_activation().total_cmp(&b.wins_per_activation())
+            }
+        }
+    }
+
+    /// Sorts `stats` by this column.
+    pub fn sort(self, stats: &mut [CardStats], descending: bool) {
+        stats.sort_by(|a, b| {
+            let ordering = self.compare(a, b);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+}
+
+/// Running totals over a history, updated one flip at a time so views
+/// never have to walk the whole history. Everything but the per-player
+/// totals counts only your own flips.
+#[derive(Default)]
+pub struct StatsAccumulator {
+    pub flips: usize,
+    pub wins: usize,
+    /// Wins since the last lost flip.
+    pub current_streak: usize,
+    pub longest_streak: usize,
+    /// `streak_counts[k]` is the number of losses that ended a streak of
+    /// exactly `k` wins.
+    pub streak_counts: Vec<usize>,
+    /// Per-card totals in the order the cards first flipped.
+    cards: Vec<CardStats>,
+    /// Per-player totals in the order the players first flipped.
+    players: Vec<PlayerStats>,
+    last_activation: Option<usize>,
+}
+
+impl StatsAccumulator {
+    pub fn new(history: &[FlipRecord]) -> Self {
+        let mut stats = StatsAccumulator::default();
+        for record in history {
+            stats.push(record);
+        }
+        stats
+    }
+
+    /// Counts one more flip.
+    pub fn push(&mut self, record: &FlipRecord) {
+        let won = record.is_win();
+        let idx = match self.players.iter().position(|s| s.player == record.player) {
+            Some(idx) => idx,
+            None => {
+                self.players.push(PlayerStats {
+                    player: record.player.clone(),
+                    flips: 0,
+                    wins: 0,
+                });
+                self.players.len() - 1
+            }
+        };
+        self.players[idx].flips += 1;
+        if won {
+            self.players[idx].wins += 1;
+        }
+        if record.player.is_some() {
+            return;
+        }
+
+        self.flips += 1;
+        if won {
+            self.wins += 1;
+            self.current_streak += 1;
+            self.longest_streak = self.longest_streak.max(self.current_streak);
+        } else {
+            if self.streak_counts.len() <= self.current_streak {
+                self.streak_counts.resize(self.current_streak + 1, 0);
+            }
+            self.streak_counts[self.current_streak] += 1;
+            self.current_streak = 0;
+        }
+
+        let idx = match self.cards.iter().position(|s| s.source == record.source) {
+            Some(idx) => idx,
+            None => {
+                self.cards.push(CardStats {
+                    source: record.source,
+                    activations: 0,
+                    flips: 0,
+                    wins: 0,
+                });
+                self.cards.len() - 1
+            }
+        };
+        let entry = &mut self.cards[idx];
+        if self.last_activation != Some(record.activation) {
+            entry.activations += 1;
+            self.last_activation = Some(record.activation);
+        }
+        entry.flips += 1;
+        if won {
+            entry.wins += 1;
+        }
+    }
+
+    /// Per-player totals in the order the players first flipped.
+    pub fn player_stats(&self) -> &[PlayerStats] {
+        &self.players
+    }
+
+    /// Per-card totals in the order the cards first flipped.
+    pub fn card_stats(&self) -> &[CardStats] {
+        &self.cards
+    }
+}
+
+impl Subscriber for StatsAccumulator {
+    fn notify(&mut self, event: &EngineEvent) {
+        if let EngineEvent::Flipped(records) = event {
+            for record in records.iter() {
+                self.push(record);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_csv_fields_with_commas_quotes_and_newlines() {
+        assert_eq!(csv_field("plain"), "\"plain\"");
+        assert_eq!(
+            csv_field("Rowan, \"the\nBold\""),
+            "\"Rowan, \"\"the\nBold\"\"\""
+        );
+
+        let record = FlipRecord {
+            source: FlipCard::ManaClash,
+            activation: 3,
+            thumbs: 0,
+            player: Some("Rowan, \"the\nBold\"".into()),
+            win: WinCondition::Heads,
+            result: FlipResult::Tails,
+        };
+        let csv = to_csv(&[record]);
+        assert_eq!(
+            csv,
+            "source,activation,thumbs,player,result\n\
+             \"Mana Clash\",3,0,\"Rowan, \"\"the\nBold\"\"\",Tails\n"
+        );
+    }
+
+    #[test]
+    fn sorts_card_stats_by_column() {
+        let stats = |source, activations, flips, wins| CardStats {
+            source,
+            activations,
+            flips,
+            wins,
+        };
+        let mut cards = vec![
+            stats(FlipCard::Krark, 4, 8, 2),
+            stats(FlipCard::ManaClash, 1, 6, 3),
+        ];
+        StatsColumn::Flips.sort(&mut cards, true);
+        assert_eq!(cards[0].source, FlipCard::Krark);
+        StatsColumn::WinRate.sort(&mut cards, true);
+        assert_eq!(cards[0].source, FlipCard::ManaClash);
+        StatsColumn::Activations.sort(&mut cards, false);
+        assert_eq!(cards[0].source, FlipCard::ManaClash);
+    }
+}
@@ -0,0 +1,1169 @@
+//! Translated UI text. Components look strings up by key in the catalog of
+//! the selected language, falling back to English.
+
+use crate::coin_flip::FlipResult;
+use serde_derive::{Deserialize, Serialize};
+use std::fmt::Display;
+use strum_macros::{Display, EnumIter, EnumString};
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumIter,
+    EnumString,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub enum Language {
+    #[default]
+    English,
+    Deutsch,
+}
+
+impl Language {
+    /// BCP 47 tag, for the browser APIs that take one.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Deutsch => "de",
+        }
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::English => ENGLISH,
+            Language::Deutsch => DEUTSCH,
+        }
+    }
+
+    /// Looks up `key`, falling back to English and then to the key itself.
+    pub fn t(self, key: &str) -> &str {
+        let lookup = |catalog: &'static [(&'static str, &'static str)]| {
+            catalog.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+        };
+        lookup(self.catalog())
+            .or_else(|| lookup(ENGLISH))
+            .unwrap_or(key)
+    }
+
+    /// Looks up `key` and fills its `{}` placeholders with `args` in order.
+    pub fn fmt(self, key: &str, args: &[&dyn Display]) -> String {
+        let mut args = args.iter();
+        let mut parts = self.t(key).split("{}");
+        let mut text = parts.next().unwrap_or_default().to_string();
+        for part in parts {
+            if let Some(arg) = args.next() {
+                text.push_str(&arg.to_string());
+            }
+            text.push_str(part);
+        }
+        text
+    }
+
+    pub fn flip(self, result: &FlipResult) -> &'static str {
+        self.t(match result {
+            FlipResult::Heads => "heads",
+            FlipResult::Tails => "tails",
+            FlipResult::Both => "both",
+        })
+    }
+}
+
+const ENGLISH: &[(&str, &str)] = &[
+    ("heads", "Heads"),
+    ("tails", "Tails"),
+    ("both", "Both"),
+    ("flip", "Flip"),
+    ("end_turn", "End turn"),
+    ("fast_forward", "Activations to fast-forward"),
+    ("flip_times", "Flip ×{}"),
+    ("save_session", "Save session"),
+    ("export_report", "Export battle report"),
+    ("export_csv", "Export session as CSV"),
+    ("export_json", "Export session as JSON"),
+    ("import_session", "Import session or setup"),
+    ("import_too_new", "That file comes from a newer version of Krarkinator."),
+    ("import_unreadable", "That file is not a session or setup."),
+    ("import_inconsistent", "That session's flips, triggers and summary don't agree."),
+    ("announce_imported", "Imported."),
+    ("take_snapshot", "Save board as image"),
+    ("haptics", "Vibrate on flips"),
+    ("speak_results", "Read results aloud"),
+    ("undo", "Undo"),
+    ("celebration_streak", "Celebrate streaks of"),
+    ("coin_skin", "Coin faces"),
+    ("win_conditions", "What wins a flip"),
+    ("combat_flipper", "Flipped by"),
+    ("combat_attacker", "Attacking"),
+    ("combat_blocker", "Blocking"),
+    ("combat_no_player", "Pick who flips, attacks and blocks first."),
+    ("combat_won", "{} ({}) won the flip."),
+    ("combat_lost", "{} ({}) lost the flip."),
+    ("combat_blocking", "{}'s blocking creatures are {}."),
+    ("combat_blocked", "{}'s blocked creatures are {}."),
+    ("fate_sacrificed", "sacrificed"),
+    ("fate_destroyed", "destroyed"),
+    ("fate_returned", "returned to their owners' hands"),
+    ("risky_move", "Risky Move"),
+    ("risky_move_controller", "Controlled by"),
+    ("risky_move_next_upkeep", "Next upkeep"),
+    ("risky_move_taken", "{} takes Risky Move and must flip for it."),
+    ("risky_move_due", "{} took Risky Move: pick a creature and an opponent, then flip."),
+    ("risky_move_creature", "Creature at risk"),
+    ("risky_move_recipient", "Opponent who gets it"),
+    ("risky_move_choose", "Pick a creature and an opponent before flipping for Risky Move."),
+    ("risky_move_won", "{} won the Risky Move flip and keeps their creature."),
+    ("risky_move_lost", "{} lost the Risky Move flip: {} gains control of {}."),
+    ("risky_move_handover", "{}: {} → {}"),
+    ("upkeep_due", "must still flip"),
+    ("upkeep_flipped", "flipped"),
+    ("upkeep_add", "Add a permanent…"),
+    ("upkeep_unresolved", "Upkeep flips not made"),
+    ("upkeep_unresolved_cards", "These have not flipped this turn: {}."),
+    ("upkeep_go_back", "Go back"),
+    ("upkeep_end_anyway", "End turn anyway"),
+    ("end_of_turn_resets", "Reset when the turn ends"),
+    ("reset_storm", "Storm count and copies"),
+    ("reset_turn_casts", "Spells cast this turn"),
+    ("reset_floating_mana", "Floating mana"),
+    ("reset_infinite_mana", "Infinite mana combo"),
+    ("reset_pumps", "Prowess and magecraft pumps"),
+    ("reset_temporary_tokens", "Tokens until end of turn"),
+    ("reset_celebr_bonuses", "Celebr-8000 bonuses"),
+    ("reset_upkeep_flips", "Upkeep flips made"),
+    ("token_temporary", "Until end of turn"),
+    ("tokens_temporary", "{} (until end of turn)"),
+    ("win_you_call", "You call it"),
+    ("win_heads", "Must be heads"),
+    ("win_opponent_calls", "Opponent calls it"),
+    ("skin_arrows", "Arrows"),
+    ("skin_classic", "Classic"),
+    ("skin_thumbs", "Thumbs"),
+    ("skin_crown", "Crown and skull"),
+    ("skin_custom", "Your pictures"),
+    ("coin_image_too_large", "That picture is too large. Pick one under 256 KB."),
+    ("off", "Off"),
+    ("celebrate_streak", "{} in a row!"),
+    ("announce_undo", "Undone."),
+    ("voice_commands", "Voice commands"),
+    ("voice_unsupported", "This browser can't listen for voice commands."),
+    ("voice_unknown", "Heard \"{}\", which isn't a command."),
+    ("qr_code", "QR code"),
+    ("qr_too_long", "Too much to fit in a QR code. Share the link instead."),
+    ("chat_join_code", "Join code"),
+    ("report_title", "Battle report: {}"),
+    ("report_turn", "Turn {}"),
+    ("report_turn_summary", "Storm {} · {} flips, {} won"),
+    ("report_notable", "{} won {} of {} flips in one go"),
+    ("report_result", "Result"),
+    ("report_flips", "{} flips, {} won ({})"),
+    ("games", "Games"),
+    ("game_name", "Game {}"),
+    ("new_game", "New game"),
+    ("close_game", "Close {}"),
+    ("clear", "Clear"),
+    ("krarks", "Krarks"),
+    ("thumbs", "Thumbs"),
+    ("triggers", "Triggers"),
+    ("payoffs", "Payoffs"),
+    ("spell", "Spell"),
+    ("mana_value", "MV"),
+    ("copies", "Copies"),
+    ("net_mana", "Net mana"),
+    ("storm", "Storm"),
+    ("spell_name", "Spell name"),
+    ("add_spell", "Add spell"),
+    ("cast", "Cast"),
+    ("cast_until_it_sticks", "Cast until it sticks"),
+    ("announce_chain_stuck", "{} stuck after {} casts with {} Krark copies."),
+    ("announce_chain_bounced", "{} was still returned after {} casts, with {} Krark copies."),
+    ("trigger_copied", "Copied"),
+    ("trigger_bounced", "Returned to hand"),
+    ("trigger_missed", "Already returned"),
+    ("step_flip", "Flip next trigger"),
+    ("resolution_progress", "Trigger {} of {}"),
+    ("resolution_new_cast", "The next flip starts a new cast"),
+    ("resolution_line", "Trigger {}: {} → {}"),
+    ("resolution_kept", "(kept {})"),
+    ("resolution_tossed", "Tossed: {}"),
+    ("resolution_choose", "Choose the coin to keep"),
+    ("resolution_keep", "Keep {}"),
+    ("storm_count", "Storm: {}"),
+    ("copy_count", "Copies: {}"),
+    ("magecraft_count", "Magecraft: {}"),
+    ("magecraft_hint", "Cast spells and copies both trigger magecraft; only cast spells add to storm."),
+    ("flip_count", "Flips: {}"),
+    ("won_count", "Won: {}"),
+    ("lost_count", "Lost: {}"),
+    ("card", "Card"),
+    ("activations", "Activations"),
+    ("flips", "Flips"),
+    ("win_rate", "Win rate"),
+    ("wins_per_activation", "Wins / activation"),
+    ("average_storm", "Average storm"),
+    ("longest_streak", "Longest streak"),
+    (
+        "run_lengths_caption",
+        "Wins before each loss across {} streaks, against the expected {} win rate",
+    ),
+    ("run_length_title", "{} wins: {} seen ({}), {} expected"),
+    ("chain_summary", "{}: won {} of {} flips"),
+    ("exact_sequence", "This exact sequence: {}"),
+    ("at_least_as_extreme", "At least this extreme: {}"),
+    ("one_in", "1 in {}"),
+    ("trials", "Trials"),
+    ("max_recasts", "Recasts at most"),
+    ("sweep", "Sweep"),
+    ("from", "from"),
+    ("to", "to"),
+    ("run", "Run"),
+    ("pause", "Pause"),
+    ("resume", "Resume"),
+    ("cancel_simulation", "Cancel"),
+    ("simulation_progress", "Simulation progress"),
+    ("copies_per_cast", "Copies per cast"),
+    ("copies_per_spell", "Copies per spell"),
+    ("casts_per_spell", "Casts per spell"),
+    ("bounce_rate", "Bounce rate"),
+    ("heads_share", "Flips on heads"),
+    ("at_least_one_copy", "At least one copy"),
+    ("analytic", "Exact"),
+    ("simulated", "Simulated"),
+    ("difference", "Difference"),
+    ("spell_casts", "Spells cast"),
+    ("spell_copies", "Copies"),
+    ("spell_total", "Total spells"),
+    ("median_copies", "Median"),
+    ("copies_spread", "5th–95th percentile"),
+    ("chain_length", "Wins before a loss"),
+    ("simulation_details", "{} {}, over {} casts"),
+    ("mean", "Mean"),
+    ("std_dev", "Std. dev."),
+    ("variance", "Variance"),
+    ("percentile", "{}th pct."),
+    ("chain_distribution", "Casts by wins before a loss"),
+    ("heads_ratio_distribution", "Spells by share of flips on heads"),
+    ("copies_distribution", "Spells by copies made"),
+    ("casts_distribution", "Spells by times cast"),
+    ("histogram_copies", "{} copies"),
+    ("histogram_casts", "Cast {} times"),
+    ("histogram_chain", "{} wins before a loss"),
+    ("histogram_bin", "{}: {} casts ({})"),
+    ("seed", "Seed"),
+    ("prev", "Prev"),
+    ("next", "Next"),
+    ("restart", "Restart"),
+    ("seed_invalid", "Seeds are whole numbers"),
+    ("session_seed", "seed {}"),
+    ("settings", "Settings"),
+    ("language", "Language"),
+    ("remove", "Remove"),
+    ("flip_source", "Card to flip for"),
+    ("mana_value_long", "Mana value"),
+    ("flip_timeline", "Flip timeline"),
+    ("sweep_from", "Sweep from"),
+    ("sweep_to", "Sweep to"),
+    ("announce_flips", "{}: {}."),
+    ("announce_flip_many", "{} activations: {} of {} flips won."),
+    ("announce_streak", "{} wins in a row!"),
+    ("announce_cast", "{} cast, resolving {} Krark triggers."),
+    ("motion", "Animations"),
+    ("motion_system", "Follow system setting"),
+    ("motion_reduced", "Reduced"),
+    ("motion_full", "Full"),
+    ("accessibility", "Accessibility"),
+    ("outcome_icons", "Mark wins and losses with shapes"),
+    ("outcome_patterns", "Hatch losses in charts"),
+    ("retention", "History retention"),
+    ("max_flips", "Flips to keep"),
+    ("max_sessions", "Sessions to keep"),
+    ("unlimited", "All"),
+    ("archive_pruned", "Download pruned flips and sessions first"),
+    ("close", "Close"),
+    ("chain_odds", "What were the odds?"),
+    ("font_scale", "Text size: {}%"),
+    ("tour", "Tour"),
+    ("tour_show", "Show tour"),
+    ("tour_step", "Step {} of {}"),
+    ("tour_done", "Done"),
+    ("tour_skip", "Skip tour"),
+    ("commands", "Commands"),
+    ("layout", "Layout"),
+    ("panel_card_of_the_day", "Card of the day"),
+    ("panel_board", "Board"),
+    ("panel_spells", "Spells"),
+    ("panel_archaeologist", "Goblin Archaeologist"),
+    ("panel_upkeep", "Upkeep flips"),
+    ("panel_custom_cards", "Custom cards"),
+    ("panel_stats", "Stats strip"),
+    ("panel_card_stats", "Per-card stats"),
+    ("panel_run_lengths", "Streak lengths"),
+    ("panel_history", "History"),
+    ("panel_resolution", "Step-by-step resolution"),
+    ("panel_sessions", "Saved sessions"),
+    ("panel_records", "Personal records"),
+    ("panel_simulator", "Simulator"),
+    ("panel_seed_explorer", "Seed explorer"),
+    ("panel_mulligan", "Mulligan trainer"),
+    ("panel_draw_odds", "Draw odds"),
+    ("panel_chat", "Chat"),
+    ("panel_scripts", "Scripts"),
+    ("panel_debug", "Debug timings"),
+    ("scripts_enabled", "Run the script on flips, rolls and casts"),
+    ("script_source", "Script"),
+    ("load_script", "Load script"),
+    ("script_error", "Script error: {}"),
+    (
+        "script_help",
+        "Define on_flip(source, wins, losses), on_roll(total) or on_cast(spell, copies) and change this.life, this.hand or this.mana. print() writes to the table log.",
+    ),
+    ("timing_name", "Measure"),
+    ("timing_count", "Count"),
+    ("timing_last", "Last (ms)"),
+    ("timing_mean", "Mean (ms)"),
+    ("timing_max", "Max (ms)"),
+    ("reset_timings", "Reset timings"),
+    ("command_search", "Type a command"),
+    ("theme", "Theme"),
+    ("theme_accent", "Accent"),
+    ("theme_background", "Background"),
+    ("theme_win", "Wins"),
+    ("theme_loss", "Losses"),
+    ("theme_name", "Theme name"),
+    ("theme_save", "Save theme"),
+    ("theme_reset", "Reset to default"),
+    ("theme_export", "Export"),
+    ("theme_import", "Import"),
+    ("theme_import_failed", "That file is not a theme."),
+    ("theme_use", "Use"),
+    ("panel_life", "Life"),
+    ("life", "Life"),
+    ("life_up", "Gain 1 life"),
+    ("life_down", "Lose 1 life"),
+    ("aetherflux", "Aetherflux Reservoir"),
+    ("aetherflux_fire", "Pay {} life: 50 damage"),
+    ("panel_opponents", "Opponents"),
+    ("panel_turn_timer", "Turn timer"),
+    ("storm_payoff", "Storm payoff"),
+    ("payoff_total", "Cast now: {} total"),
+    ("opponent_name", "Opponent name"),
+    ("add_opponent", "Add opponent"),
+    ("lethal", "Lethal"),
+    ("target_opponent", "Target {}"),
+    ("library", "Library"),
+    ("cards_drawn", "Drawn this game: {}"),
+    ("library_left", "{} left in library"),
+    ("library_low", "Only {} left in library"),
+    ("library_empty", "Library empty, your next draw loses"),
+    ("spell_draws", "Draws"),
+    ("spell_draws_long", "Cards each resolution draws"),
+    ("spell_storm", "Has storm"),
+    ("cast_cannot_pay", "The pool can't pay for that spell."),
+    ("milled", "Milled: {}"),
+    ("decked", "Library empty"),
+    ("panel_tokens", "Tokens"),
+    ("token_name", "Token name"),
+    ("add_token", "Add token"),
+    ("tokens_add", "Add a {}"),
+    ("tokens_remove", "Remove a {}"),
+    ("goblin", "Goblin"),
+    ("panel_mana", "Mana pool"),
+    ("mana_pool", "Mana pool"),
+    ("spell_kind", "Card type"),
+    ("instant", "Instant"),
+    ("sorcery", "Sorcery"),
+    ("filter_any", "All spells"),
+    ("filter_instant", "Instants"),
+    ("filter_sorcery", "Sorceries"),
+    ("reducer_name", "Cost reducer"),
+    ("reducer_amount", "Reduction"),
+    ("reducer_applies_to", "Applies to"),
+    ("add_reducer", "Add reducer"),
+    ("reducer_summary", "{}: {} less for {}"),
+    ("ritual_x", "Cards in hand / tapped lands"),
+    ("ritual_uses_x", "Adds mana equal to the count entered"),
+    ("turn_net_mana", "Net mana this turn: {}"),
+    ("panel_hands", "Hands"),
+    ("panel_player_counters", "Player counters and designations"),
+    ("you", "You"),
+    ("hand_up", "{} draws a card"),
+    ("hand_down", "{} discards a card"),
+    ("wheel_event", "{}: every player now holds {} cards."),
+    ("loop_note", "Describe the loop"),
+    ("infinite_mana_declare", "Declare infinite mana"),
+    ("infinite_mana_end", "End infinite mana"),
+    ("panel_creatures", "Creatures"),
+    ("prowess", "Prowess"),
+    ("magecraft", "Magecraft"),
+    ("creature_pumps", "{} ×{}"),
+    ("creature_counters", "+{} counters"),
+    ("creature_name", "Creature name"),
+    ("creature_pt", "Power/toughness"),
+    ("creature_trigger", "Pumped by"),
+    ("creature_bonus", "Bonus per trigger"),
+    ("add_creature", "Add creature"),
+    ("draw_pingers", "Damage per card drawn"),
+    ("draw_card", "Draw a card"),
+    ("pending_damage", "Pings: {}"),
+    ("apply_pings", "Apply {} ping damage"),
+    ("panel_dice", "Dice"),
+    ("dice_expression", "Dice expression"),
+    ("roll", "Roll"),
+    ("dice_total", "Total: {}"),
+    ("dice_empty", "Enter dice to roll, such as 2d6+3."),
+    ("dice_syntax", "Use dice like d100, 2d6+3 or 4d20kh1."),
+    ("dice_too_many", "That is too many dice to roll at once."),
+    ("config_not_a_number", "Enter a whole number."),
+    ("config_negative", "This can't be negative."),
+    ("config_no_flips", "Flip at least one coin."),
+    ("config_cap_below_one", "The cap must be at least 1."),
+    ("config_no_trials", "Run at least one trial."),
+    ("panel_attractions", "Attractions"),
+    ("attraction_name", "Attraction name"),
+    ("add_attraction", "Open attraction"),
+    ("roll_to_visit", "Roll to visit"),
+    ("lit_number", "Lit on {}"),
+    ("visits", "Visits: {}"),
+    ("visit_none", "Rolled {}: no attraction visited."),
+    ("visit_event", "Rolled {}: visited {}."),
+    ("artifact_name", "Artifact"),
+    ("add_artifact", "Add artifact"),
+    ("archaeologist_activate", "Activate: flip a coin"),
+    (
+        "archaeologist_destroyed",
+        "Goblin Archaeologist won the flip: destroyed {} and untapped.",
+    ),
+    (
+        "archaeologist_won",
+        "Goblin Archaeologist won the flip and untapped.",
+    ),
+    (
+        "archaeologist_sacrificed",
+        "Goblin Archaeologist lost the flip and was sacrificed.",
+    ),
+    ("archaeologist_gone", "Sacrificed"),
+    ("archaeologist_return", "Back on the battlefield"),
+    ("archaeologist_tally", "{} activations: {} won, {} lost"),
+    (
+        "mana_crypt_damage",
+        "Mana Crypt lost the flip and dealt {} damage to you.",
+    ),
+    ("karplusan_won", "Karplusan Minotaur deals 1 damage to any target."),
+    (
+        "karplusan_lost",
+        "Karplusan Minotaur deals 1 damage to any target an opponent chooses.",
+    ),
+    ("mana_clash_no_opponent", "Add an opponent to clash with first."),
+    (
+        "announce_mana_clash",
+        "Mana Clash ends after {} rounds: you take {} damage, {} takes {}.",
+    ),
+    ("player_stats", "Flips per player"),
+    ("thumb_tosses", "Coins tossed for your latest flips"),
+    ("toss_kept", "Kept"),
+    ("toss_discarded", "Discarded by Krark's Thumb"),
+    ("session_length", "Session length"),
+    ("flips_per_minute", "Flips per minute"),
+    ("session_length_stat", "Session {}"),
+    ("flips_per_minute_stat", "{} flips/min"),
+    ("turn_times", "Turn times, {} on average"),
+    ("average_turn", "Average turn"),
+    ("report_pace", "Session length {}, {} flips per minute"),
+    ("player", "Player"),
+    ("share_snapshot", "Share snapshot"),
+    (
+        "snapshot_link_hint",
+        "Whoever opens this link sees the game as it is now, without later changes, and cannot change it.",
+    ),
+    ("snapshot_of", "Snapshot of {}"),
+    ("stop_spectating", "Back to my games"),
+    ("cards_in_hand", "Cards in hand"),
+    ("chat_server", "Relay server"),
+    ("chat_name", "Your name"),
+    ("chat_connect", "Connect"),
+    ("chat_disconnect", "Disconnect"),
+    ("chat_disconnected", "Not connected"),
+    ("chat_connecting", "Connecting…"),
+    ("chat_connected", "Connected"),
+    ("chat_failed", "Could not reach the relay server."),
+    ("chat_message", "Message"),
+    ("chat_send", "Send"),
+    ("time_bank", "Time bank (minutes)"),
+    ("turn_warning", "Warn after (minutes per turn)"),
+    ("turn_elapsed", "This turn: {}"),
+    ("start_clock", "Start clock"),
+    ("pass_clock", "Next player"),
+    ("reset_clock", "Reset clock"),
+    ("random_seating", "Randomize seating"),
+    ("roll_for_first", "Roll for first player"),
+    ("seating_order", "Seating: {}"),
+    ("roll_off", "Roll for first player: {}"),
+    ("roll_off_reroll", "Tie, rerolled: {}"),
+    ("first_player", "{} goes first."),
+    ("monarch", "Monarch"),
+    ("initiative", "Initiative"),
+    ("nobody", "Nobody"),
+    ("day", "Day"),
+    ("night", "Night"),
+    ("day_night_none", "Neither day nor night"),
+    ("day_night_override", "Switch between day, night and neither"),
+    ("day_night_next", "{} once the turn ends"),
+    ("becomes_day", "It becomes day."),
+    ("becomes_night", "It becomes night."),
+    ("poison", "Poison"),
+    ("energy", "Energy"),
+    ("experience", "Experience"),
+    ("poisoned", "Poisoned"),
+    ("designations", "Designations"),
+    ("citys_blessing", "City's blessing"),
+    ("cant_lose", "Can't lose"),
+    ("player_hexproof", "Hexproof"),
+    ("card_of_the_day_loading", "Finding today's card…"),
+    ("card_of_the_day_failed", "Today's card could not be loaded from Scryfall."),
+    ("track_card", "Track this card"),
+    ("okaun", "Okaun, Eye of Chaos"),
+    ("record_best_streak", "Best streak"),
+    ("record_highest_storm", "Highest storm"),
+    ("record_most_copies", "Most copies of one spell"),
+    ("record_biggest_okaun", "Biggest Okaun"),
+    ("announce_record", "New record: {}!"),
+    ("counter_up", "{}: {} +1"),
+    ("counter_down", "{}: {} −1"),
+    ("initiative_taken", "{} takes the initiative and ventures into Undercity."),
+    ("monarch_end_step", "You are the monarch: draw a card."),
+    (
+        "initiative_upkeep",
+        "You have the initiative: venture into Undercity in your upkeep.",
+    ),
+    ("custom_card_json", "Card definition (JSON)"),
+    ("import_cards", "Add cards"),
+    ("decklist", "Decklist"),
+    ("import_deck", "Import deck"),
+    ("deck_none", "No decklist imported yet."),
+    ("deck_size", "{} cards, {} different"),
+    ("deck_empty", "The decklist has no cards."),
+    ("deck_bad_count", "A card count must be a whole number above zero."),
+    ("state_too_new", "Your saved setup comes from a newer version of Krarkinator. It was left alone, and nothing changed here will be saved over it."),
+    ("state_unreadable", "Your saved setup could not be read, so the defaults are in use. A copy of it was kept in this browser's storage under krarkinator.state.unreadable."),
+    ("mulligan_pieces", "Pieces to keep, one requirement per line"),
+    ("pieces_bad_count", "A piece count must be a whole number above zero."),
+    ("pieces_unknown_card", "{} is not in the decklist."),
+    ("pieces_none", "Name at least one piece to look for."),
+    ("max_mulligans", "Most mulligans"),
+    ("mulligans", "Mulligans"),
+    ("kept_by", "Kept by then"),
+    ("deal_hand", "Deal a hand"),
+    ("mulligan_keep", "Keep"),
+    ("mulligan_ship", "Mulligan"),
+    ("draw_odds_card", "Card from the decklist"),
+    ("deck_size_label", "Cards in library"),
+    ("draws", "Draws"),
+    ("draw_odds_result", "{} to draw at least one copy"),
+    ("draw_odds_too_many", "There are more copies or draws than cards in the library."),
+    ("custom_card_effect", "{}: {}"),
+    ("card_json", "That is not a card definition."),
+    ("card_no_name", "Every card needs a name."),
+    ("card_no_flips", "A card has to flip at least one coin."),
+    (
+        "card_effect_syntax",
+        "Write effects like \"life -2, hand +1\".",
+    ),
+    (
+        "card_effect_target",
+        "Effects can change life, hand, mana or tokens:<name>.",
+    ),
+    ("trigger_flip_won", "per won flip"),
+    ("trigger_flip_lost", "per lost flip"),
+    ("trigger_cast", "per spell cast"),
+    ("trigger_turn_end", "at end of turn"),
+    ("panel_unfinity", "Tickets and stickers"),
+    ("tickets", "Tickets"),
+    ("ticket_gain", "Gain a ticket"),
+    ("ticket_spend", "Spend a ticket"),
+    ("sticker_text", "Sticker"),
+    ("add_sticker", "Add sticker"),
+    ("brainiac", "Brainiac"),
+    ("big_idea", "The Big Idea: roll a d6"),
+    ("big_idea_boosted", "The Big Idea: roll 2d6"),
+    ("big_idea_boost", "Tap {} Brainiacs"),
+    ("d20_card", "Roll-a-d20 card"),
+    ("roll_d20", "Roll a d20"),
+    ("d20_event", "{}: rolled {}, {}"),
+    ("roll_celebr", "Celebr-8000: roll 2d6"),
+    ("celebr_event", "Celebr-8000 rolled {} and {}: {}"),
+    ("bonus_pump", "+1/+1"),
+    ("bonus_first_strike", "First strike"),
+    ("bonus_vigilance", "Vigilance"),
+    ("bonus_lifelink", "Lifelink"),
+    ("bonus_indestructible", "Indestructible"),
+    ("bonus_double_strike", "Double strike"),
+    ("puzzlebox", "Vexing Puzzlebox"),
+    ("puzzlebox_charge", "{} of {} charge counters"),
+    ("puzzlebox_fetch", "Remove counters: fetch an artifact"),
+    ("barbarian_class", "Barbarian Class"),
+    ("not_in_play", "Not in play"),
+    ("class_level", "Level {}"),
+    (
+        "barbarian_level_1",
+        "Roll one extra die and ignore the lowest roll",
+    ),
+    (
+        "barbarian_level_2",
+        "Whenever you roll dice, target creature you control gets +1/+0 and menace",
+    ),
+    ("barbarian_level_3", "Creatures you control have haste"),
+    (
+        "barbarian_trigger",
+        "Barbarian Class: give target creature +1/+0 and menace",
+    ),
+    ("announce_roll", "{}: {}."),
+    (
+        "tour_flip",
+        "Pick a card and press Flip to flip coins for it. Every flip lands in the history.",
+    ),
+    (
+        "tour_board",
+        "Set how many Krarks, Krark's Thumbs and mana payoffs you control.",
+    ),
+    (
+        "tour_stats",
+        "The stats strip tracks storm, wins and losses. Click a bar to jump to that flip.",
+    ),
+];
+
+const DEUTSCH: &[(&str, &str)] = &[
+    ("heads", "Kopf"),
+    ("tails", "Zahl"),
+    ("both", "Beides"),
+    ("flip", "Werfen"),
+    ("end_turn", "Zug beenden"),
+    ("fast_forward", "Aktivierungen im Schnelldurchlauf"),
+    ("flip_times", "Werfen ×{}"),
+    ("save_session", "Sitzung speichern"),
+    ("export_report", "Spielbericht exportieren"),
+    ("export_csv", "Sitzung als CSV exportieren"),
+    ("export_json", "Sitzung als JSON exportieren"),
+    ("import_session", "Sitzung oder Einrichtung importieren"),
+    ("import_too_new", "Diese Datei stammt aus einer neueren Version von Krarkinator."),
+    ("import_unreadable", "Diese Datei ist weder eine Sitzung noch eine Einrichtung."),
+    ("import_inconsistent", "Würfe, Auslöser und Zusammenfassung dieser Sitzung passen nicht zusammen."),
+    ("announce_imported", "Importiert."),
+    ("take_snapshot", "Spielfeld als Bild speichern"),
+    ("haptics", "Bei Würfen vibrieren"),
+    ("speak_results", "Ergebnisse vorlesen"),
+    ("undo", "Rückgängig"),
+    ("celebration_streak", "Serien feiern ab"),
+    ("coin_skin", "Münzseiten"),
+    ("win_conditions", "Was einen Münzwurf gewinnt"),
+    ("combat_flipper", "Wirft"),
+    ("combat_attacker", "Greift an"),
+    ("combat_blocker", "Blockt"),
+    ("combat_no_player", "Wähle erst, wer wirft, angreift und blockt."),
+    ("combat_won", "{} ({}) hat den Münzwurf gewonnen."),
+    ("combat_lost", "{} ({}) hat den Münzwurf verloren."),
+    ("combat_blocking", "Die blockenden Kreaturen von {} werden {}."),
+    ("combat_blocked", "Die geblockten Kreaturen von {} werden {}."),
+    ("fate_sacrificed", "geopfert"),
+    ("fate_destroyed", "zerstört"),
+    ("fate_returned", "auf die Hand ihrer Besitzer zurückgegeben"),
+    ("risky_move", "Riskantes Manöver"),
+    ("risky_move_controller", "Kontrolliert von"),
+    ("risky_move_next_upkeep", "Nächster Versorgungsschritt"),
+    ("risky_move_taken", "{} übernimmt Riskantes Manöver und muss dafür eine Münze werfen."),
+    ("risky_move_due", "{} hat Riskantes Manöver übernommen: Wähle eine Kreatur und einen Gegner, dann wirf."),
+    ("risky_move_creature", "Gefährdete Kreatur"),
+    ("risky_move_recipient", "Gegner, der sie bekommt"),
+    ("risky_move_choose", "Wähle eine Kreatur und einen Gegner, bevor du für Riskantes Manöver wirfst."),
+    ("risky_move_won", "{} hat den Münzwurf für Riskantes Manöver gewonnen und behält die Kreatur."),
+    ("risky_move_lost", "{} hat den Münzwurf für Riskantes Manöver verloren: {} erhält die Kontrolle über {}."),
+    ("risky_move_handover", "{}: {} → {}"),
+    ("upkeep_due", "muss noch werfen"),
+    ("upkeep_flipped", "geworfen"),
+    ("upkeep_add", "Permanent hinzufügen…"),
+    ("upkeep_unresolved", "Ausstehende Münzwürfe"),
+    ("upkeep_unresolved_cards", "Diese haben in diesem Zug noch nicht geworfen: {}."),
+    ("upkeep_go_back", "Zurück"),
+    ("upkeep_end_anyway", "Zug trotzdem beenden"),
+    ("end_of_turn_resets", "Am Zugende zurücksetzen"),
+    ("reset_storm", "Sturmzähler und Kopien"),
+    ("reset_turn_casts", "In diesem Zug gewirkte Zauber"),
+    ("reset_floating_mana", "Übriges Mana"),
+    ("reset_infinite_mana", "Unendliche Mana-Kombo"),
+    ("reset_pumps", "Bonusse durch Bravour und Magiekunst"),
+    ("reset_temporary_tokens", "Spielsteine bis zum Ende des Zuges"),
+    ("reset_celebr_bonuses", "Celebr-8000-Boni"),
+    ("reset_upkeep_flips", "Gemachte Münzwürfe im Versorgungsschritt"),
+    ("token_temporary", "Bis zum Ende des Zuges"),
+    ("tokens_temporary", "{} (bis zum Ende des Zuges)"),
+    ("win_you_call", "Du sagst an"),
+    ("win_heads", "Muss Kopf sein"),
+    ("win_opponent_calls", "Gegner sagt an"),
+    ("skin_arrows", "Pfeile"),
+    ("skin_classic", "Klassisch"),
+    ("skin_thumbs", "Daumen"),
+    ("skin_crown", "Krone und Schädel"),
+    ("skin_custom", "Eigene Bilder"),
+    ("coin_image_too_large", "Das Bild ist zu groß. Wähle eines unter 256 KB."),
+    ("off", "Aus"),
+    ("celebrate_streak", "{} am Stück!"),
+    ("announce_undo", "Rückgängig gemacht."),
+    ("voice_commands", "Sprachbefehle"),
+    ("voice_unsupported", "Dieser Browser kann keine Sprachbefehle hören."),
+    ("voice_unknown", "„{}“ gehört, das ist kein Befehl."),
+    ("qr_code", "QR-Code"),
+    ("qr_too_long", "Zu viel für einen QR-Code. Teile stattdessen den Link."),
+    ("chat_join_code", "Beitrittscode"),
+    ("report_title", "Spielbericht: {}"),
+    ("report_turn", "Zug {}"),
+    ("report_turn_summary", "Sturm {} · {} Würfe, {} gewonnen"),
+    ("report_notable", "{} gewann {} von {} Würfen am Stück"),
+    ("report_result", "Ergebnis"),
+    ("report_flips", "{} Würfe, {} gewonnen ({})"),
+    ("games", "Partien"),
+    ("game_name", "Partie {}"),
+    ("new_game", "Neue Partie"),
+    ("close_game", "{} schließen"),
+    ("clear", "Leeren"),
+    ("krarks", "Krarks"),
+    ("thumbs", "Daumen"),
+    ("triggers", "Auslöser"),
+    ("payoffs", "Belohnungen"),
+    ("spell", "Zauberspruch"),
+    ("mana_value", "MW"),
+    ("copies", "Kopien"),
+    ("net_mana", "Netto-Mana"),
+    ("storm", "Sturm"),
+    ("spell_name", "Name des Zauberspruchs"),
+    ("add_spell", "Zauberspruch hinzufügen"),
+    ("cast", "Wirken"),
+    ("cast_until_it_sticks", "Wirken, bis er bleibt"),
+    ("announce_chain_stuck", "{} blieb nach {} Mal Wirken, mit {} Krark-Kopien."),
+    ("announce_chain_bounced", "{} ging nach {} Mal Wirken noch zurück, mit {} Krark-Kopien."),
+    ("trigger_copied", "Kopiert"),
+    ("trigger_bounced", "Auf die Hand zurück"),
+    ("trigger_missed", "Schon zurück"),
+    ("step_flip", "Nächsten Auslöser werfen"),
+    ("resolution_progress", "Auslöser {} von {}"),
+    ("resolution_new_cast", "Der nächste Wurf beginnt einen neuen Zauber"),
+    ("resolution_line", "Auslöser {}: {} → {}"),
+    ("resolution_kept", "({} behalten)"),
+    ("resolution_tossed", "Geworfen: {}"),
+    ("resolution_choose", "Welche Münze behalten?"),
+    ("resolution_keep", "{} behalten"),
+    ("storm_count", "Sturm: {}"),
+    ("copy_count", "Kopien: {}"),
+    ("magecraft_count", "Magiekunst: {}"),
+    ("magecraft_hint", "Gewirkte Zauber und Kopien lösen beide Magiekunst aus; nur gewirkte Zauber erhöhen den Sturm."),
+    ("flip_count", "Würfe: {}"),
+    ("won_count", "Gewonnen: {}"),
+    ("lost_count", "Verloren: {}"),
+    ("card", "Karte"),
+    ("activations", "Aktivierungen"),
+    ("flips", "Würfe"),
+    ("win_rate", "Gewinnrate"),
+    ("wins_per_activation", "Gewinne / Aktivierung"),
+    ("average_storm", "Durchschnittlicher Sturm"),
+    ("longest_streak", "Längste Serie"),
+    (
+        "run_lengths_caption",
+        "Gewinne vor jeder Niederlage über {} Serien, bei erwarteter Gewinnrate von {}",
+    ),
+    (
+        "run_length_title",
+        "{} Gewinne: {} gesehen ({}), {} erwartet",
+    ),
+    ("chain_summary", "{}: {} von {} Würfen gewonnen"),
+    ("exact_sequence", "Genau diese Folge: {}"),
+    ("at_least_as_extreme", "Mindestens so extrem: {}"),
+    ("one_in", "1 zu {}"),
+    ("trials", "Versuche"),
+    ("max_recasts", "Höchstens so oft erneut wirken"),
+    ("sweep", "Variieren"),
+    ("from", "von"),
+    ("to", "bis"),
+    ("run", "Starten"),
+    ("pause", "Pausieren"),
+    ("resume", "Fortsetzen"),
+    ("cancel_simulation", "Abbrechen"),
+    ("simulation_progress", "Fortschritt der Simulation"),
+    ("copies_per_cast", "Kopien pro Wirkung"),
+    ("copies_per_spell", "Kopien pro Zauber"),
+    ("casts_per_spell", "Wirkungen pro Zauber"),
+    ("bounce_rate", "Rückkehrrate"),
+    ("heads_share", "Würfe auf Kopf"),
+    ("at_least_one_copy", "Mindestens eine Kopie"),
+    ("analytic", "Exakt"),
+    ("simulated", "Simuliert"),
+    ("difference", "Abweichung"),
+    ("spell_casts", "Gewirkte Zauber"),
+    ("spell_copies", "Kopien"),
+    ("spell_total", "Zauber insgesamt"),
+    ("median_copies", "Median"),
+    ("copies_spread", "5.–95. Perzentil"),
+    ("chain_length", "Siege vor einer Niederlage"),
+    ("simulation_details", "{} {}, über {} Zauber"),
+    ("mean", "Mittelwert"),
+    ("std_dev", "Std.-Abw."),
+    ("variance", "Varianz"),
+    ("percentile", "{}. Perz."),
+    ("chain_distribution", "Zauber nach Siegen vor einer Niederlage"),
+    ("heads_ratio_distribution", "Zauber nach Anteil der Würfe auf Kopf"),
+    ("copies_distribution", "Zauber nach Anzahl der Kopien"),
+    ("casts_distribution", "Zauber nach Anzahl der Wirkungen"),
+    ("histogram_copies", "{} Kopien"),
+    ("histogram_casts", "{}-mal gewirkt"),
+    ("histogram_chain", "{} Siege vor einer Niederlage"),
+    ("histogram_bin", "{}: {} Zauber ({})"),
+    ("seed", "Seed"),
+    ("prev", "Zurück"),
+    ("next", "Weiter"),
+    ("restart", "Neu starten"),
+    ("seed_invalid", "Seeds sind ganze Zahlen"),
+    ("session_seed", "Seed {}"),
+    ("settings", "Einstellungen"),
+    ("language", "Sprache"),
+    ("remove", "Entfernen"),
+    ("flip_source", "Karte, für die geworfen wird"),
+    ("mana_value_long", "Manawert"),
+    ("flip_timeline", "Wurfverlauf"),
+    ("sweep_from", "Variieren von"),
+    ("sweep_to", "Variieren bis"),
+    ("announce_flips", "{}: {}."),
+    ("announce_flip_many", "{} Aktivierungen: {} von {} Würfen gewonnen."),
+    ("announce_streak", "{} Gewinne in Folge!"),
+    (
+        "announce_cast",
+        "{} gewirkt, {} Krark-Auslöser werden verrechnet.",
+    ),
+    ("motion", "Animationen"),
+    ("motion_system", "Systemeinstellung folgen"),
+    ("motion_reduced", "Reduziert"),
+    ("motion_full", "Vollständig"),
+    ("accessibility", "Barrierefreiheit"),
+    (
+        "outcome_icons",
+        "Gewinne und Niederlagen mit Formen markieren",
+    ),
+    ("outcome_patterns", "Niederlagen in Diagrammen schraffieren"),
+    ("retention", "Verlauf aufbewahren"),
+    ("max_flips", "Behaltene Würfe"),
+    ("max_sessions", "Behaltene Sitzungen"),
+    ("unlimited", "Alle"),
+    ("archive_pruned", "Entfernte Würfe und Sitzungen vorher herunterladen"),
+    ("close", "Schließen"),
+    ("chain_odds", "Wie wahrscheinlich war das?"),
+    ("font_scale", "Textgröße: {}%"),
+    ("tour", "Einführung"),
+    ("tour_show", "Einführung zeigen"),
+    ("tour_step", "Schritt {} von {}"),
+    ("tour_done", "Fertig"),
+    ("tour_skip", "Einführung überspringen"),
+    ("commands", "Befehle"),
+    ("layout", "Layout"),
+    ("panel_card_of_the_day", "Karte des Tages"),
+    ("panel_board", "Spielfeld"),
+    ("panel_spells", "Zaubersprüche"),
+    ("panel_archaeologist", "Goblin-Archäologe"),
+    ("panel_upkeep", "Münzwürfe im Versorgungsschritt"),
+    ("panel_custom_cards", "Eigene Karten"),
+    ("panel_stats", "Statistikleiste"),
+    ("panel_card_stats", "Statistik pro Karte"),
+    ("panel_run_lengths", "Serienlängen"),
+    ("panel_history", "Verlauf"),
+    ("panel_resolution", "Schrittweise Auflösung"),
+    ("panel_sessions", "Gespeicherte Sitzungen"),
+    ("panel_records", "Persönliche Rekorde"),
+    ("panel_simulator", "Simulator"),
+    ("panel_seed_explorer", "Seed-Explorer"),
+    ("panel_mulligan", "Mulligan-Training"),
+    ("panel_draw_odds", "Ziehchancen"),
+    ("panel_chat", "Chat"),
+    ("panel_scripts", "Skripte"),
+    ("panel_debug", "Debug-Zeiten"),
+    ("scripts_enabled", "Skript bei Würfen, Würfelwürfen und Zaubern ausführen"),
+    ("script_source", "Skript"),
+    ("load_script", "Skript laden"),
+    ("script_error", "Skriptfehler: {}"),
+    (
+        "script_help",
+        "Definiere on_flip(source, wins, losses), on_roll(total) oder on_cast(spell, copies) und ändere this.life, this.hand oder this.mana. print() schreibt ins Tischprotokoll.",
+    ),
+    ("timing_name", "Messung"),
+    ("timing_count", "Anzahl"),
+    ("timing_last", "Zuletzt (ms)"),
+    ("timing_mean", "Mittel (ms)"),
+    ("timing_max", "Maximum (ms)"),
+    ("reset_timings", "Zeiten zurücksetzen"),
+    ("command_search", "Befehl eingeben"),
+    ("theme", "Farbschema"),
+    ("theme_accent", "Akzent"),
+    ("theme_background", "Hintergrund"),
+    ("theme_win", "Gewinne"),
+    ("theme_loss", "Niederlagen"),
+    ("theme_name", "Name des Farbschemas"),
+    ("theme_save", "Farbschema speichern"),
+    ("theme_reset", "Auf Standard zurücksetzen"),
+    ("theme_export", "Exportieren"),
+    ("theme_import", "Importieren"),
+    ("theme_import_failed", "Diese Datei ist kein Farbschema."),
+    ("theme_use", "Verwenden"),
+    ("panel_life", "Lebenspunkte"),
+    ("life", "Lebenspunkte"),
+    ("life_up", "1 Lebenspunkt erhalten"),
+    ("life_down", "1 Lebenspunkt verlieren"),
+    ("aetherflux", "Ätherfluss-Reservoir"),
+    ("aetherflux_fire", "{} Lebenspunkte zahlen: 50 Schaden"),
+    ("panel_opponents", "Gegner"),
+    ("panel_turn_timer", "Zugzeit"),
+    ("storm_payoff", "Sturm-Finisher"),
+    ("payoff_total", "Jetzt gewirkt: {} insgesamt"),
+    ("opponent_name", "Name des Gegners"),
+    ("add_opponent", "Gegner hinzufügen"),
+    ("lethal", "Tödlich"),
+    ("target_opponent", "{} als Ziel wählen"),
+    ("library", "Bibliothek"),
+    ("cards_drawn", "Diese Partie gezogen: {}"),
+    ("library_left", "Noch {} in der Bibliothek"),
+    ("library_low", "Nur noch {} in der Bibliothek"),
+    ("library_empty", "Bibliothek leer, der nächste Zug verliert"),
+    ("spell_draws", "Ziehen"),
+    ("spell_draws_long", "Karten, die jede Auflösung zieht"),
+    ("spell_storm", "Hat Sturm"),
+    ("cast_cannot_pay", "Der Manavorrat reicht für diesen Zauber nicht."),
+    ("milled", "Gemahlen: {}"),
+    ("decked", "Bibliothek leer"),
+    ("panel_tokens", "Spielsteine"),
+    ("token_name", "Name des Spielsteins"),
+    ("add_token", "Spielstein hinzufügen"),
+    ("tokens_add", "{} hinzufügen"),
+    ("tokens_remove", "{} entfernen"),
+    ("goblin", "Goblin"),
+    ("panel_mana", "Manavorrat"),
+    ("mana_pool", "Manavorrat"),
+    ("spell_kind", "Kartentyp"),
+    ("instant", "Spontanzauber"),
+    ("sorcery", "Hexerei"),
+    ("filter_any", "Alle Zaubersprüche"),
+    ("filter_instant", "Spontanzauber"),
+    ("filter_sorcery", "Hexereien"),
+    ("reducer_name", "Kostensenker"),
+    ("reducer_amount", "Senkung"),
+    ("reducer_applies_to", "Gilt für"),
+    ("add_reducer", "Kostensenker hinzufügen"),
+    ("reducer_summary", "{}: {} weniger für {}"),
+    ("ritual_x", "Handkarten / getappte Länder"),
+    ("ritual_uses_x", "Erzeugt so viel Mana wie die eingegebene Anzahl"),
+    ("turn_net_mana", "Netto-Mana in diesem Zug: {}"),
+    ("panel_hands", "Handkarten"),
+    ("panel_player_counters", "Spielermarken und Bezeichnungen"),
+    ("you", "Du"),
+    ("hand_up", "{} zieht eine Karte"),
+    ("hand_down", "{} wirft eine Karte ab"),
+    ("wheel_event", "{}: Jeder Spieler hält jetzt {} Karten."),
+    ("loop_note", "Schleife beschreiben"),
+    ("infinite_mana_declare", "Unendlich Mana ansagen"),
+    ("infinite_mana_end", "Unendlich Mana beenden"),
+    ("panel_creatures", "Kreaturen"),
+    ("prowess", "Bravour"),
+    ("magecraft", "Magiekunst"),
+    ("creature_pumps", "{} ×{}"),
+    ("creature_counters", "+{} Marken"),
+    ("creature_name", "Name der Kreatur"),
+    ("creature_pt", "Stärke/Widerstandskraft"),
+    ("creature_trigger", "Verstärkt durch"),
+    ("creature_bonus", "Bonus pro Auslösung"),
+    ("add_creature", "Kreatur hinzufügen"),
+    ("draw_pingers", "Schaden pro gezogener Karte"),
+    ("draw_card", "Karte ziehen"),
+    ("pending_damage", "Schaden: {}"),
+    ("apply_pings", "{} Schaden anwenden"),
+    ("panel_dice", "Würfel"),
+    ("dice_expression", "Würfelausdruck"),
+    ("roll", "Würfeln"),
+    ("dice_total", "Summe: {}"),
+    ("dice_empty", "Gib Würfel ein, etwa 2d6+3."),
+    ("dice_syntax", "Schreibe Würfel wie d100, 2d6+3 oder 4d20kh1."),
+    ("dice_too_many", "Das sind zu viele Würfel auf einmal."),
+    ("config_not_a_number", "Gib eine ganze Zahl ein."),
+    ("config_negative", "Das darf nicht negativ sein."),
+    ("config_no_flips", "Wirf mindestens eine Münze."),
+    ("config_cap_below_one", "Die Obergrenze muss mindestens 1 sein."),
+    ("config_no_trials", "Führe mindestens einen Versuch durch."),
+    ("panel_attractions", "Attraktionen"),
+    ("attraction_name", "Name der Attraktion"),
+    ("add_attraction", "Attraktion öffnen"),
+    ("roll_to_visit", "Für Besuch würfeln"),
+    ("lit_number", "Leuchtet bei {}"),
+    ("visits", "Besuche: {}"),
+    ("visit_none", "{} gewürfelt: keine Attraktion besucht."),
+    ("visit_event", "{} gewürfelt: {} besucht."),
+    ("artifact_name", "Artefakt"),
+    ("add_artifact", "Artefakt hinzufügen"),
+    ("archaeologist_activate", "Aktivieren: Münze werfen"),
+    ("archaeologist_destroyed", "Goblin-Archäologe gewinnt den Münzwurf: {} zerstört und enttappt."),
+    ("archaeologist_won", "Goblin-Archäologe gewinnt den Münzwurf und wird enttappt."),
+    ("archaeologist_sacrificed", "Goblin-Archäologe verliert den Münzwurf und wird geopfert."),
+    ("mana_crypt_damage", "Mana Crypt verliert den Münzwurf und fügt dir {} Schadenspunkte zu."),
+    ("karplusan_won", "Karplusan-Minotaurus fügt einem Ziel deiner Wahl 1 Schadenspunkt zu."),
+    ("karplusan_lost", "Karplusan-Minotaurus fügt einem Ziel nach Wahl eines Gegners 1 Schadenspunkt zu."),
+    ("mana_clash_no_opponent", "Füge zuerst einen Gegner hinzu."),
+    ("announce_mana_clash", "Manakonflikt endet nach {} Runden: Du erleidest {} Schaden, {} erleidet {}."),
+    ("player_stats", "Münzwürfe pro Spieler"),
+    ("thumb_tosses", "Geworfene Münzen deiner letzten Würfe"),
+    ("toss_kept", "Behalten"),
+    ("toss_discarded", "Durch Krark's Thumb verworfen"),
+    ("session_length", "Sitzungsdauer"),
+    ("flips_per_minute", "Würfe pro Minute"),
+    ("session_length_stat", "Sitzung {}"),
+    ("flips_per_minute_stat", "{} Würfe/Min."),
+    ("turn_times", "Zugzeiten, im Schnitt {}"),
+    ("average_turn", "Durchschnittlicher Zug"),
+    ("report_pace", "Sitzungsdauer {}, {} Würfe pro Minute"),
+    ("player", "Spieler"),
+    ("share_snapshot", "Schnappschuss teilen"),
+    ("snapshot_link_hint", "Wer diesen Link öffnet, sieht das Spiel so, wie es gerade ist, ohne spätere Änderungen, und kann es nicht ändern."),
+    ("snapshot_of", "Schnappschuss von {}"),
+    ("stop_spectating", "Zurück zu meinen Spielen"),
+    ("cards_in_hand", "Handkarten"),
+    ("chat_server", "Relay-Server"),
+    ("chat_name", "Dein Name"),
+    ("chat_connect", "Verbinden"),
+    ("chat_disconnect", "Trennen"),
+    ("chat_disconnected", "Nicht verbunden"),
+    ("chat_connecting", "Verbinde…"),
+    ("chat_connected", "Verbunden"),
+    ("chat_failed", "Der Relay-Server ist nicht erreichbar."),
+    ("chat_message", "Nachricht"),
+    ("chat_send", "Senden"),
+    ("time_bank", "Zeitkonto (Minuten)"),
+    ("turn_warning", "Warnen nach (Minuten pro Zug)"),
+    ("turn_elapsed", "Dieser Zug: {}"),
+    ("start_clock", "Uhr starten"),
+    ("pass_clock", "Nächster Spieler"),
+    ("reset_clock", "Uhr zurücksetzen"),
+    ("random_seating", "Sitzordnung auslosen"),
+    ("roll_for_first", "Startspieler auswürfeln"),
+    ("seating_order", "Sitzordnung: {}"),
+    ("roll_off", "Wurf um den Startspieler: {}"),
+    ("roll_off_reroll", "Gleichstand, neu gewürfelt: {}"),
+    ("first_player", "{} beginnt."),
+    ("monarch", "Monarch"),
+    ("initiative", "Initiative"),
+    ("nobody", "Niemand"),
+    ("day", "Tag"),
+    ("night", "Nacht"),
+    ("day_night_none", "Weder Tag noch Nacht"),
+    ("day_night_override", "Zwischen Tag, Nacht und keinem von beiden wechseln"),
+    ("day_night_next", "{} nach dem Zugende"),
+    ("becomes_day", "Es wird Tag."),
+    ("becomes_night", "Es wird Nacht."),
+    ("poison", "Gift"),
+    ("energy", "Energie"),
+    ("experience", "Erfahrung"),
+    ("poisoned", "Vergiftet"),
+    ("designations", "Bezeichnungen"),
+    ("citys_blessing", "Segen der Stadt"),
+    ("cant_lose", "Kann nicht verlieren"),
+    ("player_hexproof", "Fluchsicher"),
+    ("card_of_the_day_loading", "Die Karte des Tages wird gesucht…"),
+    ("card_of_the_day_failed", "Die Karte des Tages konnte nicht von Scryfall geladen werden."),
+    ("track_card", "Karte verfolgen"),
+    ("okaun", "Okaun, Auge des Chaos"),
+    ("record_best_streak", "Längste Serie"),
+    ("record_highest_storm", "Höchster Sturm"),
+    ("record_most_copies", "Meiste Kopien eines Zaubers"),
+    ("record_biggest_okaun", "Größter Okaun"),
+    ("announce_record", "Neuer Rekord: {}!"),
+    ("counter_up", "{}: {} +1"),
+    ("counter_down", "{}: {} −1"),
+    ("initiative_taken", "{} übernimmt die Initiative und unternimmt einen Vorstoß in die Unterstadt."),
+    ("monarch_end_step", "Du bist der Monarch: Ziehe eine Karte."),
+    ("initiative_upkeep", "Du hast die Initiative: Unternimm in deinem Versorgungssegment einen Vorstoß in die Unterstadt."),
+    ("custom_card_json", "Kartendefinition (JSON)"),
+    ("import_cards", "Karten hinzufügen"),
+    ("decklist", "Deckliste"),
+    ("import_deck", "Deck importieren"),
+    ("deck_none", "Noch keine Deckliste importiert."),
+    ("deck_size", "{} Karten, {} verschiedene"),
+    ("deck_empty", "Die Deckliste enthält keine Karten."),
+    ("deck_bad_count", "Eine Kartenanzahl muss eine ganze Zahl über null sein."),
+    ("state_too_new", "Deine gespeicherte Einrichtung stammt aus einer neueren Version von Krarkinator. Sie bleibt unverändert, und hier Geändertes wird nicht darüber gespeichert."),
+    ("state_unreadable", "Deine gespeicherte Einrichtung konnte nicht gelesen werden, daher gelten die Standardwerte. Eine Kopie liegt im Speicher dieses Browsers unter krarkinator.state.unreadable."),
+    ("mulligan_pieces", "Nötige Teile, eine Bedingung pro Zeile"),
+    ("pieces_bad_count", "Eine Anzahl muss eine ganze Zahl über null sein."),
+    ("pieces_unknown_card", "{} ist nicht in der Deckliste."),
+    ("pieces_none", "Nenne mindestens ein Teil, nach dem gesucht wird."),
+    ("max_mulligans", "Höchstens Mulligans"),
+    ("mulligans", "Mulligans"),
+    ("kept_by", "Bis dahin behalten"),
+    ("deal_hand", "Hand austeilen"),
+    ("mulligan_keep", "Behalten"),
+    ("mulligan_ship", "Mulligan"),
+    ("draw_odds_card", "Karte aus der Deckliste"),
+    ("deck_size_label", "Karten in der Bibliothek"),
+    ("draws", "Züge"),
+    ("draw_odds_result", "{}, mindestens eine Kopie zu ziehen"),
+    ("draw_odds_too_many", "Es gibt mehr Kopien oder Züge als Karten in der Bibliothek."),
+    ("custom_card_effect", "{}: {}"),
+    ("card_json", "Das ist keine Kartendefinition."),
+    ("card_no_name", "Jede Karte braucht einen Namen."),
+    ("card_no_flips", "Eine Karte muss mindestens eine Münze werfen."),
+    ("card_effect_syntax", "Schreibe Effekte wie \"life -2, hand +1\"."),
+    ("card_effect_target", "Effekte können life, hand, mana oder tokens:<Name> ändern."),
+    ("trigger_flip_won", "je gewonnenem Wurf"),
+    ("trigger_flip_lost", "je verlorenem Wurf"),
+    ("trigger_cast", "je gewirktem Zauber"),
+    ("trigger_turn_end", "am Ende des Zuges"),
+    ("archaeologist_gone", "Geopfert"),
+    ("archaeologist_return", "Wieder im Spiel"),
+    ("archaeologist_tally", "{} Aktivierungen: {} gewonnen, {} verloren"),
+    ("panel_unfinity", "Tickets und Sticker"),
+    ("tickets", "Tickets"),
+    ("ticket_gain", "Ticket erhalten"),
+    ("ticket_spend", "Ticket ausgeben"),
+    ("sticker_text", "Sticker"),
+    ("add_sticker", "Sticker hinzufügen"),
+    ("brainiac", "Brainiac"),
+    ("big_idea", "The Big Idea: W6 werfen"),
+    ("big_idea_boosted", "The Big Idea: 2W6 werfen"),
+    ("big_idea_boost", "{} Brainiacs tappen"),
+    ("d20_card", "W20-Karte"),
+    ("roll_d20", "W20 werfen"),
+    ("d20_event", "{}: {} gewürfelt, {}"),
+    ("roll_celebr", "Celebr-8000: 2W6 werfen"),
+    ("celebr_event", "Celebr-8000 würfelt {} und {}: {}"),
+    ("bonus_pump", "+1/+1"),
+    ("bonus_first_strike", "Erstschlag"),
+    ("bonus_vigilance", "Wachsamkeit"),
+    ("bonus_lifelink", "Lebensverknüpfung"),
+    ("bonus_indestructible", "Unzerstörbar"),
+    ("bonus_double_strike", "Doppelschlag"),
+    ("puzzlebox", "Vexing Puzzlebox"),
+    ("puzzlebox_charge", "{} von {} Ladungsmarken"),
+    ("puzzlebox_fetch", "Marken entfernen: Artefakt suchen"),
+    ("barbarian_class", "Barbarenklasse"),
+    ("not_in_play", "Nicht im Spiel"),
+    ("class_level", "Stufe {}"),
+    ("barbarian_level_1", "Einen Würfel mehr werfen und das niedrigste Ergebnis ignorieren"),
+    ("barbarian_level_2", "Immer wenn du würfelst, erhält eine Kreatur deiner Wahl +1/+0 und Bedrohlichkeit"),
+    ("barbarian_level_3", "Kreaturen, die du kontrollierst, haben Eile"),
+    ("barbarian_trigger", "Barbarenklasse: Kreatur erhält +1/+0 und Bedrohlichkeit"),
+    ("announce_roll", "{}: {}."),
+    (
+        "tour_flip",
+        "Wähle eine Karte und drücke Werfen, um Münzen für sie zu werfen. Jeder Wurf landet im Verlauf.",
+    ),
+    (
+        "tour_board",
+        "Lege fest, wie viele Krarks, Krarks Daumen und Mana-Belohnungen du kontrollierst.",
+    ),
+    (
+        "tour_stats",
+        "Die Statistikleiste zählt Sturm, Gewinne und Niederlagen. Klicke auf einen Balken, um zu diesem Wurf zu springen.",
+    ),
+];
@@ -1,9 +1,178 @@
 #![recursion_limit = "512"]
 
+// The engine: cards, flips, dice and simulation, with no browser code.
+pub mod archaeologist;
+pub mod attraction;
+pub mod card;
+pub mod cast;
+pub mod cleanup;
+pub mod clock;
+pub mod coin_flip;
+pub mod combat;
+pub mod creature;
+pub mod custom_card;
+pub mod day_night;
+pub mod deck;
+pub mod dice;
+pub mod effect;
+pub mod events;
+pub mod game;
+pub mod history;
+pub mod mulligan;
+pub mod opponent;
+pub mod pace;
+pub mod packed;
+pub mod persisted;
+pub mod probability;
+pub mod qr;
+pub mod records;
+pub mod risky_move;
+#[cfg(feature = "scripting")]
+pub mod script;
+pub mod seating;
+pub mod session;
+pub mod simulation;
+pub mod trigger;
+pub mod unfinity;
+
+// The Yew front end.
+#[cfg(feature = "app")]
 mod app;
-mod coin_flip;
+#[cfg(feature = "app")]
+mod archaeologist_panel;
+#[cfg(feature = "app")]
+mod attractions_panel;
+#[cfg(feature = "app")]
+mod battle_report;
+#[cfg(feature = "app")]
+mod bitmap_chart;
+#[cfg(feature = "app")]
+mod card_import;
+#[cfg(feature = "scryfall")]
+mod card_of_the_day;
+#[cfg(feature = "app")]
+mod celebration;
+#[cfg(feature = "app")]
+mod chart;
+#[cfg(feature = "networking")]
+mod chat;
+#[cfg(feature = "app")]
+mod coin_skin;
+#[cfg(feature = "app")]
+mod command_palette;
+#[cfg(feature = "app")]
+mod control_panel;
+#[cfg(feature = "app")]
+mod count_input;
+#[cfg(feature = "app")]
+mod counter;
+#[cfg(feature = "app")]
+mod creature_form;
+#[cfg(feature = "app")]
+mod creature_list;
+#[cfg(feature = "app")]
+mod custom_card_list;
+#[cfg(feature = "app")]
+mod d20_card;
+#[cfg(feature = "app")]
+mod deck_import;
+#[cfg(feature = "app")]
+mod dice_roller;
+#[cfg(feature = "app")]
+mod draw_odds;
+#[cfg(feature = "app")]
+mod export;
+#[cfg(feature = "app")]
+mod flip_log;
+#[cfg(feature = "app")]
+mod haptics;
+#[cfg(feature = "app")]
+mod histogram;
+#[cfg(feature = "app")]
+mod i18n;
+#[cfg(feature = "app")]
+mod mana_pool;
+#[cfg(feature = "app")]
+mod modal;
+#[cfg(feature = "app")]
+mod mulligan_trainer;
+#[cfg(feature = "app")]
+mod name_form;
+#[cfg(feature = "app")]
+mod neq_assign;
+#[cfg(feature = "app")]
+mod opponent_row;
+#[cfg(feature = "app")]
+mod perf;
+#[cfg(feature = "app")]
+mod qr_image;
+#[cfg(feature = "app")]
+mod records_table;
+#[cfg(feature = "app")]
+mod reducer_form;
+#[cfg(feature = "app")]
+mod reducer_list;
+#[cfg(feature = "app")]
+mod resolution_log;
+#[cfg(feature = "app")]
+mod risky_move_panel;
+#[cfg(feature = "app")]
+mod run_lengths;
+#[cfg(feature = "app")]
+mod script_editor;
+#[cfg(feature = "app")]
+mod seed_explorer;
+#[cfg(feature = "app")]
+mod seed_input;
+#[cfg(feature = "app")]
+mod session_dashboard;
+#[cfg(feature = "app")]
+mod session_import;
+#[cfg(feature = "app")]
+mod settings;
+#[cfg(feature = "app")]
+mod settings_panel;
+#[cfg(feature = "app")]
+mod simulation_worker;
+#[cfg(feature = "app")]
+mod simulator;
+#[cfg(feature = "app")]
+mod snapshot;
+#[cfg(feature = "app")]
+mod spectate;
+#[cfg(feature = "app")]
+mod speech;
+#[cfg(feature = "app")]
+mod spell_form;
+#[cfg(feature = "app")]
+mod spell_table;
+#[cfg(feature = "app")]
+mod stats_panel;
+#[cfg(feature = "app")]
+mod stats_tables;
+#[cfg(feature = "app")]
+mod theme;
+#[cfg(feature = "app")]
+mod token_list;
+#[cfg(feature = "app")]
+mod tour;
+#[cfg(feature = "app")]
+mod tracked_cards;
+#[cfg(feature = "app")]
+mod turn_timer;
+#[cfg(feature = "app")]
+mod unfinity_panel;
+#[cfg(feature = "app")]
+mod upkeep_flips;
+#[cfg(feature = "app")]
+mod voice;
 
+#[cfg(feature = "app")]
 use wasm_bindgen::prelude::*;
+#[cfg(feature = "app")]
+use wasm_bindgen::JsCast;
+#[cfg(feature = "app")]
+use yew::agent::Threaded;
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
@@ -12,9 +181,21 @@ use wasm_bindgen::prelude::*;
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 // This is the entry point for the web app
+#[cfg(feature = "app")]
 #[wasm_bindgen]
 pub fn run_app() -> Result<(), JsValue> {
     wasm_logger::init(wasm_logger::Config::default());
     yew::start_app::<app::App>();
     Ok(())
 }
+
+// This is the entry point for the simulation worker, run whenever the
+// module loads. On the page it does nothing; in the worker build it
+// registers the worker.
+#[cfg(feature = "app")]
+#[wasm_bindgen(start)]
+pub fn run_worker() {
+    if js_sys::global().dyn_into::<web_sys::Window>().is_err() {
+        simulation_worker::SimulationWorker::register();
+    }
+}
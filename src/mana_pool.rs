@@ -0,0 +1,97 @@
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// The pool's contents, or the declared infinite mana loop. Holds the
+/// loop note being typed until the loop is declared.
+pub struct ManaPool {
+    link: ComponentLink<Self>,
+    props: Props,
+    loop_note: String,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub mana: u32,
+    /// The note on the declared loop, if there is one.
+    pub infinite: Option<String>,
+    pub on_mana: Callback<u32>,
+    /// Receives the note when a loop is declared, and `None` when it ends.
+    pub on_infinite: Callback<Option<String>>,
+}
+
+pub enum Msg {
+    SetMana(String),
+    UpdateLoopNote(String),
+    Declare,
+    End,
+}
+
+impl Component for ManaPool {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        ManaPool {
+            link,
+            props,
+            loop_note: String::new(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::SetMana(val) => {
+                if let Ok(mana) = val.parse() {
+                    self.props.on_mana.emit(mana);
+                }
+                false
+            }
+            Msg::UpdateLoopNote(val) => self.loop_note.neq_assign(val),
+            Msg::Declare => {
+                let note = self.loop_note.trim().to_string();
+                self.loop_note.clear();
+                self.props.on_infinite.emit(Some(note));
+                true
+            }
+            Msg::End => {
+                self.props.on_infinite.emit(None);
+                false
+            }
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        match &self.props.infinite {
+            Some(note) => html! {
+                <div class="mana-pool infinite">
+                    <span>{ lang.t("mana_pool") }{ ": ∞" }</span>
+                    <span class="loop-note">{ note }</span>
+                    <button onclick=self.link.callback(|_| Msg::End)>{ lang.t("infinite_mana_end") }</button>
+                </div>
+            },
+            None => html! {
+                <div class="mana-pool">
+                    <label>
+                        { lang.t("mana_pool") }
+                        <input type="number" min="0"
+                               value=self.props.mana
+                               oninput=self.link.callback(|e: InputData| Msg::SetMana(e.value)) />
+                    </label>
+                    <input class="loop-note"
+                           placeholder=lang.t("loop_note")
+                           aria-label=lang.t("loop_note")
+                           value=&self.loop_note
+                           oninput=self.link.callback(|e: InputData| Msg::UpdateLoopNote(e.value)) />
+                    <button onclick=self.link.callback(|_| Msg::Declare)>{ lang.t("infinite_mana_declare") }</button>
+                </div>
+            },
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlElement;
+use yew::prelude::*;
+
+/// Elements that can take keyboard focus inside a dialog.
+const FOCUSABLE: &str = "button, [href], input, select, textarea, [tabindex]:not([tabindex='-1'])";
+
+/// A dialog that keeps keyboard focus inside itself while open and hands it
+/// back to the previously focused element when closed.
+pub struct Modal {
+    link: ComponentLink<Self>,
+    props: Props,
+    node: NodeRef,
+    previous_focus: Option<HtmlElement>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub title: String,
+    pub on_close: Callback<()>,
+    pub children: Children,
+}
+
+pub enum Msg {
+    KeyDown(KeyboardEvent),
+    Close,
+}
+
+impl Modal {
+    fn focusable(&self) -> Vec<HtmlElement> {
+        let list = match self
+            .node
+            .cast::<web_sys::Element>()
+            .and_then(|dialog| dialog.query_selector_all(FOCUSABLE).ok())
+        {
+            Some(list) => list,
+            None => return Vec::new(),
+        };
+        (0..list.length())
+            .filter_map(|i| list.item(i))
+            .filter_map(|node| node.dyn_into::<HtmlElement>().ok())
+            .collect()
+    }
+
+    /// Wraps Tab and Shift+Tab around the ends of the dialog.
+    fn trap_tab(&self, event: &KeyboardEvent) {
+        let focusable = self.focusable();
+        let (first, last) = match (focusable.first(), focusable.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return,
+        };
+        let active = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.active_element());
+        let at = |element: &HtmlElement| {
+            active
+                .as_ref()
+                .is_some_and(|active| active == element.unchecked_ref::<web_sys::Element>())
+        };
+        if event.shift_key() && at(first) {
+            event.prevent_default();
+            let _ = last.focus();
+        } else if !event.shift_key() && at(last) {
+            event.prevent_default();
+            let _ = first.focus();
+        }
+    }
+}
+
+impl Component for Modal {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let previous_focus = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.active_element())
+            .and_then(|element| element.dyn_into::<HtmlElement>().ok());
+        Modal {
+            link,
+            props,
+            node: NodeRef::default(),
+            previous_focus,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::KeyDown(event) => match event.key().as_str() {
+                "Escape" => self.props.on_close.emit(()),
+                "Tab" => self.trap_tab(&event),
+                _ => {}
+            },
+            Msg::Close => self.props.on_close.emit(()),
+        }
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            let autofocus = self
+                .node
+                .cast::<web_sys::Element>()
+                .and_then(|dialog| dialog.query_selector("[autofocus]").ok().flatten())
+                .and_then(|element| element.dyn_into::<HtmlElement>().ok());
+            if let Some(first) = autofocus.or_else(|| self.focusable().into_iter().next()) {
+                let _ = first.focus();
+            }
+        }
+    }
+
+    fn destroy(&mut self) {
+        if let Some(previous) = &self.previous_focus {
+            let _ = previous.focus();
+        }
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div class="modal-backdrop" onclick=self.link.callback(|_| Msg::Close)>
+                <div class="modal"
+                     role="dialog"
+                     aria-modal="true"
+                     aria-label=&self.props.title
+                     ref=self.node.clone()
+                     onclick=Callback::from(|e: MouseEvent| e.stop_propagation())
+                     onkeydown=self.link.callback(Msg::KeyDown)>
+                    <header>
+                        <h2>{ &self.props.title }</h2>
+                        <button class="close"
+                                aria-label=self.props.language.t("close")
+                                onclick=self.link.callback(|_| Msg::Close)>{ "×" }</button>
+                    </header>
+                    { self.props.children.clone() }
+                </div>
+            </div>
+        }
+    }
+}
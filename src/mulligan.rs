@@ -0,0 +1,239 @@
+//! Opening hands dealt from a decklist, and how often one is worth keeping
+//! under the London mulligan: draw seven, and after each mulligan put one
+//! more card on the bottom.
+
+use crate::deck::{parse_line, Decklist};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use strum_macros::AsRefStr;
+
+/// Cards in an opening hand before any go to the bottom.
+pub const HAND_SIZE: usize = 7;
+
+/// Why the pieces for a keepable hand were rejected. `as_ref` gives the
+/// error's text key.
+#[derive(AsRefStr, Clone, Debug, PartialEq)]
+pub enum PiecesError {
+    #[strum(serialize = "pieces_bad_count")]
+    BadCount,
+    /// The named card is not in the deck.
+    #[strum(serialize = "pieces_unknown_card")]
+    UnknownCard(String),
+    #[strum(serialize = "pieces_none")]
+    NoPieces,
+}
+
+/// At least `at_least` cards from `cards`, which index the deck's entries.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Requirement {
+    pub cards: Vec<usize>,
+    pub at_least: u32,
+}
+
+/// What a hand needs to be kept, such as Krark and one of the Thumbs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeepRule {
+    pub requirements: Vec<Requirement>,
+}
+
+impl KeepRule {
+    /// Reads one requirement per line, as a count and card names split by
+    /// `|`: `1 Krark's Thumb | Harmonic Prodigy`. A missing count means
+    /// one.
+    pub fn parse(text: &str, deck: &Decklist) -> Result<Self, PiecesError> {
+        let requirements = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (at_least, names) = parse_line(line).map_err(|_| PiecesError::BadCount)?;
+                let cards = names
+                    .split('|')
+                    .map(|name| {
+                        deck.find(name)
+                            .ok_or_else(|| PiecesError::UnknownCard(name.trim().to_string()))
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(Requirement { cards, at_least })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if requirements.is_empty() {
+            return Err(PiecesError::NoPieces);
+        }
+        Ok(KeepRule { requirements })
+    }
+
+    /// Whether `hand` has every piece, with room left for them once
+    /// `bottomed` cards go to the bottom. A card counts toward one
+    /// requirement only, so a Krark's Thumb asked for twice must be there
+    /// twice.
+    pub fn keeps(&self, hand: &[usize], bottomed: usize) -> bool {
+        // One slot per card a requirement asks for.
+        let slots: Vec<&Requirement> = self
+            .requirements
+            .iter()
+            .flat_map(|r| std::iter::repeat_n(r, r.at_least as usize))
+            .collect();
+        if slots.len() > hand.len().saturating_sub(bottomed) {
+            return false;
+        }
+        let mut filling = vec![None; hand.len()];
+        (0..slots.len()).all(|slot| {
+            fill(
+                slot,
+                &slots,
+                hand,
+                &mut filling,
+                &mut vec![false; hand.len()],
+            )
+        })
+    }
+}
+
+/// Finds a card in `hand` for `slot`, moving cards that fill other slots
+/// to other cards they can use if that frees one up. `filling[i]` is the
+/// slot card `i` fills, and `tried` marks the cards already looked at.
+fn fill(
+    slot: usize,
+    slots: &[&Requirement],
+    hand: &[usize],
+    filling: &mut [Option<usize>],
+    tried: &mut [bool],
+) -> bool {
+    for (idx, card) in hand.iter().enumerate() {
+        if tried[idx] || !slots[slot].cards.contains(card) {
+            continue;
+        }
+        tried[idx] = true;
+        let free = match filling[idx] {
+            Some(other) => fill(other, slots, hand, filling, tried),
+            None => true,
+        };
+        if free {
+            filling[idx] = Some(slot);
+            return true;
+        }
+    }
+    false
+}
+
+/// Deals a hand of seven from a shuffled `library`.
+pub fn deal<R: Rng>(library: &mut [usize], rng: &mut R) -> Vec<usize> {
+    let (hand, _) = library.partial_shuffle(rng, HAND_SIZE);
+    hand.to_vec()
+}
+
+/// How often a keepable hand came up over many trials.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MulliganReport {
+    pub trials: usize,
+    /// `kept[m]` counts trials kept after `m` mulligans.
+    pub kept: Vec<usize>,
+}
+
+impl MulliganReport {
+    /// Share of trials kept after at most `mulligans` mulligans.
+    pub fn kept_by(&self, mulligans: usize) -> f64 {
+        let kept = self.kept.iter().take(mulligans + 1).sum::<usize>();
+        kept as f64 / self.trials.max(1) as f64
+    }
+}
+
+/// Mulligans each trial up to `max_mulligans` times looking for a hand
+/// `rule` keeps. There is no hand left past seven mulligans.
+pub fn simulate<R: Rng>(
+    deck: &Decklist,
+    rule: &KeepRule,
+    trials: usize,
+    max_mulligans: usize,
+    rng: &mut R,
+) -> MulliganReport {
+    let max_mulligans = max_mulligans.min(HAND_SIZE);
+    let mut library = deck.library();
+    let mut kept = vec![0; max_mulligans + 1];
+    for _ in 0..trials {
+        if let Some(mulligans) =
+            (0..=max_mulligans).find(|&m| rule.keeps(&deal(&mut library, rng), m))
+        {
+            kept[mulligans] += 1;
+        }
+    }
+    MulliganReport { trials, kept }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    fn deck() -> Decklist {
+        Decklist::parse("1 Krark, the Thumbless\n2 Krark's Thumb\n1 Harmonic Prodigy\n56 Mountain")
+            .unwrap()
+    }
+
+    fn rule(text: &str) -> KeepRule {
+        KeepRule::parse(text, &deck()).unwrap()
+    }
+
+    #[test]
+    fn parses_one_requirement_per_line() {
+        let rule = rule("Krark, the Thumbless\n\n2 krark's thumb | Harmonic Prodigy");
+        assert_eq!(
+            rule.requirements,
+            vec![
+                Requirement {
+                    cards: vec![0],
+                    at_least: 1
+                },
+                Requirement {
+                    cards: vec![1, 2],
+                    at_least: 2
+                },
+            ]
+        );
+        let deck = deck();
+        assert_eq!(KeepRule::parse(" \n", &deck), Err(PiecesError::NoPieces));
+        assert_eq!(
+            KeepRule::parse("0 Mountain", &deck),
+            Err(PiecesError::BadCount)
+        );
+        assert_eq!(
+            KeepRule::parse("Krark's Thumb | Island", &deck),
+            Err(PiecesError::UnknownCard("Island".into()))
+        );
+    }
+
+    #[test]
+    fn counts_each_card_toward_one_requirement() {
+        let rule = rule("Krark's Thumb | Harmonic Prodigy\nKrark's Thumb");
+        // One Thumb can't be both pieces.
+        assert!(!rule.keeps(&[1, 3, 3, 3, 3, 3, 3], 0));
+        // The Prodigy fills the first, leaving the Thumb for the second.
+        assert!(rule.keeps(&[1, 2, 3, 3, 3, 3, 3], 0));
+        assert!(rule.keeps(&[1, 1, 3, 3, 3, 3, 3], 0));
+        assert!(!rule.keeps(&[2, 2, 3, 3, 3, 3, 3], 0));
+    }
+
+    #[test]
+    fn needs_room_for_the_pieces_after_bottoming() {
+        let rule = rule("Krark, the Thumbless\n2 Krark's Thumb");
+        let hand = [0, 1, 1, 3, 3, 3, 3];
+        assert!(rule.keeps(&hand, 4));
+        assert!(!rule.keeps(&hand, 5));
+    }
+
+    #[test]
+    fn simulates_a_rule_every_hand_keeps() {
+        let mut rng = ChaCha12Rng::seed_from_u64(3);
+        let report = simulate(&deck(), &rule("Mountain"), 100, 2, &mut rng);
+        assert_eq!(report.kept, vec![100, 0, 0]);
+        assert_eq!(report.kept_by(0), 1.0);
+
+        // Krark and both Thumbs are rarely all in one hand.
+        let rare = rule("Krark, the Thumbless\n2 Krark's Thumb");
+        let report = simulate(&deck(), &rare, 200, 7, &mut rng);
+        assert_eq!(report.kept.len(), HAND_SIZE + 1);
+        assert!(report.kept_by(0) < 0.05);
+        assert!(report.kept_by(0) < report.kept_by(7));
+    }
+}
@@ -0,0 +1,211 @@
+use crate::coin_flip::{parse_count, ConfigError};
+use crate::deck::Decklist;
+use crate::i18n::Language;
+use crate::mulligan::{deal, simulate, KeepRule, MulliganReport, PiecesError};
+use crate::neq_assign::NeqAssign;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use yew::prelude::*;
+
+/// Stream of the game's seed the trainer deals from, apart from the one
+/// its flips and rolls use.
+const MULLIGAN_STREAM: u64 = 1;
+
+/// Random numbers for dealing, from `seed` but not the same as the
+/// game's, so dealing does not change its flips.
+fn dealer(seed: u64) -> ChaCha12Rng {
+    let mut rng = ChaCha12Rng::seed_from_u64(seed);
+    rng.set_stream(MULLIGAN_STREAM);
+    rng
+}
+
+/// Deals opening hands from the imported decklist and reports how often
+/// one holds the pieces a Krark hand needs.
+pub struct MulliganTrainer {
+    link: ComponentLink<Self>,
+    props: Props,
+    pieces: String,
+    trials: String,
+    max_mulligans: String,
+    /// A hand dealt to look at, as indexes into the deck's entries.
+    hand: Vec<usize>,
+    report: Option<MulliganReport>,
+    pieces_error: Option<PiecesError>,
+    trials_error: Option<ConfigError>,
+    rng: ChaCha12Rng,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub deck: Decklist,
+    /// Seed of the game, which hands are dealt from.
+    pub seed: u64,
+}
+
+pub enum Msg {
+    UpdatePieces(String),
+    UpdateTrials(String),
+    UpdateMaxMulligans(String),
+    Deal,
+    Run,
+}
+
+impl MulliganTrainer {
+    fn view_hand(&self) -> Html {
+        let lang = self.props.language;
+        if self.hand.is_empty() {
+            return html! {};
+        }
+        let rule = KeepRule::parse(&self.pieces, &self.props.deck).ok();
+        let keep = if rule.is_some_and(|rule| rule.keeps(&self.hand, 0)) {
+            "mulligan_keep"
+        } else {
+            "mulligan_ship"
+        };
+        html! {
+            <div class="sample-hand">
+                <ul>
+                    { for self.hand.iter().map(|&card| html! {
+                        <li>{ &self.props.deck.cards[card].name }</li>
+                    }) }
+                </ul>
+                <p>{ lang.t(keep) }</p>
+            </div>
+        }
+    }
+
+    fn view_report(&self) -> Html {
+        let lang = self.props.language;
+        let report = match &self.report {
+            Some(report) => report,
+            None => return html! {},
+        };
+        html! {
+            <table class="mulligan-report">
+                <thead>
+                    <tr>
+                        <th>{ lang.t("mulligans") }</th>
+                        <th>{ lang.t("kept_by") }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for (0..report.kept.len()).map(|m| html! {
+                        <tr>
+                            <td>{ m }</td>
+                            <td>{ format!("{:.1}%", report.kept_by(m) * 100.0) }</td>
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+        }
+    }
+}
+
+impl Component for MulliganTrainer {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        MulliganTrainer {
+            link,
+            rng: dealer(props.seed),
+            props,
+            pieces: "1 Krark, the Thumbless".into(),
+            trials: "10000".into(),
+            max_mulligans: "2".into(),
+            hand: Vec::new(),
+            report: None,
+            pieces_error: None,
+            trials_error: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdatePieces(val) => return self.pieces.neq_assign(val),
+            Msg::UpdateTrials(val) => return self.trials.neq_assign(val),
+            Msg::UpdateMaxMulligans(val) => return self.max_mulligans.neq_assign(val),
+            Msg::Deal => {
+                let mut library = self.props.deck.library();
+                self.hand = deal(&mut library, &mut self.rng);
+            }
+            Msg::Run => {
+                let rule = match KeepRule::parse(&self.pieces, &self.props.deck) {
+                    Ok(rule) => rule,
+                    Err(error) => return self.pieces_error.neq_assign(Some(error)),
+                };
+                self.pieces_error = None;
+                let counts = parse_count(&self.trials)
+                    .and_then(|trials| Ok((trials, parse_count(&self.max_mulligans)?)));
+                let (trials, max_mulligans) = match counts {
+                    Ok(counts) => counts,
+                    Err(error) => return self.trials_error.neq_assign(Some(error)),
+                };
+                self.trials_error = None;
+                self.report = Some(simulate(
+                    &self.props.deck,
+                    &rule,
+                    trials,
+                    max_mulligans,
+                    &mut self.rng,
+                ));
+            }
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props.deck != props.deck {
+            self.hand.clear();
+            self.report = None;
+        }
+        if self.props.seed != props.seed {
+            self.rng = dealer(props.seed);
+        }
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <div class="mulligan-trainer">
+                <label>
+                    { lang.t("mulligan_pieces") }
+                    <textarea placeholder="1 Krark, the Thumbless\n1 Krark's Thumb | Harmonic Prodigy"
+                              value=&self.pieces
+                              aria-invalid=self.pieces_error.is_some().to_string()
+                              oninput=self.link.callback(|e: InputData| Msg::UpdatePieces(e.value)) />
+                </label>
+                { match &self.pieces_error {
+                    Some(PiecesError::UnknownCard(name)) => html! {
+                        <p class="error" role="alert">{ lang.fmt("pieces_unknown_card", &[name]) }</p>
+                    },
+                    Some(error) => html! { <p class="error" role="alert">{ lang.t(error.as_ref()) }</p> },
+                    None => html! {},
+                } }
+                <label>
+                    { lang.t("trials") }
+                    <input type="number" min="1"
+                           value=&self.trials
+                           aria-invalid=self.trials_error.is_some().to_string()
+                           oninput=self.link.callback(|e: InputData| Msg::UpdateTrials(e.value)) />
+                </label>
+                <label>
+                    { lang.t("max_mulligans") }
+                    <input type="number" min="0"
+                           value=&self.max_mulligans
+                           oninput=self.link.callback(|e: InputData| Msg::UpdateMaxMulligans(e.value)) />
+                </label>
+                { match self.trials_error {
+                    Some(error) => html! { <p class="error" role="alert">{ lang.t(error.as_ref()) }</p> },
+                    None => html! {},
+                } }
+                <button onclick=self.link.callback(|_| Msg::Run)>{ lang.t("run") }</button>
+                <button onclick=self.link.callback(|_| Msg::Deal)>{ lang.t("deal_hand") }</button>
+                { self.view_report() }
+                { self.view_hand() }
+            </div>
+        }
+    }
+}
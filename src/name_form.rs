@@ -0,0 +1,82 @@
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// A text box and a button that add something by name. Holds the text
+/// until it is submitted with the button or Enter, then hands it on
+/// trimmed and clears itself. Blank names are ignored.
+pub struct NameForm {
+    link: ComponentLink<Self>,
+    props: Props,
+    value: String,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    /// Placeholder and accessible name of the text box.
+    pub label: String,
+    pub button: String,
+    /// Class of the text box.
+    #[prop_or_default]
+    pub class: String,
+    /// Receives the trimmed name.
+    pub on_submit: Callback<String>,
+    /// Shown between the text box and the button.
+    #[prop_or_default]
+    pub children: Children,
+}
+
+pub enum Msg {
+    Update(String),
+    Submit,
+    Nope,
+}
+
+impl Component for NameForm {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        NameForm {
+            link,
+            props,
+            value: String::new(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Update(value) => self.value.neq_assign(value),
+            Msg::Submit => {
+                let name = self.value.trim();
+                if name.is_empty() {
+                    return false;
+                }
+                self.props.on_submit.emit(name.to_string());
+                self.value.clear();
+                true
+            }
+            Msg::Nope => false,
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <>
+                <input class=&self.props.class
+                       placeholder=&self.props.label
+                       aria-label=&self.props.label
+                       value=&self.value
+                       oninput=self.link.callback(|e: InputData| Msg::Update(e.value))
+                       onkeypress=self.link.callback(|e: KeyboardEvent| {
+                           if e.key() == "Enter" { Msg::Submit } else { Msg::Nope }
+                       }) />
+                { self.props.children.clone() }
+                <button onclick=self.link.callback(|_| Msg::Submit)>{ &self.props.button }</button>
+            </>
+        }
+    }
+}
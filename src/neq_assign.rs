@@ -0,0 +1,16 @@
+/// Assignment that reports whether anything changed, so `update` and
+/// `change` only ask for a render when the state actually moved.
+pub trait NeqAssign {
+    fn neq_assign(&mut self, new: Self) -> bool;
+}
+
+impl<T: PartialEq> NeqAssign for T {
+    fn neq_assign(&mut self, new: Self) -> bool {
+        if *self != new {
+            *self = new;
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use crate::cast::STARTING_LIFE;
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, Display, EnumIter, EnumString};
+
+/// Cards in a hand after drawing an opening seven.
+pub const OPENING_HAND: u32 = 7;
+
+/// Poison counters that make a player lose.
+pub const LETHAL_POISON: u32 = 10;
+
+/// Counters a player has, as opposed to their permanents.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayerCounters {
+    pub poison: u32,
+    pub energy: u32,
+    pub experience: u32,
+}
+
+/// Kinds of [`PlayerCounters`]. `as_ref` gives the kind's text key.
+#[derive(AsRefStr, Clone, Copy, Debug, EnumIter, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+pub enum PlayerCounter {
+    Poison,
+    Energy,
+    Experience,
+}
+
+/// Things that are true of one player until removed. Adding a variant is
+/// all it takes to offer a new one. `as_ref` gives its text key.
+#[derive(AsRefStr, Clone, Copy, Debug, EnumIter, PartialEq, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum Designation {
+    /// Also what Ascend grants.
+    CitysBlessing,
+    /// From Platinum Angel and the like.
+    CantLose,
+    /// From Leyline of Sanctity and the like.
+    PlayerHexproof,
+}
+
+/// Turns `designation` on or off in `designations`.
+pub fn toggle_designation(designations: &mut Vec<Designation>, designation: Designation) {
+    if designations.contains(&designation) {
+        designations.retain(|&d| d != designation);
+    } else {
+        designations.push(designation);
+    }
+}
+
+impl PlayerCounters {
+    pub fn get(&self, kind: PlayerCounter) -> u32 {
+        match kind {
+            PlayerCounter::Poison => self.poison,
+            PlayerCounter::Energy => self.energy,
+            PlayerCounter::Experience => self.experience,
+        }
+    }
+
+    /// Changes a count by `delta`, stopping at zero.
+    pub fn adjust(&mut self, kind: PlayerCounter, delta: i32) {
+        let count = match kind {
+            PlayerCounter::Poison => &mut self.poison,
+            PlayerCounter::Energy => &mut self.energy,
+            PlayerCounter::Experience => &mut self.experience,
+        };
+        *count = (*count as i32 + delta).max(0) as u32;
+    }
+
+    pub fn poisoned(&self) -> bool {
+        self.poison >= LETHAL_POISON
+    }
+}
+
+/// A player across the table whose life total is tracked.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Opponent {
+    pub name: String,
+    pub life: i32,
+    /// Library size the opponent declared, if known.
+    pub library: Option<u32>,
+    pub milled: u32,
+    /// Cards in hand.
+    pub hand: u32,
+    /// Ping damage dealt this turn and not yet applied to `life`.
+    pub pending_damage: u32,
+    #[serde(default)]
+    pub counters: PlayerCounters,
+    #[serde(default)]
+    pub designations: Vec<Designation>,
+}
+
+impl Opponent {
+    pub fn new(name: String) -> Self {
+        Opponent {
+            name,
+            life: STARTING_LIFE,
+            library: None,
+            milled: 0,
+            hand: OPENING_HAND,
+            pending_damage: 0,
+            counters: PlayerCounters::default(),
+            designations: Vec::new(),
+        }
+    }
+
+    /// Cards left in the library after milling, if its size is known.
+    pub fn library_left(&self) -> Option<u32> {
+        self.library.map(|size| size.saturating_sub(self.milled))
+    }
+
+    pub fn decked(&self) -> bool {
+        self.library_left() == Some(0)
+    }
+
+    /// Moves pending ping damage onto the life total.
+    pub fn apply_pending_damage(&mut self) {
+        self.life -= self.pending_damage as i32;
+        self.pending_damage = 0;
+    }
+}
+
+/// Spells that make every player discard their hand and draw a new one.
+/// Display gives the card name.
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Wheel {
+    #[strum(serialize = "Wheel of Fortune")]
+    WheelOfFortune,
+    #[strum(serialize = "Windfall")]
+    Windfall,
+}
+
+impl Wheel {
+    /// Hand size every player ends up with, given all hands before the
+    /// wheel.
+    pub fn refill(self, hands: &[u32]) -> u32 {
+        match self {
+            Wheel::WheelOfFortune => OPENING_HAND,
+            Wheel::Windfall => hands.iter().copied().max().unwrap_or(0),
+        }
+    }
+}
+
+/// Storm spells that finish the game by damage or life loss.
+#[derive(
+    Clone, Copy, Debug, Display, EnumIter, EnumString, Eq, PartialEq, Serialize, Deserialize,
+)]
+pub enum StormPayoff {
+    #[strum(serialize = "Grapeshot")]
+    Grapeshot,
+    #[strum(serialize = "Tendrils of Agony")]
+    Tendrils,
+}
+
+impl StormPayoff {
+    /// Damage or life loss dealt by the original spell or one copy.
+    pub fn per_copy(self) -> u32 {
+        match self {
+            StormPayoff::Grapeshot => 1,
+            StormPayoff::Tendrils => 2,
+        }
+    }
+
+    /// Total dealt when the payoff is cast after `storm` other spells this
+    /// turn: the original plus one storm copy per earlier spell.
+    pub fn total(self, storm: u32) -> u32 {
+        self.per_copy() * (storm + 1)
+    }
+
+    /// Whether casting the payoff now finishes `opponent` on its own. A
+    /// `storm` of `None` stands for an unbounded storm count.
+    pub fn is_lethal(self, storm: Option<u32>, opponent: &Opponent) -> bool {
+        storm.is_none_or(|storm| self.total(storm) as i32 >= opponent.life)
+    }
+}
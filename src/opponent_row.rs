@@ -0,0 +1,116 @@
+use crate::coin_skin::outcome_icon;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::opponent::Opponent;
+use yew::prelude::*;
+
+/// One opponent's life total and library, with badges for what they hold
+/// and whether the storm payoff would finish them. Edits hand back the
+/// whole opponent.
+pub struct OpponentRow {
+    link: ComponentLink<Self>,
+    props: Props,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    /// Position among the opponents, handed back with every change.
+    pub idx: usize,
+    pub opponent: Opponent,
+    pub targeted: bool,
+    pub lethal: bool,
+    /// Text keys of the designations they hold.
+    pub held: Vec<String>,
+    pub on_change: Callback<(usize, Opponent)>,
+    pub on_target: Callback<usize>,
+    pub on_remove: Callback<usize>,
+}
+
+pub enum Msg {
+    SetLife(String),
+    SetLibrary(String),
+}
+
+impl Component for OpponentRow {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        OpponentRow { link, props }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let mut opponent = self.props.opponent.clone();
+        match msg {
+            Msg::SetLife(val) => match val.parse() {
+                Ok(life) => opponent.life = life,
+                Err(_) => return false,
+            },
+            Msg::SetLibrary(val) => opponent.library = val.parse().ok(),
+        }
+        self.props.on_change.emit((self.props.idx, opponent));
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        let idx = self.props.idx;
+        let opponent = &self.props.opponent;
+        let library = opponent
+            .library
+            .map(|size| size.to_string())
+            .unwrap_or_default();
+        html! {
+            <li class=if self.props.lethal { "lethal" } else { "" }>
+                <input type="radio" name="target"
+                       aria-label=lang.fmt("target_opponent", &[&opponent.name])
+                       checked=self.props.targeted
+                       onclick=self.props.on_target.reform(move |_| idx) />
+                <label>
+                    { &opponent.name }
+                    <input type="number"
+                           value=opponent.life
+                           oninput=self.link.callback(|e: InputData| Msg::SetLife(e.value)) />
+                </label>
+                <label>
+                    { lang.t("library") }
+                    <input type="number" min="0"
+                           value=library
+                           oninput=self.link.callback(|e: InputData| Msg::SetLibrary(e.value)) />
+                </label>
+                <span class="milled">{ lang.fmt("milled", &[&opponent.milled]) }</span>
+                { for self.props.held.iter().map(|key| html! {
+                    <span class="designation-badge">{ lang.t(key) }</span>
+                }) }
+                { if opponent.pending_damage > 0 {
+                    html! { <span class="pending-damage">{ lang.fmt("pending_damage", &[&opponent.pending_damage]) }</span> }
+                } else {
+                    html! {}
+                } }
+                { if opponent.counters.poisoned() {
+                    html! { <span class="decked-badge">{ outcome_icon(true) }{ lang.t("poisoned") }</span> }
+                } else {
+                    html! {}
+                } }
+                { if opponent.decked() {
+                    html! { <span class="decked-badge">{ outcome_icon(true) }{ lang.t("decked") }</span> }
+                } else {
+                    html! {}
+                } }
+                { if self.props.lethal {
+                    html! { <span class="lethal-badge">{ outcome_icon(true) }{ lang.t("lethal") }</span> }
+                } else {
+                    html! {}
+                } }
+                <button class="destroy"
+                        aria-label=lang.t("remove")
+                        onclick=self.props.on_remove.reform(move |_| idx)>{ "×" }</button>
+            </li>
+        }
+    }
+}
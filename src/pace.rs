@@ -0,0 +1,99 @@
+//! How long a session has run and how long each player's turns take,
+//! from wall-clock times in milliseconds as `Date.now()` gives them.
+
+use serde_derive::{Deserialize, Serialize};
+
+/// Milliseconds in a minute.
+const MINUTE: f64 = 60_000.0;
+
+/// One finished turn. Players are numbered 0 for you, then the opponents
+/// in seating order.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TurnTime {
+    pub player: usize,
+    pub millis: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pace {
+    /// When the session's first flip or turn happened.
+    pub started: Option<f64>,
+    /// When the turn under way began.
+    pub turn_started: Option<f64>,
+    pub turns: Vec<TurnTime>,
+}
+
+impl Pace {
+    /// Starts the clock at `now`, unless it is already running.
+    pub fn begin(&mut self, now: f64) {
+        self.started.get_or_insert(now);
+        self.turn_started.get_or_insert(now);
+    }
+
+    /// Ends `player`'s turn at `now` and starts the next one.
+    pub fn end_turn(&mut self, player: usize, now: f64) {
+        self.begin(now);
+        let began = self.turn_started.replace(now).unwrap_or(now);
+        self.turns.push(TurnTime {
+            player,
+            millis: (now - began).max(0.0) as u64,
+        });
+    }
+
+    /// Milliseconds since the session began.
+    pub fn elapsed(&self, now: f64) -> u64 {
+        self.started
+            .map_or(0, |started| (now - started).max(0.0) as u64)
+    }
+
+    pub fn flips_per_minute(&self, flips: usize, now: f64) -> f64 {
+        match self.elapsed(now) {
+            0 => 0.0,
+            millis => flips as f64 * MINUTE / millis as f64,
+        }
+    }
+
+    /// Average length of every finished turn.
+    pub fn average_turn(&self) -> Option<u64> {
+        average(self.turns.iter().map(|turn| turn.millis))
+    }
+
+    /// Average turn length for each player with a finished turn, slowest
+    /// first.
+    pub fn by_player(&self) -> Vec<(usize, u64)> {
+        let mut players = self
+            .turns
+            .iter()
+            .map(|turn| turn.player)
+            .collect::<Vec<_>>();
+        players.sort_unstable();
+        players.dedup();
+        let mut averages = players
+            .into_iter()
+            .filter_map(|player| {
+                let turns = self.turns.iter().filter(|turn| turn.player == player);
+                Some((player, average(turns.map(|turn| turn.millis))?))
+            })
+            .collect::<Vec<_>>();
+        averages.sort_by_key(|&(_, millis)| std::cmp::Reverse(millis));
+        averages
+    }
+
+    /// Keeps player numbers pointing at the same players after seats
+    /// change, as `renumber` maps them. Turns of players who left are
+    /// forgotten.
+    pub fn renumber(&mut self, renumber: impl Fn(usize) -> Option<usize>) {
+        self.turns.retain_mut(|turn| match renumber(turn.player) {
+            Some(player) => {
+                turn.player = player;
+                true
+            }
+            None => false,
+        });
+    }
+}
+
+fn average(millis: impl Iterator<Item = u64>) -> Option<u64> {
+    let (count, total) = millis.fold((0, 0), |(count, total), m| (count + 1, total + m));
+    total.checked_div(count)
+}
@@ -0,0 +1,155 @@
+//! A compact storage format: bincode wrapped in base64, since
+//! localStorage only holds strings. Packed text starts with the layout it
+//! was written in, so values written before a type changed can still be
+//! read.
+
+use serde::de::{DeserializeOwned, Deserializer, Error};
+use serde::{Deserialize, Serialize, Serializer};
+#[cfg(feature = "app")]
+use yew::format::Text;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// A type that can be packed. Bincode keeps no field names and ignores
+/// `#[serde(default)]`, so every change to the fields of the type, or of
+/// anything in it, needs a new `LAYOUT` and `upgrade` reading the old one.
+pub trait Layout: Serialize + DeserializeOwned {
+    /// The layout this build writes, counting from 1.
+    const LAYOUT: u32;
+
+    /// Reads `bytes` written in an older `layout`, or gives `None` for a
+    /// layout that can no longer be read.
+    fn upgrade(_layout: u32, _bytes: &[u8]) -> Option<Self> {
+        None
+    }
+}
+
+/// `value` as text, marked with its layout.
+pub fn pack<T: Layout>(value: &T) -> Option<String> {
+    let bytes = bincode::serialize(value).ok()?;
+    Some(format!("{}.{}", T::LAYOUT, encode(&bytes)))
+}
+
+/// Reads text written by [`pack`], in this layout or an older one.
+pub fn unpack<T: Layout>(text: &str) -> Option<T> {
+    match text.split_once('.') {
+        Some((layout, data)) => read(layout.parse().ok()?, &decode(data)?),
+        // Packed before layouts were marked, so in any of them.
+        None => {
+            let bytes = decode(text)?;
+            (1..=T::LAYOUT)
+                .rev()
+                .find_map(|layout| read(layout, &bytes))
+        }
+    }
+}
+
+fn read<T: Layout>(layout: u32, bytes: &[u8]) -> Option<T> {
+    if layout == T::LAYOUT {
+        exact(bytes)
+    } else if layout < T::LAYOUT {
+        T::upgrade(layout, bytes)
+    } else {
+        None
+    }
+}
+
+/// Decodes `bytes` if they hold a `T` and nothing else. Bincode stops
+/// reading once it has a value, so without the check a layout with fewer
+/// fields could decode from bytes written with more.
+pub fn exact<T: Serialize + DeserializeOwned>(bytes: &[u8]) -> Option<T> {
+    let value = bincode::deserialize(bytes).ok()?;
+    match bincode::serialize(&value) {
+        Ok(written) if written == bytes => Some(value),
+        _ => None,
+    }
+}
+
+/// Packs a field of a JSON save, for `#[serde(with = "packed::field")]`.
+/// Values saved before the field was packed are read as they were.
+pub mod field {
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Stored<T> {
+        Packed(String),
+        Plain(T),
+    }
+
+    pub fn serialize<T: Layout, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let text = pack(value).ok_or_else(|| serde::ser::Error::custom("unpackable value"))?;
+        serializer.serialize_str(&text)
+    }
+
+    pub fn deserialize<'de, T: Layout, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        match Stored::deserialize(deserializer)? {
+            Stored::Packed(text) => {
+                unpack(&text).ok_or_else(|| D::Error::custom("unreadable packed value"))
+            }
+            Stored::Plain(value) => Ok(value),
+        }
+    }
+}
+
+/// Wraps data for `StorageService` like `Json` does, at a fraction of the
+/// size. Restoring gives `None` for anything that does not decode.
+#[cfg(feature = "app")]
+pub struct Packed<T>(pub T);
+
+#[cfg(feature = "app")]
+impl<T: Layout> From<Packed<&T>> for Text {
+    fn from(Packed(value): Packed<&T>) -> Text {
+        let error = || std::io::Error::new(std::io::ErrorKind::InvalidData, "unpackable value");
+        pack(value).ok_or_else(|| error().into())
+    }
+}
+
+#[cfg(feature = "app")]
+impl<T: Layout> From<Text> for Packed<Option<T>> {
+    fn from(text: Text) -> Self {
+        Packed(text.ok().and_then(|text| unpack(&text)))
+    }
+}
+
+fn encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &byte)| n | u32::from(byte) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+fn decode(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=').as_bytes();
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    for chunk in text.chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let mut n = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            let value = ALPHABET.iter().position(|a| a == c)? as u32;
+            n |= value << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            bytes.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(bytes)
+}
@@ -0,0 +1,94 @@
+//! `performance.mark`/`measure` around the app's busy paths, with running
+//! totals for the debug panel. Measures show up in the browser's profiler
+//! too.
+
+use std::cell::RefCell;
+use wasm_bindgen::JsCast;
+use web_sys::{Performance, PerformanceEntry};
+
+/// Running totals of one measure, in milliseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Timing {
+    pub count: u32,
+    pub last: f64,
+    pub total: f64,
+    pub max: f64,
+}
+
+impl Timing {
+    pub fn mean(&self) -> f64 {
+        self.total / f64::from(self.count.max(1))
+    }
+}
+
+thread_local! {
+    static TIMINGS: RefCell<Vec<(&'static str, Timing)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn performance() -> Option<Performance> {
+    web_sys::window()?.performance()
+}
+
+/// Measures from its creation until it is dropped.
+pub struct Span {
+    name: &'static str,
+}
+
+/// Starts measuring `name`.
+pub fn span(name: &'static str) -> Span {
+    if let Some(performance) = performance() {
+        let _ = performance.mark(&format!("{}:start", name));
+    }
+    Span { name }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let performance = match performance() {
+            Some(performance) => performance,
+            None => return,
+        };
+        let (start, end) = (format!("{}:start", self.name), format!("{}:end", self.name));
+        let _ = performance.mark(&end);
+        let _ = performance.measure_with_start_mark_and_end_mark(self.name, &start, &end);
+        let duration = performance
+            .get_entries_by_name(self.name)
+            .pop()
+            .dyn_into::<PerformanceEntry>()
+            .map(|entry| entry.duration());
+        // Only the totals are kept, so the browser's buffer does not grow.
+        performance.clear_marks_with_mark_name(&start);
+        performance.clear_marks_with_mark_name(&end);
+        performance.clear_measures_with_measure_name(self.name);
+        if let Ok(duration) = duration {
+            record(self.name, duration);
+        }
+    }
+}
+
+fn record(name: &'static str, duration: f64) {
+    TIMINGS.with(|timings| {
+        let mut timings = timings.borrow_mut();
+        let idx = match timings.iter().position(|(n, _)| *n == name) {
+            Some(idx) => idx,
+            None => {
+                timings.push((name, Timing::default()));
+                timings.len() - 1
+            }
+        };
+        let timing = &mut timings[idx].1;
+        timing.count += 1;
+        timing.last = duration;
+        timing.total += duration;
+        timing.max = timing.max.max(duration);
+    });
+}
+
+/// Totals of every measure taken so far, in the order first seen.
+pub fn timings() -> Vec<(&'static str, Timing)> {
+    TIMINGS.with(|timings| timings.borrow().clone())
+}
+
+pub fn reset() {
+    TIMINGS.with(|timings| timings.borrow_mut().clear());
+}
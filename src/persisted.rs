@@ -0,0 +1,187 @@
+//! What is kept between visits: every open game and the summaries of past
+//! sessions. Each save carries the version that wrote it, so older saves
+//! are brought up to date rather than dropped.
+
+use crate::cast::Spell;
+use crate::game::Game;
+use crate::history::FlipRecord;
+use crate::packed;
+use crate::session::SessionSummary;
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::AsRefStr;
+
+/// Version this build writes. Fields added with `#[serde(default)]` need
+/// no new version; anything `migrate` has to rewrite does. Packed fields
+/// have layouts of their own instead, see [`packed::Layout`].
+pub const STATE_VERSION: u32 = 3;
+
+/// Why a stored state was not loaded. `as_ref` gives its text key.
+#[derive(AsRefStr, Clone, Copy, Debug, PartialEq)]
+pub enum StateError {
+    /// Written by a newer build than this one.
+    #[strum(serialize = "state_too_new")]
+    TooNew,
+    #[strum(serialize = "state_unreadable")]
+    Unreadable,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PersistedState {
+    pub version: u32,
+    /// The board, spells and flips of the game in front, as versions 1
+    /// and 2 kept it. Version 3 keeps whole games instead.
+    #[serde(default, skip_serializing)]
+    pub krarks: usize,
+    #[serde(default, skip_serializing)]
+    pub thumbs: usize,
+    #[serde(default, skip_serializing)]
+    pub spells: Vec<Spell>,
+    #[serde(default, skip_serializing, with = "packed::field")]
+    pub history: Vec<FlipRecord>,
+    /// Packed from version 2 on; version 1 wrote it as JSON.
+    #[serde(default, with = "packed::field")]
+    pub sessions: Vec<SessionSummary>,
+    /// Every open game in tab order, packed.
+    #[serde(default, with = "packed::field")]
+    pub games: Vec<Game>,
+    /// Index into `games` of the game in front.
+    #[serde(default)]
+    pub active_game: usize,
+}
+
+impl PersistedState {
+    /// The state to store for `games` and `sessions`, with `games[active]`
+    /// in front.
+    pub fn capture(games: Vec<Game>, active_game: usize, sessions: &[SessionSummary]) -> Self {
+        PersistedState {
+            version: STATE_VERSION,
+            krarks: 0,
+            thumbs: 0,
+            spells: Vec::new(),
+            history: Vec::new(),
+            sessions: sessions.to_vec(),
+            games,
+            active_game,
+        }
+    }
+
+    /// Brings a stored state up to `STATE_VERSION`.
+    pub fn migrate(mut self) -> Result<Self, StateError> {
+        if self.version > STATE_VERSION {
+            return Err(StateError::TooNew);
+        }
+        // Version 2 differs from 1 only in packing sessions, which
+        // reading takes care of. Version 3 moved the game in front into
+        // a list of every game.
+        if self.version < 3 {
+            let mut game = Game::default();
+            game.board.krarks = self.krarks;
+            game.board.thumbs = self.thumbs;
+            game.spells = std::mem::take(&mut self.spells);
+            // Spells saved then did not say whether they have storm.
+            for spell in &mut game.spells {
+                spell.storm = Spell::has_storm_by_name(&spell.name);
+            }
+            game.history = std::mem::take(&mut self.history);
+            game.activations = game
+                .history
+                .iter()
+                .map(|record| record.activation + 1)
+                .max()
+                .unwrap_or(0);
+            self.games = vec![game];
+            self.active_game = 0;
+        }
+        if self.games.is_empty() {
+            self.games.push(Game::default());
+        }
+        if self.active_game >= self.games.len() {
+            self.active_game = 0;
+        }
+        Ok(PersistedState {
+            version: STATE_VERSION,
+            ..self
+        })
+    }
+
+    /// The game that was in front, if any game was stored.
+    pub fn active(&self) -> Option<&Game> {
+        self.games.get(self.active_game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::FlipCard;
+    use crate::coin_flip::{FlipResult, WinCondition};
+    use crate::opponent::Opponent;
+    use crate::packed::{pack, unpack};
+
+    fn record(activation: usize, result: FlipResult) -> FlipRecord {
+        FlipRecord {
+            source: FlipCard::Krark,
+            activation,
+            thumbs: 1,
+            player: None,
+            win: WinCondition::YouCall,
+            result,
+        }
+    }
+
+    #[test]
+    fn moves_the_game_in_front_of_older_saves_into_games() {
+        let old = PersistedState {
+            version: 2,
+            krarks: 3,
+            thumbs: 1,
+            spells: vec![Spell {
+                name: "Brain Freeze".into(),
+                mana_value: 2,
+                kind: Default::default(),
+                draws: 0,
+                storm: false,
+            }],
+            history: vec![record(0, FlipResult::Both), record(4, FlipResult::Tails)],
+            sessions: Vec::new(),
+            games: Vec::new(),
+            active_game: 0,
+        };
+        let state = old.migrate().unwrap();
+        assert_eq!(state.version, STATE_VERSION);
+        let game = state.active().unwrap();
+        assert_eq!(state.games.len(), 1);
+        assert_eq!((game.board.krarks, game.board.thumbs), (3, 1));
+        assert_eq!(game.history.len(), 2);
+        assert_eq!(game.activations, 5);
+        assert!(game.spells[0].storm);
+    }
+
+    #[test]
+    fn keeps_every_game_of_a_current_save() {
+        let mut first = Game::new("First".into());
+        first.board.krarks = 2;
+        first.history.push(record(0, FlipResult::Heads));
+        first.opponents.push(Opponent::new("Rowan".into()));
+        first.counters.poison = 4;
+        let second = Game::new("Second".into());
+        let state = PersistedState::capture(vec![first, second], 1, &[])
+            .migrate()
+            .unwrap();
+        assert_eq!(state.active().unwrap().name, "Second");
+
+        let games = unpack::<Vec<Game>>(&pack(&state.games).unwrap()).unwrap();
+        assert_eq!(games[0].board.krarks, 2);
+        assert_eq!(games[0].history, state.games[0].history);
+        assert_eq!(games[0].opponents[0].name, "Rowan");
+        assert_eq!(games[0].counters.poison, 4);
+        assert_eq!(games[1].name, "Second");
+    }
+
+    #[test]
+    fn refuses_saves_from_newer_builds() {
+        let mut state = PersistedState::capture(vec![Game::default()], 0, &[]);
+        state.version = STATE_VERSION + 1;
+        assert!(matches!(state.migrate(), Err(StateError::TooNew)));
+    }
+}
@@ -0,0 +1,174 @@
+use crate::card::FlipCard;
+use crate::coin_flip::Coin;
+use crate::history::FlipRecord;
+use serde_derive::{Deserialize, Serialize};
+
+/// Chance of exactly `k` wins from `n` flips won with probability `p`.
+pub fn binomial(n: usize, k: usize, p: f64) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    let choose = (0..k.min(n - k)).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64);
+    choose * p.powi(k as i32) * (1.0 - p).powi((n - k) as i32)
+}
+
+/// Chance of drawing at least one of `copies` cards in `draws` draws from
+/// a library of `deck` cards, or `None` when the library is too small for
+/// the copies or the draws.
+pub fn hit_at_least_one(deck: usize, copies: usize, draws: usize) -> Option<f64> {
+    if copies > deck || draws > deck {
+        return None;
+    }
+    let miss = (0..draws).fold(1.0, |acc, i| {
+        acc * (deck - copies).saturating_sub(i) as f64 / (deck - i) as f64
+    });
+    Some(1.0 - miss)
+}
+
+/// Exact odds for one cast with `krarks` triggers, each flipping a coin
+/// with `thumbs` Krark's Thumbs out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CastOdds {
+    /// Chance of winning a single flip.
+    pub win: f64,
+    pub expected_copies: f64,
+    pub at_least_one_copy: f64,
+    /// Chance that some trigger loses its flip and returns the spell.
+    pub bounce: f64,
+}
+
+impl CastOdds {
+    pub fn new(krarks: usize, thumbs: usize) -> Self {
+        let win = Coin::win_chance(thumbs);
+        CastOdds {
+            win,
+            expected_copies: krarks as f64 * win,
+            at_least_one_copy: 1.0 - (1.0 - win).powi(krarks as i32),
+            bounce: 1.0 - win.powi(krarks as i32),
+        }
+    }
+
+    /// Times a spell is cast on average when each bounce is recast, at
+    /// most `max_recasts` times.
+    pub fn expected_casts(&self, max_recasts: usize) -> f64 {
+        (0..=max_recasts).map(|i| self.bounce.powi(i as i32)).sum()
+    }
+}
+
+/// How likely a completed chain of flips was under the thumbs it was made with.
+#[derive(Serialize, Deserialize)]
+pub struct ChainOdds {
+    pub source: FlipCard,
+    pub flips: usize,
+    pub wins: usize,
+    /// Chance of this exact sequence of wins and losses.
+    pub exact: f64,
+    /// Chance of a result at least this far from the expected number of wins,
+    /// on the same side.
+    pub at_least: f64,
+}
+
+impl ChainOdds {
+    /// Computes the odds for the flips of a single activation.
+    pub fn new(chain: &[&FlipRecord]) -> Option<Self> {
+        let first = chain.first()?;
+        let p = Coin::win_chance(first.thumbs);
+        let flips = chain.len();
+        let wins = chain.iter().filter(|r| r.is_win()).count();
+        let exact = p.powi(wins as i32) * (1.0 - p).powi((flips - wins) as i32);
+        let at_least = match first.source.flips() {
+            // Chains that stop at the first loss: at least this many wins
+            // before losing.
+            None => p.powi(wins as i32),
+            Some(_) if wins as f64 >= flips as f64 * p => {
+                (wins..=flips).map(|k| binomial(flips, k, p)).sum()
+            }
+            Some(_) => (0..=wins).map(|k| binomial(flips, k, p)).sum(),
+        };
+        Some(ChainOdds {
+            source: first.source,
+            flips,
+            wins,
+            exact,
+            at_least,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::{simulate, Scenario};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    #[test]
+    fn binomial_sums_to_one() {
+        let total = (0..=10).map(|k| binomial(10, k, 0.75)).sum::<f64>();
+        assert!(close(total, 1.0));
+        assert!(close(binomial(4, 2, 0.5), 0.375));
+        assert_eq!(binomial(3, 4, 0.5), 0.0);
+    }
+
+    #[test]
+    fn hit_at_least_one_needs_a_big_enough_library() {
+        assert_eq!(hit_at_least_one(10, 11, 1), None);
+        assert_eq!(hit_at_least_one(10, 1, 11), None);
+        assert!(close(hit_at_least_one(60, 4, 1).unwrap(), 4.0 / 60.0));
+        assert!(close(hit_at_least_one(10, 10, 1).unwrap(), 1.0));
+        assert!(close(hit_at_least_one(10, 0, 7).unwrap(), 0.0));
+    }
+
+    #[test]
+    fn cast_odds_for_known_boards() {
+        let none = CastOdds::new(0, 0);
+        assert_eq!(none.expected_copies, 0.0);
+        assert_eq!(none.bounce, 0.0);
+        assert!(close(none.expected_casts(20), 1.0));
+        let one = CastOdds::new(1, 1);
+        assert!(close(one.win, 0.75));
+        assert!(close(one.at_least_one_copy, 0.75));
+        assert!(close(one.bounce, 0.25));
+        assert!(close(one.expected_casts(1), 1.25));
+    }
+
+    #[test]
+    fn cast_odds_match_the_simulator() {
+        let mut rng = ChaCha12Rng::seed_from_u64(509);
+        for &(krarks, thumbs) in &[(1, 0), (3, 0), (2, 1), (5, 1), (4, 2), (8, 3)] {
+            let max_recasts = 5;
+            let scenario = Scenario {
+                krarks,
+                thumbs,
+                max_recasts,
+            };
+            let report = simulate(&scenario, 50_000, &mut rng);
+            let odds = CastOdds::new(krarks, thumbs);
+            let casts = odds.expected_casts(max_recasts);
+            let pairs = [
+                (report.copies_per_cast(), odds.expected_copies),
+                (report.copy_rate, odds.at_least_one_copy),
+                (report.bounce_rate, odds.bounce),
+                (report.heads_share, odds.win),
+                (report.casts.mean, casts),
+                (report.copies.mean, odds.expected_copies * casts),
+            ];
+            for (simulated, exact) in pairs.iter() {
+                // Within 2%, or 0.01 for odds near zero.
+                let tolerance = (exact.abs() * 0.02).max(0.01);
+                assert!(
+                    (simulated - exact).abs() < tolerance,
+                    "{} Krarks, {} thumbs: simulated {} against {}",
+                    krarks,
+                    thumbs,
+                    simulated,
+                    exact
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,502 @@
+//! QR codes for share links, so tablemates can scan them off the screen.
+//! Data is encoded as bytes at error correction level L, in the smallest
+//! version it fits.
+
+/// Version 40 is the largest there is.
+const MAX_VERSION: usize = 40;
+
+/// Error correction codewords per block at level L, by version.
+const ECC_PER_BLOCK: [usize; MAX_VERSION] = [
+    7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30, 30,
+    26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+];
+
+/// Error correction blocks at level L, by version.
+const BLOCKS: [usize; MAX_VERSION] = [
+    1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13, 14,
+    15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25,
+];
+
+/// Level L's two format bits.
+const FORMAT_BITS_L: u32 = 1;
+
+/// Light modules left around the code so scanners can find its edge.
+pub const QUIET_ZONE: usize = 4;
+
+pub struct QrCode {
+    size: usize,
+    /// Dark modules, row by row.
+    modules: Vec<bool>,
+    /// Finder, timing, alignment and format modules, which masks skip.
+    function: Vec<bool>,
+}
+
+/// Modules left for data and error correction once the function patterns
+/// are drawn.
+fn raw_data_modules(version: usize) -> usize {
+    let mut modules = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let alignments = version / 7 + 2;
+        modules -= (25 * alignments - 10) * alignments - 55;
+        if version >= 7 {
+            modules -= 36;
+        }
+    }
+    modules
+}
+
+fn data_codewords(version: usize) -> usize {
+    raw_data_modules(version) / 8 - ECC_PER_BLOCK[version - 1] * BLOCKS[version - 1]
+}
+
+/// Bits holding the byte count in byte mode.
+fn count_bits(version: usize) -> usize {
+    if version < 10 {
+        8
+    } else {
+        16
+    }
+}
+
+/// Product in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1.
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z: u32 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11d);
+        z ^= u32::from((y >> i) & 1) * u32::from(x);
+    }
+    z as u8
+}
+
+/// Reed-Solomon generator polynomial of `degree`, without its leading
+/// term.
+fn rs_divisor(degree: usize) -> Vec<u8> {
+    let mut divisor = vec![0; degree];
+    divisor[degree - 1] = 1;
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            divisor[j] = gf_multiply(divisor[j], root);
+            if j + 1 < degree {
+                divisor[j] ^= divisor[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    divisor
+}
+
+fn rs_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut remainder = vec![0; divisor.len()];
+    for &byte in data {
+        let factor = byte ^ remainder.remove(0);
+        remainder.push(0);
+        for (r, &d) in remainder.iter_mut().zip(divisor) {
+            *r ^= gf_multiply(d, factor);
+        }
+    }
+    remainder
+}
+
+/// Splits `data` into blocks, appends each block's error correction and
+/// interleaves the lot.
+fn add_error_correction(data: &[u8], version: usize) -> Vec<u8> {
+    let blocks = BLOCKS[version - 1];
+    let ecc_len = ECC_PER_BLOCK[version - 1];
+    let raw_codewords = raw_data_modules(version) / 8;
+    let short_blocks = blocks - raw_codewords % blocks;
+    let short_len = raw_codewords / blocks;
+    let divisor = rs_divisor(ecc_len);
+
+    let mut rest = data;
+    let blocks: Vec<Vec<u8>> = (0..blocks)
+        .map(|i| {
+            let data_len = short_len - ecc_len + usize::from(i >= short_blocks);
+            let (block_data, tail) = rest.split_at(data_len);
+            rest = tail;
+            let mut block = block_data.to_vec();
+            let ecc = rs_remainder(&block, &divisor);
+            if i < short_blocks {
+                // Placeholder so every block is as long; skipped below.
+                block.push(0);
+            }
+            block.extend(ecc);
+            block
+        })
+        .collect();
+
+    let mut result = Vec::with_capacity(raw_codewords);
+    for i in 0..=short_len {
+        for (j, block) in blocks.iter().enumerate() {
+            if i != short_len - ecc_len || j >= short_blocks {
+                result.push(block[i]);
+            }
+        }
+    }
+    result
+}
+
+fn bit(value: u32, i: usize) -> bool {
+    (value >> i) & 1 != 0
+}
+
+/// The smallest version that holds `len` bytes, if any does.
+fn version_for(len: usize) -> Option<usize> {
+    (1..=MAX_VERSION).find(|&v| 4 + count_bits(v) + len * 8 <= data_codewords(v) * 8)
+}
+
+/// Whether `len` bytes fit in a QR code.
+pub fn fits(len: usize) -> bool {
+    version_for(len).is_some()
+}
+
+impl QrCode {
+    /// Encodes `data`, or returns `None` if it is too long for any
+    /// version.
+    pub fn encode(data: &[u8]) -> Option<QrCode> {
+        let version = version_for(data.len())?;
+        let capacity = data_codewords(version) * 8;
+
+        let mut bits = Vec::with_capacity(capacity);
+        let mut push = |value: usize, len: usize| {
+            for i in (0..len).rev() {
+                bits.push((value >> i) & 1 != 0);
+            }
+        };
+        push(0b0100, 4);
+        push(data.len(), count_bits(version));
+        for &byte in data {
+            push(usize::from(byte), 8);
+        }
+        let terminator = (capacity - bits.len()).min(4);
+        bits.extend(std::iter::repeat_n(false, terminator));
+        bits.extend(std::iter::repeat_n(false, (8 - bits.len() % 8) % 8));
+        let mut codewords: Vec<u8> = bits
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0, |acc, &b| (acc << 1) | u8::from(b)))
+            .collect();
+        for pad in [0xec, 0x11].iter().cycle() {
+            if codewords.len() == capacity / 8 {
+                break;
+            }
+            codewords.push(*pad);
+        }
+
+        let size = version * 4 + 17;
+        let mut code = QrCode {
+            size,
+            modules: vec![false; size * size],
+            function: vec![false; size * size],
+        };
+        code.draw_function_patterns(version);
+        code.draw_codewords(&add_error_correction(&codewords, version));
+
+        let mut best = None;
+        for mask in 0..8 {
+            code.apply_mask(mask);
+            code.draw_format_bits(mask);
+            let penalty = code.penalty();
+            if best.is_none_or(|(_, lowest)| penalty < lowest) {
+                best = Some((mask, penalty));
+            }
+            // Masks are their own inverse.
+            code.apply_mask(mask);
+        }
+        let (mask, _) = best?;
+        code.apply_mask(mask);
+        code.draw_format_bits(mask);
+        Some(code)
+    }
+
+    /// Modules along each side, not counting the quiet zone.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    /// SVG path data drawing every dark module as a unit square.
+    pub fn svg_path(&self) -> String {
+        let mut path = String::new();
+        for y in 0..self.size {
+            for x in 0..self.size {
+                if self.is_dark(x, y) {
+                    path += &format!("M{},{}h1v1h-1z", x, y);
+                }
+            }
+        }
+        path
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y * self.size + x] = dark;
+        self.function[y * self.size + x] = true;
+    }
+
+    fn draw_function_patterns(&mut self, version: usize) {
+        let size = self.size;
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+        for &(x, y) in &[(3, 3), (size - 4, 3), (3, size - 4)] {
+            self.draw_finder(x, y);
+        }
+
+        let positions = alignment_positions(version, size);
+        let last = positions.len().saturating_sub(1);
+        for (i, &y) in positions.iter().enumerate() {
+            for (j, &x) in positions.iter().enumerate() {
+                // These would overlap the finders.
+                let corner = [(0, 0), (0, last), (last, 0)].contains(&(i, j));
+                if !corner {
+                    self.draw_alignment(x, y);
+                }
+            }
+        }
+
+        // Reserved until a mask is chosen.
+        self.draw_format_bits(0);
+        if version >= 7 {
+            let mut remainder = version as u32;
+            for _ in 0..12 {
+                remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1f25);
+            }
+            let bits = (version as u32) << 12 | remainder;
+            for i in 0..18 {
+                let (a, b) = (size - 11 + i % 3, i / 3);
+                self.set_function(a, b, bit(bits, i));
+                self.set_function(b, a, bit(bits, i));
+            }
+        }
+    }
+
+    /// Draws a finder and its separator around the center `(x, y)`.
+    fn draw_finder(&mut self, x: usize, y: usize) {
+        for dy in -4i32..=4 {
+            for dx in -4i32..=4 {
+                let (fx, fy) = (x as i32 + dx, y as i32 + dy);
+                if (0..self.size as i32).contains(&fx) && (0..self.size as i32).contains(&fy) {
+                    let distance = dx.abs().max(dy.abs());
+                    self.set_function(fx as usize, fy as usize, distance != 2 && distance != 4);
+                }
+            }
+        }
+    }
+
+    fn draw_alignment(&mut self, x: usize, y: usize) {
+        for dy in -2i32..=2 {
+            for dx in -2i32..=2 {
+                let dark = dx.abs().max(dy.abs()) != 1;
+                self.set_function((x as i32 + dx) as usize, (y as i32 + dy) as usize, dark);
+            }
+        }
+    }
+
+    fn draw_format_bits(&mut self, mask: u32) {
+        let data = FORMAT_BITS_L << 3 | mask;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = (data << 10 | remainder) ^ 0x5412;
+        let size = self.size;
+
+        for i in 0..6 {
+            self.set_function(8, i, bit(bits, i));
+        }
+        self.set_function(8, 7, bit(bits, 6));
+        self.set_function(8, 8, bit(bits, 7));
+        self.set_function(7, 8, bit(bits, 8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(bits, i));
+        }
+
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(bits, i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(bits, i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    /// Fills the data modules in the zigzag order scanners read them.
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size;
+        let total = codewords.len() * 8;
+        let mut i = 0;
+        let mut right = size - 1;
+        while right >= 1 {
+            if right == 6 {
+                // The vertical timing pattern takes up this column.
+                right = 5;
+            }
+            let upward = (right + 1) & 2 == 0;
+            for vert in 0..size {
+                for j in 0..2 {
+                    let x = right - j;
+                    let y = if upward { size - 1 - vert } else { vert };
+                    if !self.function[y * size + x] && i < total {
+                        self.modules[y * size + x] = codewords[i / 8] >> (7 - i % 8) & 1 != 0;
+                        i += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u32) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                let idx = y * self.size + x;
+                if invert && !self.function[idx] {
+                    self.modules[idx] = !self.modules[idx];
+                }
+            }
+        }
+    }
+
+    /// How hard the code is to scan, by the standard's four rules. The
+    /// mask with the lowest penalty is used.
+    fn penalty(&self) -> usize {
+        let size = self.size;
+        let mut penalty = 0;
+        let lines = (0..size).flat_map(|i| {
+            let row = (0..size).map(move |j| (j, i));
+            let column = (0..size).map(move |j| (i, j));
+            vec![row.collect::<Vec<_>>(), column.collect::<Vec<_>>()]
+        });
+        for line in lines {
+            let line: Vec<bool> = line.iter().map(|&(x, y)| self.is_dark(x, y)).collect();
+            let mut run = 1;
+            for k in 1..=size {
+                if k < size && line[k] == line[k - 1] {
+                    run += 1;
+                } else {
+                    if run >= 5 {
+                        penalty += run - 2;
+                    }
+                    run = 1;
+                }
+            }
+            let finder = [true, false, true, true, true, false, true];
+            for window in line.windows(11) {
+                let light = |w: &[bool]| w.iter().all(|&d| !d);
+                if (window[..7] == finder && light(&window[7..]))
+                    || (light(&window[..4]) && window[4..] == finder)
+                {
+                    penalty += 40;
+                }
+            }
+        }
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let dark = self.is_dark(x, y);
+                if self.is_dark(x + 1, y) == dark
+                    && self.is_dark(x, y + 1) == dark
+                    && self.is_dark(x + 1, y + 1) == dark
+                {
+                    penalty += 3;
+                }
+            }
+        }
+        let total = size * size;
+        let dark = self.modules.iter().filter(|&&d| d).count();
+        // Ten points for each full 5% the dark share strays from half.
+        let steps = (dark * 20).abs_diff(total * 10).div_ceil(total);
+        penalty + steps.saturating_sub(1) * 10
+    }
+}
+
+/// Centers of the alignment patterns along either axis.
+fn alignment_positions(version: usize, size: usize) -> Vec<usize> {
+    if version == 1 {
+        return Vec::new();
+    }
+    let count = version / 7 + 2;
+    let step = (version * 8 + count * 3 + 5) / (count * 4 - 4) * 2;
+    let mut positions: Vec<usize> = (0..count - 1).map(|i| size - 7 - i * step).collect();
+    positions.push(6);
+    positions.reverse();
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether the 7×7 finder pattern centred on `(x, y)` is drawn.
+    fn has_finder(code: &QrCode, x: usize, y: usize) -> bool {
+        (0..7).all(|dy| {
+            (0..7).all(|dx| {
+                let ring = dx.min(dy).min(6 - dx).min(6 - dy);
+                let dark = ring != 1;
+                code.is_dark(x + dx - 3, y + dy - 3) == dark
+            })
+        })
+    }
+
+    #[test]
+    fn picks_the_smallest_version() {
+        assert_eq!(QrCode::encode(b"").unwrap().size(), 21);
+        // Version 1 at level L holds 17 bytes.
+        assert_eq!(QrCode::encode(&[b'a'; 17]).unwrap().size(), 21);
+        assert_eq!(QrCode::encode(&[b'a'; 18]).unwrap().size(), 25);
+    }
+
+    #[test]
+    fn refuses_data_over_the_largest_version() {
+        // Version 40 at level L holds 2953 bytes.
+        assert_eq!(QrCode::encode(&[0; 2953]).unwrap().size(), 177);
+        assert!(QrCode::encode(&[0; 2954]).is_none());
+        assert!(fits(2953) && !fits(2954));
+    }
+
+    #[test]
+    fn draws_finders_and_timing_patterns() {
+        let code = QrCode::encode(b"https://example.com/#krarks=2").unwrap();
+        let size = code.size();
+        assert!(has_finder(&code, 3, 3));
+        assert!(has_finder(&code, size - 4, 3));
+        assert!(has_finder(&code, 3, size - 4));
+        for i in 8..size - 8 {
+            assert_eq!(code.is_dark(6, i), i % 2 == 0);
+            assert_eq!(code.is_dark(i, 6), i % 2 == 0);
+        }
+    }
+
+    #[test]
+    fn draws_the_module_always_dark() {
+        for len in [1, 40, 200] {
+            let code = QrCode::encode(&vec![b'x'; len]).unwrap();
+            assert!(code.is_dark(8, code.size() - 8));
+        }
+    }
+
+    #[test]
+    fn draws_one_square_per_dark_module() {
+        let code = QrCode::encode(b"krark").unwrap();
+        let dark = (0..code.size())
+            .flat_map(|y| (0..code.size()).map(move |x| (x, y)))
+            .filter(|&(x, y)| code.is_dark(x, y))
+            .count();
+        assert_eq!(code.svg_path().matches('M').count(), dark);
+    }
+}
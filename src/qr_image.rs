@@ -0,0 +1,64 @@
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::qr::{QrCode, QUIET_ZONE};
+use yew::prelude::*;
+
+/// A scannable QR code of `text`, or a note when it is too long for one.
+pub struct QrImage {
+    props: Props,
+    /// Size and path data of the encoded text, kept so it is only encoded
+    /// again when the text changes.
+    code: Option<(usize, String)>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub text: String,
+}
+
+fn encode(text: &str) -> Option<(usize, String)> {
+    QrCode::encode(text.as_bytes()).map(|code| (code.size(), code.svg_path()))
+}
+
+impl Component for QrImage {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        let code = encode(&props.text);
+        QrImage { props, code }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        let text_changed = props.text != self.props.text;
+        let changed = self.props.neq_assign(props);
+        if text_changed {
+            self.code = encode(&self.props.text);
+        }
+        changed
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        match &self.code {
+            Some((size, path)) => {
+                let side = size + 2 * QUIET_ZONE;
+                let view_box = format!("-{q} -{q} {s} {s}", q = QUIET_ZONE, s = side);
+                html! {
+                    <svg class="qr-code" role="img" aria-label=lang.t("qr_code")
+                         viewBox=view_box shape-rendering="crispEdges">
+                        <rect x=format!("-{}", QUIET_ZONE) y=format!("-{}", QUIET_ZONE)
+                              width=side height=side fill="#fff" />
+                        <path d=path fill="#000" />
+                    </svg>
+                }
+            }
+            None => html! { <p class="qr-too-long">{ lang.t("qr_too_long") }</p> },
+        }
+    }
+}
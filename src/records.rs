@@ -0,0 +1,68 @@
+//! Personal bests kept across every session, each with the date it was
+//! set.
+
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumIter};
+
+/// Things worth bragging about. `as_ref` gives the record's text key.
+#[derive(AsRefStr, Clone, Copy, Debug, EnumIter, PartialEq)]
+pub enum RecordKind {
+    #[strum(serialize = "record_best_streak")]
+    BestStreak,
+    #[strum(serialize = "record_highest_storm")]
+    HighestStorm,
+    /// Storm and Krark copies of a single spell.
+    #[strum(serialize = "record_most_copies")]
+    MostCopies,
+    /// Measured in doublings of Okaun, Eye of Chaos, which outgrow any
+    /// power a number can hold.
+    #[strum(serialize = "record_biggest_okaun")]
+    BiggestOkaun,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub value: u64,
+    pub set_on: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Records {
+    #[serde(default)]
+    best_streak: Option<Record>,
+    #[serde(default)]
+    highest_storm: Option<Record>,
+    #[serde(default)]
+    most_copies: Option<Record>,
+    #[serde(default)]
+    biggest_okaun: Option<Record>,
+}
+
+impl Records {
+    pub fn get(&self, kind: RecordKind) -> Option<&Record> {
+        match kind {
+            RecordKind::BestStreak => self.best_streak.as_ref(),
+            RecordKind::HighestStorm => self.highest_storm.as_ref(),
+            RecordKind::MostCopies => self.most_copies.as_ref(),
+            RecordKind::BiggestOkaun => self.biggest_okaun.as_ref(),
+        }
+    }
+
+    /// Keeps `value` if it beats the record, returning whether it did.
+    pub fn offer(&mut self, kind: RecordKind, value: u64, set_on: &str) -> bool {
+        let record = match kind {
+            RecordKind::BestStreak => &mut self.best_streak,
+            RecordKind::HighestStorm => &mut self.highest_storm,
+            RecordKind::MostCopies => &mut self.most_copies,
+            RecordKind::BiggestOkaun => &mut self.biggest_okaun,
+        };
+        if value == 0 || record.as_ref().is_some_and(|r| r.value >= value) {
+            return false;
+        }
+        *record = Some(Record {
+            value,
+            set_on: set_on.to_string(),
+        });
+        true
+    }
+}
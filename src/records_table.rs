@@ -0,0 +1,60 @@
+use crate::effect::okaun_size;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::records::{RecordKind, Records};
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+/// Personal bests with the day each was set.
+pub struct RecordsTable {
+    props: Props,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub records: Records,
+}
+
+impl Component for RecordsTable {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+        RecordsTable { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <table class="records">
+                <tbody>
+                    { for RecordKind::iter().map(|kind| {
+                        let (value, set_on) = match self.props.records.get(kind) {
+                            Some(record) if kind == RecordKind::BiggestOkaun => {
+                                (okaun_size(record.value as u32), record.set_on.as_str())
+                            }
+                            Some(record) => (record.value.to_string(), record.set_on.as_str()),
+                            None => ("—".to_string(), ""),
+                        };
+                        html! {
+                            <tr>
+                                <th>{ lang.t(kind.as_ref()) }</th>
+                                <td>{ value }</td>
+                                <td><time>{ set_on }</time></td>
+                            </tr>
+                        }
+                    }) }
+                </tbody>
+            </table>
+        }
+    }
+}
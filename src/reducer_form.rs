@@ -0,0 +1,98 @@
+use crate::cast::{CostReducer, SpellFilter};
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+/// The form adding a cost reducer, which takes 1 off by default.
+pub struct ReducerForm {
+    link: ComponentLink<Self>,
+    props: Props,
+    name: String,
+    amount: String,
+    filter: SpellFilter,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub on_add: Callback<CostReducer>,
+}
+
+pub enum Msg {
+    UpdateName(String),
+    UpdateAmount(String),
+    SetFilter(SpellFilter),
+    Add,
+    Nope,
+}
+
+impl Component for ReducerForm {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        ReducerForm {
+            link,
+            props,
+            name: String::new(),
+            amount: "1".into(),
+            filter: SpellFilter::default(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateName(val) => self.name.neq_assign(val),
+            Msg::UpdateAmount(val) => self.amount.neq_assign(val),
+            Msg::SetFilter(filter) => self.filter.neq_assign(filter),
+            Msg::Add => {
+                let name = self.name.trim();
+                if name.is_empty() {
+                    return false;
+                }
+                self.props.on_add.emit(CostReducer {
+                    name: name.to_string(),
+                    amount: self.amount.parse().unwrap_or(1),
+                    applies_to: self.filter,
+                });
+                self.name.clear();
+                true
+            }
+            Msg::Nope => false,
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <div class="reducer-form">
+                <input placeholder=lang.t("reducer_name")
+                       aria-label=lang.t("reducer_name")
+                       value=&self.name
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateName(e.value)) />
+                <input type="number" min="1"
+                       aria-label=lang.t("reducer_amount")
+                       value=&self.amount
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateAmount(e.value)) />
+                <select aria-label=lang.t("reducer_applies_to")
+                        onchange=self.link.callback(|e: ChangeData| match e {
+                    ChangeData::Select(select) => SpellFilter::from_str(&select.value())
+                        .map(Msg::SetFilter)
+                        .unwrap_or(Msg::Nope),
+                    _ => Msg::Nope,
+                })>
+                    { for SpellFilter::iter().map(|filter| html! {
+                        <option value=filter selected=filter == self.filter>{ lang.t(filter.as_ref()) }</option>
+                    }) }
+                </select>
+                <button onclick=self.link.callback(|_| Msg::Add)>{ lang.t("add_reducer") }</button>
+            </div>
+        }
+    }
+}
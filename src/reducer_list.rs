@@ -0,0 +1,73 @@
+use crate::cast::CostReducer;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::reducer_form::ReducerForm;
+use yew::prelude::*;
+
+/// The cost reducers on the battlefield. Adding or removing one hands
+/// back the whole new list.
+pub struct ReducerList {
+    link: ComponentLink<Self>,
+    props: Props,
+    on_add: Callback<CostReducer>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub reducers: Vec<CostReducer>,
+    pub on_change: Callback<Vec<CostReducer>>,
+}
+
+pub enum Msg {
+    Add(CostReducer),
+    Remove(usize),
+}
+
+impl Component for ReducerList {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        ReducerList {
+            on_add: link.callback(Msg::Add),
+            link,
+            props,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let mut reducers = self.props.reducers.clone();
+        match msg {
+            Msg::Add(reducer) => reducers.push(reducer),
+            Msg::Remove(idx) => {
+                reducers.remove(idx);
+            }
+        }
+        self.props.on_change.emit(reducers);
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <>
+                <ul class="reducers">
+                    { for self.props.reducers.iter().enumerate().map(|(idx, reducer)| html! {
+                        <li>
+                            { lang.fmt("reducer_summary", &[&reducer.name, &reducer.amount, &lang.t(reducer.applies_to.as_ref())]) }
+                            <button class="destroy"
+                                    aria-label=lang.t("remove")
+                                    onclick=self.link.callback(move |_| Msg::Remove(idx))>{ "×" }</button>
+                        </li>
+                    }) }
+                </ul>
+                <ReducerForm language=lang on_add=self.on_add.clone() />
+            </>
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::coin_flip::Call;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::trigger::{StepCast, StepLine};
+use yew::prelude::*;
+
+/// Krark triggers flipped one at a time, each with a line saying what its
+/// coins did, for playing along at the table.
+pub struct ResolutionLog {
+    props: Props,
+    list: NodeRef,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub lines: Vec<StepLine>,
+    /// The cast being resolved, if any.
+    pub cast: Option<StepCast>,
+    /// Coins tossed for the current trigger while one must be picked.
+    pub pending: Option<Vec<Call>>,
+    /// Whether there is a Krark to flip for.
+    pub can_flip: bool,
+    pub on_flip: Callback<()>,
+    /// Keeps a coin that landed this way.
+    pub on_keep: Callback<Call>,
+    pub on_clear: Callback<()>,
+}
+
+fn side(call: Call) -> &'static str {
+    if call == Call::Heads {
+        "heads"
+    } else {
+        "tails"
+    }
+}
+
+impl ResolutionLog {
+    fn view_line(&self, line: &StepLine) -> Html {
+        let lang = self.props.language;
+        let coins = line
+            .coins
+            .iter()
+            .map(|&coin| lang.t(side(coin)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let class = if line.number == 1 {
+            "first-trigger"
+        } else {
+            ""
+        };
+        html! {
+            <li class=class>
+                { lang.fmt("resolution_line", &[&line.number, &coins, &lang.t(line.trigger.as_ref())]) }
+                { for (line.coins.len() > 1).then(|| html! {
+                    <span class="kept">{ lang.fmt("resolution_kept", &[&lang.t(side(line.kept))]) }</span>
+                }) }
+            </li>
+        }
+    }
+
+    fn view_pending(&self) -> Html {
+        let lang = self.props.language;
+        let coins = match &self.props.pending {
+            Some(coins) => coins,
+            None => return html! {},
+        };
+        let tossed = coins
+            .iter()
+            .map(|&coin| lang.t(side(coin)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        html! {
+            <div class="resolution-choice" role="group" aria-label=lang.t("resolution_choose")>
+                <p>{ lang.fmt("resolution_tossed", &[&tossed]) }</p>
+                { for [Call::Heads, Call::Tails].iter().map(|&call| html! {
+                    <button onclick=self.props.on_keep.reform(move |_| call)>
+                        { lang.fmt("resolution_keep", &[&lang.t(side(call))]) }
+                    </button>
+                }) }
+            </div>
+        }
+    }
+}
+
+impl Component for ResolutionLog {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+        ResolutionLog {
+            props,
+            list: NodeRef::default(),
+        }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn rendered(&mut self, _first_render: bool) {
+        // Keep the newest line in sight.
+        if let Some(list) = self.list.cast::<web_sys::Element>() {
+            list.set_scroll_top(list.scroll_height());
+        }
+    }
+
+    fn view(&self) -> Html {
+        let props = &self.props;
+        let lang = props.language;
+        let progress = match &props.cast {
+            Some(cast) if !cast.done() => lang.fmt(
+                "resolution_progress",
+                &[&(cast.resolution.triggers.len() + 1), &cast.triggers],
+            ),
+            _ => lang.t("resolution_new_cast").to_string(),
+        };
+        html! {
+            <div class="resolution">
+                <button class="step-flip"
+                        disabled=!props.can_flip || props.pending.is_some()
+                        onclick=props.on_flip.reform(|_| ())>
+                    { lang.t("step_flip") }
+                </button>
+                <span class="resolution-progress">{ progress }</span>
+                { self.view_pending() }
+                <ol class="resolution-log" ref=self.list.clone() aria-live="polite">
+                    { for props.lines.iter().map(|line| self.view_line(line)) }
+                </ol>
+                <button class="clear"
+                        disabled=props.lines.is_empty()
+                        onclick=props.on_clear.reform(|_| ())>{ lang.t("clear") }</button>
+            </div>
+        }
+    }
+}
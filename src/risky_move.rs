@@ -0,0 +1,86 @@
+//! Risky Move, which each player takes at the beginning of their upkeep.
+//! Whoever takes it from another player flips a coin, and on a loss gives
+//! one of their creatures to an opponent other than that player.
+
+use serde_derive::{Deserialize, Serialize};
+
+/// A creature Risky Move made change controllers. Players are numbered 0
+/// for you, then the opponents in seating order.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Handover {
+    pub creature: String,
+    pub from: usize,
+    pub to: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RiskyMove {
+    pub controller: usize,
+    /// Who the controller took it from this upkeep, while the flip for
+    /// that is due.
+    pub taken_from: Option<usize>,
+    /// Creatures given away through it, newest last.
+    pub handovers: Vec<Handover>,
+}
+
+impl RiskyMove {
+    pub fn new(controller: usize) -> Self {
+        RiskyMove {
+            controller,
+            ..RiskyMove::default()
+        }
+    }
+
+    /// Begins the upkeep of the player after the controller in seating
+    /// order, who takes Risky Move. Returns whether it changed hands,
+    /// which makes a flip due.
+    pub fn next_upkeep(&mut self, players: usize) -> bool {
+        let next = (self.controller + 1) % players.max(1);
+        if next == self.controller {
+            return false;
+        }
+        self.taken_from = Some(self.controller);
+        self.controller = next;
+        true
+    }
+
+    /// Players the controller may give a creature to: anyone but
+    /// themselves and whoever they took Risky Move from.
+    pub fn recipients(&self, players: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..players).filter(move |&p| p != self.controller && Some(p) != self.taken_from)
+    }
+
+    /// Settles the due flip. On a loss `creature` goes to `recipient`,
+    /// and the handover is returned.
+    pub fn resolve(&mut self, won: bool, creature: String, recipient: usize) -> Option<&Handover> {
+        self.taken_from.take()?;
+        if won {
+            return None;
+        }
+        self.handovers.push(Handover {
+            creature,
+            from: self.controller,
+            to: recipient,
+        });
+        self.handovers.last()
+    }
+
+    /// Keeps player numbers pointing at the same players after seats
+    /// change, as `renumber` maps them. Risky Move falls to you if its
+    /// controller left, and handovers involving players who left are
+    /// forgotten.
+    pub fn renumber(&mut self, renumber: impl Fn(usize) -> Option<usize>) {
+        self.controller = renumber(self.controller).unwrap_or(0);
+        self.taken_from = self.taken_from.and_then(&renumber);
+        self.handovers.retain_mut(|handover| {
+            match (renumber(handover.from), renumber(handover.to)) {
+                (Some(from), Some(to)) => {
+                    handover.from = from;
+                    handover.to = to;
+                    true
+                }
+                _ => false,
+            }
+        });
+    }
+}
@@ -0,0 +1,200 @@
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::risky_move::RiskyMove;
+use yew::prelude::*;
+
+/// Who controls Risky Move, passing it on each upkeep, and the flip due
+/// when it changes hands. Holds the creature and opponent picked for that
+/// flip until it is made.
+pub struct RiskyMovePanel {
+    link: ComponentLink<Self>,
+    props: Props,
+    creature: String,
+    recipient: Option<usize>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub risky_move: Option<RiskyMove>,
+    /// Names of the players in seat order, you first.
+    pub players: Vec<String>,
+    /// Your creatures, offered when picking the one at stake.
+    pub creatures: Vec<String>,
+    /// Receives the card as it should be after it is toggled or handed to
+    /// another controller.
+    pub on_change: Callback<Option<RiskyMove>>,
+    pub on_upkeep: Callback<()>,
+    /// Receives the creature at stake and the opponent picked for it.
+    pub on_flip: Callback<(String, Option<usize>)>,
+}
+
+pub enum Msg {
+    Toggle,
+    SetController(usize),
+    UpdateCreature(String),
+    SetRecipient(Option<usize>),
+    Flip,
+    Nope,
+}
+
+impl RiskyMovePanel {
+    fn player_name(&self, player: usize) -> &str {
+        self.props.players.get(player).map_or("", String::as_str)
+    }
+
+    /// The creature and opponent to pick, and the flip to make, while a
+    /// Risky Move flip is due.
+    fn view_flip(&self, risky_move: &RiskyMove) -> Html {
+        if risky_move.taken_from.is_none() {
+            return html! {};
+        }
+        let lang = self.props.language;
+        html! {
+            <div class="risky-move-flip">
+                <p role="alert">
+                    { lang.fmt("risky_move_due", &[&self.player_name(risky_move.controller)]) }
+                </p>
+                <input list="risky-move-creatures"
+                       aria-label=lang.t("risky_move_creature")
+                       placeholder=lang.t("risky_move_creature")
+                       value=&self.creature
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateCreature(e.value)) />
+                <datalist id="risky-move-creatures">
+                    { for self.props.creatures.iter().map(|name| html! { <option value=name /> }) }
+                </datalist>
+                <select aria-label=lang.t("risky_move_recipient")
+                        onchange=self.link.callback(|e: ChangeData| match e {
+                    ChangeData::Select(select) => Msg::SetRecipient(select.value().parse().ok()),
+                    _ => Msg::Nope,
+                })>
+                    <option value="" selected=self.recipient.is_none()>
+                        { lang.t("risky_move_recipient") }
+                    </option>
+                    { for risky_move.recipients(self.props.players.len()).map(|player| html! {
+                        <option value=player selected=self.recipient == Some(player)>
+                            { self.player_name(player) }
+                        </option>
+                    }) }
+                </select>
+                <button class="flip" onclick=self.link.callback(|_| Msg::Flip)>
+                    { lang.t("flip") }
+                </button>
+            </div>
+        }
+    }
+}
+
+impl Component for RiskyMovePanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        RiskyMovePanel {
+            link,
+            props,
+            creature: String::new(),
+            recipient: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Toggle => {
+                let risky_move = match self.props.risky_move {
+                    Some(_) => None,
+                    None => Some(RiskyMove::new(0)),
+                };
+                self.props.on_change.emit(risky_move);
+                false
+            }
+            Msg::SetController(player) => {
+                if let Some(risky_move) = &self.props.risky_move {
+                    self.props.on_change.emit(Some(RiskyMove {
+                        controller: player,
+                        taken_from: None,
+                        handovers: risky_move.handovers.clone(),
+                    }));
+                }
+                false
+            }
+            Msg::UpdateCreature(creature) => self.creature.neq_assign(creature),
+            Msg::SetRecipient(player) => self.recipient.neq_assign(player),
+            Msg::Flip => {
+                self.props
+                    .on_flip
+                    .emit((self.creature.trim().to_string(), self.recipient));
+                false
+            }
+            Msg::Nope => false,
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // A flip coming due or being made starts the picks afresh.
+        let due = |props: &Props| {
+            props
+                .risky_move
+                .as_ref()
+                .map(|risky_move| (risky_move.controller, risky_move.taken_from))
+        };
+        if due(&self.props) != due(&props) {
+            self.creature.clear();
+            self.recipient = None;
+        }
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        let toggle = html! {
+            <label>
+                <input type="checkbox"
+                       checked=self.props.risky_move.is_some()
+                       onclick=self.link.callback(|_| Msg::Toggle) />
+                { lang.t("risky_move") }
+            </label>
+        };
+        let risky_move = match &self.props.risky_move {
+            Some(risky_move) => risky_move,
+            None => return toggle,
+        };
+        html! {
+            <div class="risky-move">
+                { toggle }
+                <label>
+                    { lang.t("risky_move_controller") }
+                    <select onchange=self.link.callback(|e: ChangeData| match e {
+                        ChangeData::Select(select) => select
+                            .value()
+                            .parse()
+                            .map(Msg::SetController)
+                            .unwrap_or(Msg::Nope),
+                        _ => Msg::Nope,
+                    })>
+                        { for (0..self.props.players.len()).map(|player| html! {
+                            <option value=player selected=player == risky_move.controller>
+                                { self.player_name(player) }
+                            </option>
+                        }) }
+                    </select>
+                </label>
+                <button onclick=self.props.on_upkeep.reform(|_| ())>
+                    { lang.t("risky_move_next_upkeep") }
+                </button>
+                { self.view_flip(risky_move) }
+                <ul class="risky-move-handovers">
+                    { for risky_move.handovers.iter().map(|handover| html! {
+                        <li>
+                            { lang.fmt("risky_move_handover", &[
+                                &handover.creature,
+                                &self.player_name(handover.from),
+                                &self.player_name(handover.to),
+                            ]) }
+                        </li>
+                    }) }
+                </ul>
+            </div>
+        }
+    }
+}
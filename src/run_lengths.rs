@@ -0,0 +1,101 @@
+use crate::chart::Chart;
+use crate::coin_flip::Coin;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::session::geometric;
+use yew::prelude::*;
+
+/// Width of one streak length in the plot, in SVG user units.
+const RUN_BAR_WIDTH: usize = 20;
+/// Height of the plot, in SVG user units.
+const RUN_HEIGHT: usize = 100;
+
+/// Plots the observed streak lengths as bars against the geometric
+/// distribution expected for the current thumb count.
+pub struct RunLengths {
+    props: Props,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    /// `counts[k]` is the number of streaks of exactly `k` wins.
+    pub counts: Vec<usize>,
+    pub thumbs: usize,
+}
+
+impl Component for RunLengths {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+        RunLengths { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let counts = &self.props.counts;
+        let total: usize = counts.iter().sum();
+        if total == 0 {
+            return html! {};
+        }
+        let win = Coin::win_chance(self.props.thumbs);
+        let lang = self.props.language;
+        // Show a few lengths past the longest streak so the expected tail is visible.
+        let lengths = counts.len() + 3;
+        let width = lengths * RUN_BAR_WIDTH;
+        let height = RUN_HEIGHT as f64;
+        let expected = (0..lengths)
+            .map(|k| {
+                let x = k * RUN_BAR_WIDTH + RUN_BAR_WIDTH / 2;
+                let y = height - geometric(win, k) * height;
+                format!("{},{:.1}", x, y)
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        html! {
+            <figure class="run-lengths">
+                <Chart name="run-lengths">
+                    <svg width=width
+                         height=RUN_HEIGHT + 12
+                         viewBox=format!("0 0 {} {}", width, RUN_HEIGHT + 12)>
+                        { for (0..lengths).map(|k| {
+                            let observed = counts.get(k).cloned().unwrap_or(0);
+                            let fraction = observed as f64 / total as f64;
+                            html! {
+                                <g>
+                                    <title>{ lang.fmt("run_length_title", &[
+                                        &k,
+                                        &observed,
+                                        &format!("{:.0}%", fraction * 100.0),
+                                        &format!("{:.0}%", geometric(win, k) * 100.0),
+                                    ]) }</title>
+                                    <rect class="observed"
+                                          x=k * RUN_BAR_WIDTH + 1
+                                          y=format!("{:.1}", height - fraction * height)
+                                          width=RUN_BAR_WIDTH - 2
+                                          height=format!("{:.1}", fraction * height) />
+                                    <text x=k * RUN_BAR_WIDTH + RUN_BAR_WIDTH / 2
+                                          y=RUN_HEIGHT + 11
+                                          text-anchor="middle">{ k }</text>
+                                </g>
+                            }
+                        }) }
+                        <polyline class="expected" points=expected />
+                    </svg>
+                </Chart>
+                <figcaption>{ lang.fmt(
+                    "run_lengths_caption",
+                    &[&total, &format!("{:.0}%", win * 100.0)],
+                ) }</figcaption>
+            </figure>
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! User scripts in Rhai for effects custom card definitions can't express.
+//! A script defines any of
+//!
+//! ```text
+//! fn on_flip(source, wins, losses) { ... }
+//! fn on_roll(total) { ... }
+//! fn on_cast(spell, copies) { ... }
+//! ```
+//!
+//! and changes `this.life`, `this.hand` and `this.mana`. Whatever it
+//! `print`s goes to the table log. Scripts can't import modules, read the
+//! clock or run for long.
+
+use crate::cast::Board;
+use crate::events::EngineEvent;
+use rhai::{CallFnOptions, Dynamic, Engine, Map, Scope, AST};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Operations one event may take before the script is stopped.
+const MAX_OPERATIONS: u64 = 100_000;
+
+pub struct Script {
+    engine: Engine,
+    ast: AST,
+    /// Lines printed during the current event.
+    printed: Rc<RefCell<Vec<String>>>,
+}
+
+impl Script {
+    pub fn compile(source: &str) -> Result<Self, String> {
+        let printed = Rc::new(RefCell::new(Vec::new()));
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(MAX_OPERATIONS)
+            .set_max_call_levels(32)
+            .set_max_expr_depths(64, 32)
+            .set_max_string_size(10_000)
+            .set_max_array_size(10_000)
+            .set_max_map_size(1_000)
+            .disable_symbol("eval");
+        let sink = printed.clone();
+        engine.on_print(move |line| sink.borrow_mut().push(line.to_string()));
+        let ast = engine.compile(source).map_err(|err| err.to_string())?;
+        Ok(Script {
+            engine,
+            ast,
+            printed,
+        })
+    }
+
+    /// Calls the script's handler for `event`, if it has one, and applies
+    /// its changes to `board`. Returns what it printed.
+    pub fn run(&self, event: &EngineEvent, board: &mut Board) -> Result<Vec<String>, String> {
+        let (name, args): (_, Vec<Dynamic>) = match event {
+            EngineEvent::Flipped(records) => {
+                let source = records.first().map(|r| r.source.to_string());
+                let wins = records.iter().filter(|r| r.is_win()).count() as i64;
+                let losses = records.len() as i64 - wins;
+                (
+                    "on_flip",
+                    vec![
+                        source.unwrap_or_default().into(),
+                        wins.into(),
+                        losses.into(),
+                    ],
+                )
+            }
+            EngineEvent::Rolled(roll) => ("on_roll", vec![roll.total.into()]),
+            EngineEvent::Cast { spell, outcome } => {
+                let copies = i64::from(outcome.copies());
+                ("on_cast", vec![spell.name.clone().into(), copies.into()])
+            }
+        };
+        let defined = self
+            .ast
+            .iter_functions()
+            .any(|f| f.name == name && f.params.len() == args.len());
+        if !defined {
+            return Ok(Vec::new());
+        }
+
+        let mut this = Map::new();
+        this.insert("life".into(), i64::from(board.life).into());
+        this.insert("hand".into(), i64::from(board.hand).into());
+        this.insert("mana".into(), i64::from(board.mana).into());
+        let mut this = Dynamic::from_map(this);
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut this);
+        let result = self.engine.call_fn_with_options::<Dynamic>(
+            options,
+            &mut Scope::new(),
+            &self.ast,
+            name,
+            args,
+        );
+        let printed = self.printed.take();
+        if let Err(err) = result {
+            return Err(err.to_string());
+        }
+
+        let this = this.try_cast::<Map>().unwrap_or_default();
+        let field = |name: &str| this.get(name).and_then(|v| v.as_int().ok());
+        if let Some(life) = field("life") {
+            board.life = life.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32;
+        }
+        if let Some(hand) = field("hand") {
+            board.hand = hand.clamp(0, i64::from(u32::MAX)) as u32;
+        }
+        if let Some(mana) = field("mana") {
+            board.mana = mana.clamp(0, i64::from(u32::MAX)) as u32;
+        }
+        Ok(printed)
+    }
+}
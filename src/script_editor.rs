@@ -0,0 +1,78 @@
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// The user's script, the switch that runs it and any compile error. The
+/// script being edited is only handed on when loaded.
+pub struct ScriptEditor {
+    link: ComponentLink<Self>,
+    props: Props,
+    text: String,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub enabled: bool,
+    /// The saved script, which the editor starts from.
+    pub source: String,
+    pub error: Option<String>,
+    pub on_toggle: Callback<()>,
+    pub on_load: Callback<String>,
+}
+
+pub enum Msg {
+    Update(String),
+    Load,
+}
+
+impl Component for ScriptEditor {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        ScriptEditor {
+            link,
+            text: props.source.clone(),
+            props,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Update(val) => self.text.neq_assign(val),
+            Msg::Load => {
+                self.props.on_load.emit(self.text.clone());
+                false
+            }
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <div class="scripts">
+                <label>
+                    <input type="checkbox"
+                           checked=self.props.enabled
+                           onclick=self.props.on_toggle.reform(|_| ()) />
+                    { lang.t("scripts_enabled") }
+                </label>
+                <p class="script-help">{ lang.t("script_help") }</p>
+                <textarea aria-label=lang.t("script_source")
+                          spellcheck="false"
+                          value=&self.text
+                          oninput=self.link.callback(|e: InputData| Msg::Update(e.value)) />
+                <button onclick=self.link.callback(|_| Msg::Load)>{ lang.t("load_script") }</button>
+                { match &self.props.error {
+                    Some(error) => html! { <p class="error" role="alert">{ lang.fmt("script_error", &[error]) }</p> },
+                    None => html! {},
+                } }
+            </div>
+        }
+    }
+}
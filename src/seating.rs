@@ -0,0 +1,54 @@
+//! Pregame roll-off for the first player, and random seating.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Sides of the die players roll off with.
+pub const ROLL_OFF_DIE: u32 = 20;
+
+/// Rerolls allowed before the first of the tied players is picked, so a
+/// roll-off always ends.
+const MAX_ROUNDS: usize = 100;
+
+/// A finished roll-off. Players are indices into the list that rolled.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RollOff {
+    /// Each round's rolls; every round after the first is a reroll among
+    /// the players tied for the highest roll.
+    pub rounds: Vec<Vec<(usize, u32)>>,
+    pub winner: usize,
+}
+
+/// Rolls a d20 for each of `players`, rerolling ties for the highest roll
+/// until one player is left.
+pub fn roll_off<R: Rng>(players: usize, rng: &mut R) -> Option<RollOff> {
+    let mut rolling = (0..players).collect::<Vec<_>>();
+    let mut rounds = Vec::new();
+    while !rolling.is_empty() && rounds.len() < MAX_ROUNDS {
+        let rolls = rolling
+            .iter()
+            .map(|&player| (player, rng.gen_range(1..=ROLL_OFF_DIE)))
+            .collect::<Vec<_>>();
+        let high = rolls.iter().map(|&(_, roll)| roll).max()?;
+        rolling = rolls
+            .iter()
+            .filter(|&&(_, roll)| roll == high)
+            .map(|&(player, _)| player)
+            .collect();
+        rounds.push(rolls);
+        if rolling.len() == 1 {
+            break;
+        }
+    }
+    Some(RollOff {
+        winner: *rolling.first()?,
+        rounds,
+    })
+}
+
+/// A random seating order for `players`, as indices.
+pub fn shuffle_seating<R: Rng>(players: usize, rng: &mut R) -> Vec<usize> {
+    let mut seats = (0..players).collect::<Vec<_>>();
+    seats.shuffle(rng);
+    seats
+}
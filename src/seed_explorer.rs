@@ -0,0 +1,98 @@
+use crate::coin_flip::{FlipConfig, FlipResult};
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use yew::prelude::*;
+
+/// Replays the flips produced by a seed one step at a time.
+pub struct SeedExplorer {
+    link: ComponentLink<Self>,
+    props: Props,
+    seed: String,
+    step: usize,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub thumbs: usize,
+}
+
+pub enum Msg {
+    UpdateSeed(String),
+    Next,
+    Prev,
+    Restart,
+}
+
+impl SeedExplorer {
+    fn flips(&self, seed: u64) -> Vec<FlipResult> {
+        FlipConfig::new()
+            .thumbs(self.props.thumbs)
+            .flips(self.step)
+            .flip(&mut StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Component for SeedExplorer {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        SeedExplorer {
+            link,
+            props,
+            seed: "".into(),
+            step: 0,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateSeed(seed) => {
+                self.seed = seed;
+                self.step = 0;
+            }
+            Msg::Next => self.step += 1,
+            Msg::Prev => self.step = self.step.saturating_sub(1),
+            Msg::Restart => self.step = 0,
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let seed = self.seed.trim().parse::<u64>();
+        let lang = self.props.language;
+        html! {
+            <div class="seed-explorer">
+                <input class="seed"
+                       placeholder=lang.t("seed")
+                       aria-label=lang.t("seed")
+                       value=&self.seed
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateSeed(e.value)) />
+                <button disabled=seed.is_err() || self.step == 0
+                        onclick=self.link.callback(|_| Msg::Prev)>{ lang.t("prev") }</button>
+                <button disabled=seed.is_err()
+                        onclick=self.link.callback(|_| Msg::Next)>{ lang.t("next") }</button>
+                <button disabled=self.step == 0
+                        onclick=self.link.callback(|_| Msg::Restart)>{ lang.t("restart") }</button>
+                {
+                    match seed {
+                        Ok(seed) => html! {
+                            <ol class="item-list">
+                                { for self.flips(seed).iter().map(|f| html! { <li>{ lang.flip(f) }</li> }) }
+                            </ol>
+                        },
+                        Err(_) if self.seed.is_empty() => html! {},
+                        Err(_) => html! { <p class="error">{ lang.t("seed_invalid") }</p> },
+                    }
+                }
+            </div>
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// The box showing the game's seed, handing on each seed typed into it.
+/// Holds the text as typed, so one that does not parse stays to be fixed.
+pub struct SeedInput {
+    link: ComponentLink<Self>,
+    props: Props,
+    text: String,
+    invalid: bool,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub seed: Option<u64>,
+    pub on_change: Callback<u64>,
+}
+
+fn seed_text(seed: Option<u64>) -> String {
+    seed.map(|seed| seed.to_string()).unwrap_or_default()
+}
+
+impl Component for SeedInput {
+    /// The text typed.
+    type Message = String;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        SeedInput {
+            text: seed_text(props.seed),
+            link,
+            props,
+            invalid: false,
+        }
+    }
+
+    fn update(&mut self, text: Self::Message) -> ShouldRender {
+        match text.trim().parse() {
+            Ok(seed) => {
+                self.invalid = false;
+                self.props.on_change.emit(seed);
+            }
+            Err(_) => self.invalid = !text.trim().is_empty(),
+        }
+        self.text = text;
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // A game reseeded or switched to shows its own seed, unless it is
+        // the one just typed.
+        if props.seed != self.props.seed && self.text.trim().parse().ok() != props.seed {
+            self.text = seed_text(props.seed);
+            self.invalid = false;
+        }
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <>
+                <label>
+                    { lang.t("seed") }
+                    <input class="seed"
+                           value=&self.text
+                           aria-invalid=self.invalid.to_string()
+                           oninput=self.link.callback(|e: InputData| e.value) />
+                </label>
+                { for self.invalid.then(|| html! {
+                    <p class="error" role="alert">{ lang.t("seed_invalid") }</p>
+                }) }
+            </>
+        }
+    }
+}
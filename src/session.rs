@@ -0,0 +1,436 @@
+use crate::card::FlipCard;
+use crate::game::Game;
+use crate::history::{self, FlipRecord, StatsAccumulator};
+use crate::packed::{self, Layout};
+use crate::persisted::StateError;
+use crate::trigger::{Trigger, TriggerRecord};
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::AsRefStr;
+
+/// Version of the export format this build writes.
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Headline numbers of a finished session, kept so sessions can be compared.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub saved_at: String,
+    pub flips: usize,
+    pub wins: usize,
+    pub turns: usize,
+    pub storm: u32,
+    pub longest_streak: usize,
+    /// Milliseconds the session ran for.
+    #[serde(default)]
+    pub duration: u64,
+    /// Seed the session's flips came from, to replay them.
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+/// Layout 1 of a summary, from before sessions were timed.
+#[derive(Deserialize, Serialize)]
+struct SummaryV1 {
+    saved_at: String,
+    flips: usize,
+    wins: usize,
+    turns: usize,
+    storm: u32,
+    longest_streak: usize,
+}
+
+/// Layout 2 of a summary, from before sessions kept their seed.
+#[derive(Deserialize, Serialize)]
+struct SummaryV2 {
+    saved_at: String,
+    flips: usize,
+    wins: usize,
+    turns: usize,
+    storm: u32,
+    longest_streak: usize,
+    duration: u64,
+}
+
+impl From<SummaryV2> for SessionSummary {
+    fn from(old: SummaryV2) -> Self {
+        SessionSummary {
+            saved_at: old.saved_at,
+            flips: old.flips,
+            wins: old.wins,
+            turns: old.turns,
+            storm: old.storm,
+            longest_streak: old.longest_streak,
+            duration: old.duration,
+            seed: None,
+        }
+    }
+}
+
+impl From<SummaryV1> for SessionSummary {
+    fn from(old: SummaryV1) -> Self {
+        SessionSummary::from(SummaryV2 {
+            saved_at: old.saved_at,
+            flips: old.flips,
+            wins: old.wins,
+            turns: old.turns,
+            storm: old.storm,
+            longest_streak: old.longest_streak,
+            duration: 0,
+        })
+    }
+}
+
+impl Layout for Vec<SessionSummary> {
+    const LAYOUT: u32 = 3;
+
+    fn upgrade(layout: u32, bytes: &[u8]) -> Option<Self> {
+        match layout {
+            1 => packed::exact::<Vec<SummaryV1>>(bytes)
+                .map(|old| old.into_iter().map(Into::into).collect()),
+            2 => packed::exact::<Vec<SummaryV2>>(bytes)
+                .map(|old| old.into_iter().map(Into::into).collect()),
+            _ => None,
+        }
+    }
+}
+
+impl SessionSummary {
+    pub fn new(
+        saved_at: String,
+        history: &[FlipRecord],
+        turn_storms: &[u32],
+        duration: u64,
+        seed: Option<u64>,
+    ) -> Self {
+        let stats = StatsAccumulator::new(history);
+        SessionSummary {
+            saved_at,
+            flips: stats.flips,
+            wins: stats.wins,
+            turns: turn_storms.len(),
+            storm: turn_storms.iter().sum(),
+            longest_streak: stats.longest_streak,
+            duration,
+            seed,
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.flips == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.flips as f64
+        }
+    }
+
+    pub fn flips_per_minute(&self) -> f64 {
+        if self.duration == 0 {
+            0.0
+        } else {
+            self.flips as f64 * 60_000.0 / self.duration as f64
+        }
+    }
+
+    pub fn average_storm(&self) -> f64 {
+        if self.turns == 0 {
+            0.0
+        } else {
+            f64::from(self.storm) / self.turns as f64
+        }
+    }
+}
+
+/// Why an imported file was turned down. `as_ref` gives its text key.
+#[derive(AsRefStr, Clone, Copy, Debug, PartialEq)]
+pub enum ImportError {
+    /// Written by a newer build than this one.
+    #[strum(serialize = "import_too_new")]
+    TooNew,
+    /// Not a session or setup at all.
+    #[strum(serialize = "import_unreadable")]
+    Unreadable,
+    /// A session whose parts do not agree with each other.
+    #[strum(serialize = "import_inconsistent")]
+    Inconsistent,
+}
+
+impl From<StateError> for ImportError {
+    fn from(error: StateError) -> Self {
+        match error {
+            StateError::TooNew => ImportError::TooNew,
+            StateError::Unreadable => ImportError::Unreadable,
+        }
+    }
+}
+
+/// The current session in full, for analysing elsewhere: the board it was
+/// played on, every flip, every Krark trigger and the headline numbers.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SessionExport {
+    pub version: u32,
+    pub krarks: usize,
+    pub thumbs: usize,
+    pub summary: SessionSummary,
+    pub flips: Vec<FlipRecord>,
+    #[serde(default)]
+    pub triggers: Vec<TriggerRecord>,
+}
+
+impl SessionExport {
+    pub fn new(game: &Game, summary: SessionSummary) -> Self {
+        SessionExport {
+            version: EXPORT_VERSION,
+            krarks: game.board.krarks,
+            thumbs: game.board.thumbs,
+            summary,
+            flips: game.history.clone(),
+            triggers: game.triggers.clone(),
+        }
+    }
+
+    /// Checks that the file was written by this build or an older one and
+    /// that its flips, triggers and summary agree.
+    pub fn validate(&self) -> Result<(), ImportError> {
+        if self.version > EXPORT_VERSION {
+            return Err(ImportError::TooNew);
+        }
+        let wins = self.flips.iter().filter(|record| record.is_win()).count();
+        if self.summary.flips != self.flips.len() || self.summary.wins != wins {
+            return Err(ImportError::Inconsistent);
+        }
+        let in_order = self
+            .flips
+            .windows(2)
+            .all(|pair| pair[0].activation <= pair[1].activation);
+        let flipped = |activation| {
+            self.flips
+                .iter()
+                .any(|record| record.activation == activation && record.source == FlipCard::Krark)
+        };
+        let triggers_flipped = self
+            .triggers
+            .iter()
+            .all(|record| record.number > 0 && flipped(record.activation));
+        if !in_order || !triggers_flipped {
+            return Err(ImportError::Inconsistent);
+        }
+        Ok(())
+    }
+
+    /// Puts `game` back where the session was exported, flips and all.
+    /// Its seed is left alone: the flips that follow are new ones, not a
+    /// replay of those imported.
+    pub fn restore(&self, game: &mut Game) {
+        game.board.krarks = self.krarks;
+        game.board.thumbs = self.thumbs;
+        game.history = self.flips.clone();
+        game.triggers = self.triggers.clone();
+        game.activations = self.flips.last().map_or(0, |record| record.activation + 1);
+    }
+
+    /// The flips, the triggers and the summary as three tables, one after
+    /// the other with a blank line between.
+    pub fn to_csv(&self) -> String {
+        let mut csv = history::to_csv(&self.flips);
+        csv += "\ncast,trigger,activation,result\n";
+        for record in &self.triggers {
+            let result = match record.trigger {
+                Trigger::Copied => "copied",
+                Trigger::Bounced => "bounced",
+                Trigger::Missed => "missed",
+            };
+            csv += &format!(
+                "{},{},{},{}\n",
+                record.cast, record.number, record.activation, result
+            );
+        }
+        let summary = &self.summary;
+        csv += "\nsaved_at,krarks,thumbs,flips,wins,turns,storm,longest_streak,duration_ms,seed\n";
+        csv += &format!(
+            "{},{},{},{},{},{},{},{},{},{}\n",
+            history::csv_field(&summary.saved_at),
+            self.krarks,
+            self.thumbs,
+            summary.flips,
+            summary.wins,
+            summary.turns,
+            summary.storm,
+            summary.longest_streak,
+            summary.duration,
+            summary
+                .seed
+                .map(|seed| seed.to_string())
+                .unwrap_or_default()
+        );
+        csv
+    }
+}
+
+/// Chance that a streak is exactly `length` wins long when each flip is won
+/// with probability `win`.
+pub fn geometric(win: f64, length: usize) -> f64 {
+    win.powi(length as i32) * (1.0 - win)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coin_flip::{FlipResult, WinCondition};
+    use crate::packed::{pack, unpack};
+
+    impl Layout for Vec<SummaryV1> {
+        const LAYOUT: u32 = 1;
+    }
+
+    impl Layout for Vec<SummaryV2> {
+        const LAYOUT: u32 = 2;
+    }
+
+    fn summary(duration: u64, seed: Option<u64>) -> SessionSummary {
+        SessionSummary {
+            saved_at: "2021-03-04 05:06".into(),
+            flips: 12,
+            wins: 9,
+            turns: 3,
+            storm: 4,
+            longest_streak: 5,
+            duration,
+            seed,
+        }
+    }
+
+    fn v1() -> SummaryV1 {
+        SummaryV1 {
+            saved_at: "2021-03-04 05:06".into(),
+            flips: 12,
+            wins: 9,
+            turns: 3,
+            storm: 4,
+            longest_streak: 5,
+        }
+    }
+
+    /// Packed text as written before layouts were marked.
+    fn unmarked(text: String) -> String {
+        text.split_once('.').unwrap().1.to_string()
+    }
+
+    #[test]
+    fn reads_summaries_in_every_layout() {
+        let current = vec![summary(60_000, Some(7)), summary(0, None)];
+        let text = pack(&current).unwrap();
+        assert!(text.starts_with("3."));
+        assert_eq!(unpack::<Vec<SessionSummary>>(&text), Some(current));
+
+        let old = pack(&vec![v1(), v1()]).unwrap();
+        let expected = vec![summary(0, None), summary(0, None)];
+        assert_eq!(unpack::<Vec<SessionSummary>>(&old), Some(expected));
+
+        let timed = SummaryV2 {
+            saved_at: "2021-03-04 05:06".into(),
+            flips: 12,
+            wins: 9,
+            turns: 3,
+            storm: 4,
+            longest_streak: 5,
+            duration: 60_000,
+        };
+        let old = pack(&vec![timed]).unwrap();
+        assert_eq!(
+            unpack::<Vec<SessionSummary>>(&old),
+            Some(vec![summary(60_000, None)])
+        );
+    }
+
+    #[test]
+    fn reads_unmarked_summaries_by_their_shape() {
+        let expected = vec![summary(0, None); 3];
+        let old = unmarked(pack(&vec![v1(), v1(), v1()]).unwrap());
+        assert_eq!(unpack::<Vec<SessionSummary>>(&old), Some(expected));
+        let current = vec![summary(60_000, Some(7))];
+        let text = unmarked(pack(&current).unwrap());
+        assert_eq!(unpack::<Vec<SessionSummary>>(&text), Some(current));
+    }
+
+    #[test]
+    fn refuses_layouts_from_newer_builds() {
+        let text = pack(&vec![summary(1, None)])
+            .unwrap()
+            .replacen("3.", "4.", 1);
+        assert_eq!(unpack::<Vec<SessionSummary>>(&text), None);
+    }
+
+    fn export() -> SessionExport {
+        let record = |activation, result| FlipRecord {
+            source: FlipCard::Krark,
+            activation,
+            thumbs: 1,
+            player: None,
+            win: WinCondition::YouCall,
+            result,
+        };
+        let trigger = |cast, number, activation, trigger| TriggerRecord {
+            cast,
+            number,
+            activation,
+            trigger,
+        };
+        let mut game = Game::new("Exported".into());
+        game.board.krarks = 2;
+        game.board.thumbs = 1;
+        game.history = vec![
+            record(0, FlipResult::Heads),
+            record(0, FlipResult::Tails),
+            record(1, FlipResult::Both),
+        ];
+        game.triggers = vec![
+            trigger(0, 1, 0, Trigger::Copied),
+            trigger(0, 2, 0, Trigger::Bounced),
+            trigger(1, 1, 1, Trigger::Copied),
+        ];
+        let summary = SessionSummary::new("2021-03-04 05:06".into(), &game.history, &[2], 90, None);
+        SessionExport::new(&game, summary)
+    }
+
+    #[test]
+    fn restores_an_exported_session() {
+        let exported = export();
+        assert_eq!(exported.validate(), Ok(()));
+        let mut game = Game {
+            seed: Some(11),
+            ..Game::default()
+        };
+        exported.restore(&mut game);
+        assert_eq!((game.board.krarks, game.board.thumbs), (2, 1));
+        assert_eq!(game.activations, 2);
+        assert_eq!(game.seed, Some(11));
+
+        let again = SessionExport::new(&game, exported.summary.clone());
+        assert_eq!(again.flips, exported.flips);
+        assert_eq!(again.triggers, exported.triggers);
+        assert_eq!(again.to_csv(), exported.to_csv());
+    }
+
+    #[test]
+    fn refuses_exports_from_newer_builds() {
+        let mut exported = export();
+        exported.version = EXPORT_VERSION + 1;
+        assert_eq!(exported.validate(), Err(ImportError::TooNew));
+        exported.version = EXPORT_VERSION - 1;
+        assert_eq!(exported.validate(), Ok(()));
+    }
+
+    #[test]
+    fn refuses_exports_whose_parts_disagree() {
+        let corrupt = |corrupt: fn(&mut SessionExport)| {
+            let mut exported = export();
+            corrupt(&mut exported);
+            exported.validate()
+        };
+        let inconsistent = Err(ImportError::Inconsistent);
+        assert_eq!(corrupt(|e| e.summary.wins += 1), inconsistent);
+        assert_eq!(corrupt(|e| drop(e.flips.pop())), inconsistent);
+        assert_eq!(corrupt(|e| e.flips.swap(0, 2)), inconsistent);
+        assert_eq!(corrupt(|e| e.triggers[2].activation = 5), inconsistent);
+        assert_eq!(corrupt(|e| e.triggers[0].number = 0), inconsistent);
+    }
+}
@@ -0,0 +1,99 @@
+use crate::clock::format_clock;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::session::SessionSummary;
+use yew::prelude::*;
+
+/// Compares saved sessions side by side, one column per session.
+pub struct SessionDashboard {
+    props: Props,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub sessions: Vec<SessionSummary>,
+    /// Drops the session at the given index.
+    pub on_remove: Callback<usize>,
+}
+
+impl SessionDashboard {
+    /// One dashboard row: the metric for each session with a bar scaled to
+    /// the best session.
+    fn view_metric(
+        &self,
+        label: &str,
+        metric: impl Fn(&SessionSummary) -> f64,
+        format: impl Fn(f64) -> String,
+    ) -> Html {
+        let values = self.props.sessions.iter().map(metric).collect::<Vec<_>>();
+        let max = values.iter().cloned().fold(0.0, f64::max);
+        html! {
+            <tr>
+                <th>{ self.props.language.t(label) }</th>
+                { for values.iter().map(|&value| {
+                    let width = if max > 0.0 { value / max * 100.0 } else { 0.0 };
+                    html! {
+                        <td>
+                            <svg class="metric-bar" width="100" height="8" viewBox="0 0 100 8">
+                                <rect width=format!("{:.1}", width) height="8" />
+                            </svg>
+                            { format(value) }
+                        </td>
+                    }
+                }) }
+            </tr>
+        }
+    }
+}
+
+impl Component for SessionDashboard {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+        SessionDashboard { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        if self.props.sessions.is_empty() {
+            return html! {};
+        }
+        let lang = self.props.language;
+        html! {
+            <table class="sessions">
+                <thead>
+                    <tr>
+                        <th></th>
+                        { for self.props.sessions.iter().enumerate().map(|(idx, session)| html! {
+                            <th>
+                                { &session.saved_at }
+                                { for session.seed.map(|seed| html! {
+                                    <small class="session-seed">{ lang.fmt("session_seed", &[&seed]) }</small>
+                                }) }
+                                <button class="destroy"
+                                        aria-label=lang.t("remove")
+                                        onclick=self.props.on_remove.reform(move |_| idx)>{ "×" }</button>
+                            </th>
+                        }) }
+                    </tr>
+                </thead>
+                <tbody>
+                    { self.view_metric("win_rate", |s| s.win_rate(), |v| format!("{:.0}%", v * 100.0)) }
+                    { self.view_metric("average_storm", |s| s.average_storm(), |v| format!("{:.1}", v)) }
+                    { self.view_metric("longest_streak", |s| s.longest_streak as f64, |v| format!("{}", v)) }
+                    { self.view_metric("session_length", |s| s.duration as f64, |v| format_clock(v as u64)) }
+                    { self.view_metric("flips_per_minute", |s| s.flips_per_minute(), |v| format!("{:.1}", v)) }
+                </tbody>
+            </table>
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::persisted::PersistedState;
+use crate::session::{ImportError, SessionExport};
+use yew::format::{Json, Text};
+use yew::prelude::*;
+use yew::services::reader::{File, FileData, ReaderService, ReaderTask};
+
+/// What an imported file held.
+pub enum Import {
+    /// A session exported in full.
+    Session(Box<SessionExport>),
+    /// The games kept between visits, of which the one in front is taken.
+    Setup(PersistedState),
+}
+
+/// Reads an exported session, or a stored setup, from a file picked by
+/// the user and hands it on once it has been checked.
+pub struct SessionImport {
+    link: ComponentLink<Self>,
+    props: Props,
+    reader: ReaderService,
+    _task: Option<ReaderTask>,
+    error: Option<ImportError>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub on_import: Callback<Import>,
+}
+
+pub enum Msg {
+    Read(File),
+    Loaded(FileData),
+    Nope,
+}
+
+/// Parses `text` as a session, or failing that as a setup, and checks it.
+fn parse(text: Text) -> Result<Import, ImportError> {
+    let text = text.map_err(|_| ImportError::Unreadable)?;
+    if let Json(Ok(session)) = Json::<Result<SessionExport, _>>::from(Ok(text.clone())) {
+        session.validate()?;
+        return Ok(Import::Session(Box::new(session)));
+    }
+    match Json::<Result<PersistedState, _>>::from(Ok(text)) {
+        Json(Ok(state)) => Ok(Import::Setup(state.migrate()?)),
+        Json(Err(_)) => Err(ImportError::Unreadable),
+    }
+}
+
+impl Component for SessionImport {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        SessionImport {
+            link,
+            props,
+            reader: ReaderService::new(),
+            _task: None,
+            error: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Read(file) => {
+                let callback = self.link.callback(Msg::Loaded);
+                self._task = self.reader.read_file(file, callback).ok();
+                return false;
+            }
+            Msg::Loaded(data) => {
+                self._task = None;
+                let text: Text = String::from_utf8(data.content).map_err(Into::into);
+                match parse(text) {
+                    Ok(import) => {
+                        self.error = None;
+                        self.props.on_import.emit(import);
+                    }
+                    Err(error) => self.error = Some(error),
+                }
+            }
+            Msg::Nope => return false,
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <>
+                <label class="session-import">
+                    { lang.t("import_session") }
+                    <input type="file" accept="application/json,.json"
+                           onchange=self.link.callback(|e: ChangeData| match e {
+                               ChangeData::Files(files) => files.get(0).map(Msg::Read).unwrap_or(Msg::Nope),
+                               _ => Msg::Nope,
+                           }) />
+                </label>
+                { for self.error.map(|error| html! {
+                    <p class="error" role="alert">{ lang.t(error.as_ref()) }</p>
+                }) }
+            </>
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use crate::card::FlipCard;
+use crate::cleanup::Reset;
+use crate::coin_flip::WinCondition;
+use crate::coin_skin::CoinFaces;
+use crate::i18n::Language;
+use crate::theme::Theme;
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, Display, EnumIter, EnumString};
+use wasm_bindgen::JsCast;
+
+/// Smallest and largest text scale, in percent.
+pub const FONT_SCALE_RANGE: (u32, u32) = (75, 200);
+
+/// User preferences, persisted separately from game data.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub language: Language,
+    pub motion: Motion,
+    /// Mark won and lost flips with shapes as well as colour.
+    pub outcome_icons: bool,
+    /// Fill lost flips in charts with a hatch pattern.
+    pub outcome_patterns: bool,
+    /// Vibrate on flips, on phones that can.
+    pub haptics: bool,
+    /// Read flip, roll and cast results aloud.
+    pub speak_results: bool,
+    /// Streak that sets off a celebration, or none.
+    pub celebration_streak: Option<usize>,
+    pub coin_faces: CoinFaces,
+    /// Cards whose flips are won differently from how they are printed,
+    /// such as older printings where an opponent calls.
+    pub win_conditions: Vec<(FlipCard, WinCondition)>,
+    /// What lasts past end of turn at this table, such as floating mana
+    /// with Upwelling out.
+    pub kept_at_end_of_turn: Vec<Reset>,
+    /// Text size in percent of the browser default.
+    pub font_scale: u32,
+    /// Whether the onboarding tour has been shown.
+    pub tour_seen: bool,
+    pub hidden_panels: Vec<Panel>,
+    pub theme: Theme,
+    /// Themes the user saved from the theme editor.
+    pub saved_themes: Vec<Theme>,
+    /// Flips kept in the history, or all of them when unset.
+    pub max_flips: Option<usize>,
+    /// Saved sessions kept, or all of them when unset.
+    pub max_sessions: Option<usize>,
+    /// Download whatever pruning is about to drop.
+    pub archive_pruned: bool,
+    /// Whether `script` runs on flips, rolls and casts.
+    pub scripts_enabled: bool,
+    pub script: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            language: Language::default(),
+            motion: Motion::default(),
+            outcome_icons: false,
+            outcome_patterns: false,
+            haptics: false,
+            speak_results: false,
+            celebration_streak: None,
+            coin_faces: CoinFaces::default(),
+            win_conditions: Vec::new(),
+            kept_at_end_of_turn: Vec::new(),
+            font_scale: 100,
+            tour_seen: false,
+            hidden_panels: vec![Panel::Chat, Panel::Scripts, Panel::Debug],
+            theme: Theme::default(),
+            saved_themes: Vec::new(),
+            max_flips: None,
+            max_sessions: None,
+            archive_pruned: false,
+            scripts_enabled: false,
+            script: String::new(),
+        }
+    }
+}
+
+/// Whether coin animations play. `as_ref` gives the option's text key.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumIter,
+    EnumString,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub enum Motion {
+    /// Follow the browser's `prefers-reduced-motion` setting.
+    #[default]
+    #[strum(serialize = "motion_system")]
+    System,
+    #[strum(serialize = "motion_reduced")]
+    Reduced,
+    #[strum(serialize = "motion_full")]
+    Full,
+}
+
+/// Panels that can be hidden from the layout. `as_ref` gives the panel's
+/// text key.
+#[derive(AsRefStr, Clone, Copy, Debug, EnumIter, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Panel {
+    #[strum(serialize = "panel_card_of_the_day")]
+    CardOfTheDay,
+    #[strum(serialize = "panel_board")]
+    Board,
+    #[strum(serialize = "panel_life")]
+    Life,
+    #[strum(serialize = "panel_opponents")]
+    Opponents,
+    #[strum(serialize = "panel_turn_timer")]
+    TurnTimer,
+    #[strum(serialize = "panel_creatures")]
+    Creatures,
+    #[strum(serialize = "panel_hands")]
+    Hands,
+    #[strum(serialize = "panel_player_counters")]
+    PlayerCounters,
+    #[strum(serialize = "panel_mana")]
+    Mana,
+    #[strum(serialize = "panel_tokens")]
+    Tokens,
+    #[strum(serialize = "panel_spells")]
+    Spells,
+    #[strum(serialize = "panel_archaeologist")]
+    Archaeologist,
+    #[strum(serialize = "panel_upkeep")]
+    Upkeep,
+    #[strum(serialize = "panel_custom_cards")]
+    CustomCards,
+    #[strum(serialize = "panel_stats")]
+    Stats,
+    #[strum(serialize = "panel_card_stats")]
+    CardStats,
+    #[strum(serialize = "panel_run_lengths")]
+    RunLengths,
+    #[strum(serialize = "panel_dice")]
+    Dice,
+    #[strum(serialize = "panel_attractions")]
+    Attractions,
+    #[strum(serialize = "panel_unfinity")]
+    Unfinity,
+    #[strum(serialize = "panel_history")]
+    History,
+    #[strum(serialize = "panel_resolution")]
+    Resolution,
+    #[strum(serialize = "panel_sessions")]
+    Sessions,
+    #[strum(serialize = "panel_records")]
+    Records,
+    #[strum(serialize = "panel_simulator")]
+    Simulator,
+    #[strum(serialize = "panel_mulligan")]
+    Mulligan,
+    #[strum(serialize = "panel_draw_odds")]
+    DrawOdds,
+    #[strum(serialize = "panel_seed_explorer")]
+    SeedExplorer,
+    #[strum(serialize = "panel_chat")]
+    Chat,
+    #[strum(serialize = "panel_scripts")]
+    Scripts,
+    #[strum(serialize = "panel_debug")]
+    Debug,
+}
+
+impl Panel {
+    /// Whether this build has the panel. The chat needs the `networking`
+    /// feature.
+    pub fn is_built(self) -> bool {
+        self != Panel::Chat || cfg!(feature = "networking")
+    }
+}
+
+impl Settings {
+    pub fn shows(&self, panel: Panel) -> bool {
+        !self.hidden_panels.contains(&panel)
+    }
+
+    pub fn toggle_panel(&mut self, panel: Panel) {
+        if self.shows(panel) {
+            self.hidden_panels.push(panel);
+        } else {
+            self.hidden_panels.retain(|&p| p != panel);
+        }
+    }
+
+    /// What counts as winning `card`'s flips at this table.
+    pub fn win_condition(&self, card: FlipCard) -> WinCondition {
+        self.win_conditions
+            .iter()
+            .find(|&&(c, _)| c == card)
+            .map_or_else(|| card.win_condition(), |&(_, win)| win)
+    }
+
+    /// Wins `card`'s flips as `win` says, forgetting the override when
+    /// that is how the card is printed.
+    pub fn set_win_condition(&mut self, card: FlipCard, win: WinCondition) {
+        self.win_conditions.retain(|&(c, _)| c != card);
+        if win != card.win_condition() {
+            self.win_conditions.push((card, win));
+        }
+    }
+
+    pub fn reduced_motion(&self) -> bool {
+        match self.motion {
+            Motion::System => prefers_reduced_motion(),
+            Motion::Reduced => true,
+            Motion::Full => false,
+        }
+    }
+}
+
+impl Settings {
+    /// Publishes the text scale as `--font-scale` on the document root so
+    /// the stylesheet can size everything from it.
+    pub fn apply_font_scale(&self) {
+        let (min, max) = FONT_SCALE_RANGE;
+        let scale = f64::from(self.font_scale.clamp(min, max)) / 100.0;
+        if let Some(style) = root_style() {
+            let _ = style.set_property("--font-scale", &scale.to_string());
+        }
+    }
+}
+
+/// Inline style of the document root, where custom properties are set.
+pub fn root_style() -> Option<web_sys::CssStyleDeclaration> {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.document_element())
+        .and_then(|root| root.dyn_into::<web_sys::HtmlElement>().ok())
+        .map(|root| root.style())
+}
+
+fn prefers_reduced_motion() -> bool {
+    web_sys::window()
+        .and_then(|window| {
+            window
+                .match_media("(prefers-reduced-motion: reduce)")
+                .ok()
+                .flatten()
+        })
+        .is_some_and(|query| query.matches())
+}
@@ -0,0 +1,320 @@
+use crate::card::FlipCard;
+use crate::cleanup::Reset;
+use crate::coin_flip::WinCondition;
+use crate::coin_skin::{CoinFaces, CoinSkinPicker};
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::settings::{Motion, Panel, Settings, FONT_SCALE_RANGE};
+use crate::theme::{Theme, ThemeEditor};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+/// Edits the user's preferences, handing each change back as a whole new
+/// set of settings for the app to apply and save.
+pub struct SettingsPanel {
+    link: ComponentLink<Self>,
+    props: Props,
+    /// Made once so the editors below only render again when their data
+    /// changes.
+    on_theme: Callback<Theme>,
+    on_save_themes: Callback<Vec<Theme>>,
+    on_coin_faces: Callback<CoinFaces>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub settings: Settings,
+    pub on_change: Callback<Settings>,
+    pub on_start_tour: Callback<()>,
+}
+
+pub enum Msg {
+    SetLanguage(Language),
+    StartTour,
+    TogglePanel(Panel),
+    SetMotion(Motion),
+    ToggleOutcomeIcons,
+    ToggleOutcomePatterns,
+    ToggleHaptics,
+    ToggleSpeakResults,
+    SetCelebrationStreak(String),
+    SetFontScale(String),
+    SetMaxFlips(String),
+    SetMaxSessions(String),
+    ToggleArchivePruned,
+    SetWinCondition(FlipCard, WinCondition),
+    ToggleReset(Reset),
+    SetTheme(Theme),
+    SaveThemes(Vec<Theme>),
+    SetCoinFaces(CoinFaces),
+    Nope,
+}
+
+impl SettingsPanel {
+    fn edit(&self, update: impl FnOnce(&mut Settings)) {
+        let mut settings = self.props.settings.clone();
+        update(&mut settings);
+        self.props.on_change.emit(settings);
+    }
+
+    fn view_layout(&self) -> Html {
+        let settings = &self.props.settings;
+        let lang = settings.language;
+        html! {
+            <fieldset class="layout">
+                <legend>{ lang.t("layout") }</legend>
+                { for Panel::iter().filter(|panel| panel.is_built()).map(|panel| html! {
+                    <label>
+                        <input type="checkbox"
+                               checked=settings.shows(panel)
+                               onclick=self.link.callback(move |_| Msg::TogglePanel(panel)) />
+                        { lang.t(panel.as_ref()) }
+                    </label>
+                }) }
+            </fieldset>
+        }
+    }
+
+    fn view_win_conditions(&self) -> Html {
+        let settings = &self.props.settings;
+        let lang = settings.language;
+        html! {
+            <fieldset class="win-conditions">
+                <legend>{ lang.t("win_conditions") }</legend>
+                { for FlipCard::iter().filter(|&card| card != FlipCard::Homebrew).map(|card| {
+                    let current = settings.win_condition(card);
+                    html! {
+                        <label>
+                            { card }
+                            <select onchange=self.link.callback(move |e: ChangeData| match e {
+                                ChangeData::Select(select) => WinCondition::from_str(&select.value())
+                                    .map(|win| Msg::SetWinCondition(card, win))
+                                    .unwrap_or(Msg::Nope),
+                                _ => Msg::Nope,
+                            })>
+                                { for WinCondition::iter().map(|w| html! {
+                                    <option value=w selected=w == current>{ lang.t(w.as_ref()) }</option>
+                                }) }
+                            </select>
+                        </label>
+                    }
+                }) }
+            </fieldset>
+        }
+    }
+
+    /// What ends with the turn, each of which can be kept instead.
+    fn view_cleanup(&self) -> Html {
+        let settings = &self.props.settings;
+        let lang = settings.language;
+        html! {
+            <fieldset class="cleanup">
+                <legend>{ lang.t("end_of_turn_resets") }</legend>
+                { for Reset::iter().map(|reset| html! {
+                    <label>
+                        <input type="checkbox"
+                               checked=!settings.kept_at_end_of_turn.contains(&reset)
+                               onclick=self.link.callback(move |_| Msg::ToggleReset(reset)) />
+                        { lang.t(reset.as_ref()) }
+                    </label>
+                }) }
+            </fieldset>
+        }
+    }
+
+    fn view_retention(&self) -> Html {
+        let settings = &self.props.settings;
+        let lang = settings.language;
+        let max_flips = settings
+            .max_flips
+            .map(|n| n.to_string())
+            .unwrap_or_default();
+        let max_sessions = settings
+            .max_sessions
+            .map(|n| n.to_string())
+            .unwrap_or_default();
+        html! {
+            <fieldset class="retention">
+                <legend>{ lang.t("retention") }</legend>
+                <label>
+                    { lang.t("max_flips") }
+                    <input type="number" min="1"
+                           placeholder=lang.t("unlimited")
+                           value=max_flips
+                           oninput=self.link.callback(|e: InputData| Msg::SetMaxFlips(e.value)) />
+                </label>
+                <label>
+                    { lang.t("max_sessions") }
+                    <input type="number" min="1"
+                           placeholder=lang.t("unlimited")
+                           value=max_sessions
+                           oninput=self.link.callback(|e: InputData| Msg::SetMaxSessions(e.value)) />
+                </label>
+                <label>
+                    <input type="checkbox"
+                           checked=settings.archive_pruned
+                           onclick=self.link.callback(|_| Msg::ToggleArchivePruned) />
+                    { lang.t("archive_pruned") }
+                </label>
+            </fieldset>
+        }
+    }
+
+    fn view_accessibility(&self) -> Html {
+        let settings = &self.props.settings;
+        let lang = settings.language;
+        html! {
+            <fieldset class="accessibility">
+                <legend>{ lang.t("accessibility") }</legend>
+                <label>
+                    { lang.t("motion") }
+                    <select onchange=self.link.callback(|e: ChangeData| match e {
+                        ChangeData::Select(select) => Motion::from_str(&select.value())
+                            .map(Msg::SetMotion)
+                            .unwrap_or(Msg::Nope),
+                        _ => Msg::Nope,
+                    })>
+                        { for Motion::iter().map(|m| html! {
+                            <option value=m selected=m == settings.motion>{ lang.t(m.as_ref()) }</option>
+                        }) }
+                    </select>
+                </label>
+                <label>
+                    <input type="checkbox"
+                           checked=settings.outcome_icons
+                           onclick=self.link.callback(|_| Msg::ToggleOutcomeIcons) />
+                    { lang.t("outcome_icons") }
+                </label>
+                <label>
+                    <input type="checkbox"
+                           checked=settings.outcome_patterns
+                           onclick=self.link.callback(|_| Msg::ToggleOutcomePatterns) />
+                    { lang.t("outcome_patterns") }
+                </label>
+                <label>
+                    <input type="checkbox"
+                           checked=settings.haptics
+                           onclick=self.link.callback(|_| Msg::ToggleHaptics) />
+                    { lang.t("haptics") }
+                </label>
+                <label>
+                    <input type="checkbox"
+                           checked=settings.speak_results
+                           onclick=self.link.callback(|_| Msg::ToggleSpeakResults) />
+                    { lang.t("speak_results") }
+                </label>
+                <label>
+                    { lang.t("celebration_streak") }
+                    <input type="number" min="1"
+                           placeholder=lang.t("off")
+                           value=settings.celebration_streak.map(|n| n.to_string()).unwrap_or_default()
+                           oninput=self.link.callback(|e: InputData| Msg::SetCelebrationStreak(e.value)) />
+                </label>
+                <label>
+                    { lang.fmt("font_scale", &[&settings.font_scale]) }
+                    <input type="range"
+                           min=FONT_SCALE_RANGE.0
+                           max=FONT_SCALE_RANGE.1
+                           step="5"
+                           value=settings.font_scale
+                           oninput=self.link.callback(|e: InputData| Msg::SetFontScale(e.value)) />
+                </label>
+            </fieldset>
+        }
+    }
+}
+
+impl Component for SettingsPanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        SettingsPanel {
+            on_theme: link.callback(Msg::SetTheme),
+            on_save_themes: link.callback(Msg::SaveThemes),
+            on_coin_faces: link.callback(Msg::SetCoinFaces),
+            link,
+            props,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::SetLanguage(language) => self.edit(|s| s.language = language),
+            Msg::StartTour => self.props.on_start_tour.emit(()),
+            Msg::TogglePanel(panel) => self.edit(|s| s.toggle_panel(panel)),
+            Msg::SetMotion(motion) => self.edit(|s| s.motion = motion),
+            Msg::ToggleOutcomeIcons => self.edit(|s| s.outcome_icons = !s.outcome_icons),
+            Msg::ToggleOutcomePatterns => self.edit(|s| s.outcome_patterns = !s.outcome_patterns),
+            Msg::ToggleHaptics => self.edit(|s| s.haptics = !s.haptics),
+            Msg::ToggleSpeakResults => self.edit(|s| s.speak_results = !s.speak_results),
+            Msg::SetCelebrationStreak(val) => {
+                self.edit(|s| s.celebration_streak = val.parse().ok().filter(|&n| n > 0))
+            }
+            Msg::SetFontScale(val) => {
+                if let Ok(scale) = val.parse() {
+                    self.edit(|s| s.font_scale = scale);
+                }
+            }
+            Msg::SetMaxFlips(val) => self.edit(|s| s.max_flips = val.parse().ok()),
+            Msg::SetMaxSessions(val) => self.edit(|s| s.max_sessions = val.parse().ok()),
+            Msg::ToggleArchivePruned => self.edit(|s| s.archive_pruned = !s.archive_pruned),
+            Msg::SetWinCondition(card, win) => self.edit(|s| s.set_win_condition(card, win)),
+            Msg::ToggleReset(reset) => self.edit(|s| {
+                let kept = &mut s.kept_at_end_of_turn;
+                if kept.contains(&reset) {
+                    kept.retain(|&r| r != reset);
+                } else {
+                    kept.push(reset);
+                }
+            }),
+            Msg::SetTheme(theme) => self.edit(|s| s.theme = theme),
+            Msg::SaveThemes(themes) => self.edit(|s| s.saved_themes = themes),
+            Msg::SetCoinFaces(faces) => self.edit(|s| s.coin_faces = faces),
+            Msg::Nope => {}
+        }
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let settings = &self.props.settings;
+        let lang = settings.language;
+        html! {
+            <fieldset class="settings">
+                <legend>{ lang.t("settings") }</legend>
+                <label>
+                    { lang.t("language") }
+                    <select onchange=self.link.callback(|e: ChangeData| match e {
+                        ChangeData::Select(select) => Language::from_str(&select.value())
+                            .map(Msg::SetLanguage)
+                            .unwrap_or(Msg::Nope),
+                        _ => Msg::Nope,
+                    })>
+                        { for Language::iter().map(|l| html! {
+                            <option value=l selected=l == lang>{ l }</option>
+                        }) }
+                    </select>
+                </label>
+                <button onclick=self.link.callback(|_| Msg::StartTour)>{ lang.t("tour_show") }</button>
+                { self.view_layout() }
+                { self.view_accessibility() }
+                { self.view_retention() }
+                { self.view_win_conditions() }
+                { self.view_cleanup() }
+                <ThemeEditor language=lang
+                             theme=settings.theme.clone()
+                             saved=settings.saved_themes.clone()
+                             on_change=self.on_theme.clone()
+                             on_save=self.on_save_themes.clone() />
+                <CoinSkinPicker language=lang
+                                faces=settings.coin_faces.clone()
+                                on_change=self.on_coin_faces.clone() />
+            </fieldset>
+        }
+    }
+}
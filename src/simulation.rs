@@ -0,0 +1,433 @@
+use crate::coin_flip::{CoinBits, ConfigError, WinCondition};
+use crate::packed::Layout;
+use crate::trigger::{CastChain, SpellCounts};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde_derive::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use strum_macros::{AsRefStr, Display, EnumIter, EnumString};
+
+/// Percentiles given for every metric of a report.
+pub const PERCENTILES: [u32; 5] = [5, 25, 50, 75, 95];
+
+/// Bins the share of heads is counted in, above the one for no heads.
+pub const RATIO_BINS: usize = 10;
+
+/// The board a simulated spell is cast onto.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Scenario {
+    pub krarks: usize,
+    pub thumbs: usize,
+    /// Times a returned spell is cast again, at most.
+    pub max_recasts: usize,
+}
+
+/// Board and sample size for a batch of simulated casts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimulationConfig {
+    pub scenario: Scenario,
+    pub trials: usize,
+}
+
+impl SimulationConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.trials == 0 {
+            return Err(ConfigError::NoTrials);
+        }
+        Ok(())
+    }
+}
+
+/// What is measured for each simulated spell or cast. `as_ref` gives the
+/// metric's text key.
+#[derive(AsRefStr, Clone, Copy, Debug, EnumIter, PartialEq, Serialize, Deserialize)]
+pub enum Metric {
+    /// Copies over every cast of a spell.
+    #[strum(serialize = "copies_per_spell")]
+    Copies,
+    /// Times a spell was cast, counting recasts after bounces.
+    #[strum(serialize = "casts_per_spell")]
+    Casts,
+    /// Won flips before the first lost one, for each cast.
+    #[strum(serialize = "chain_length")]
+    Chain,
+}
+
+/// Summary of one whole-number metric over every trial.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Distribution {
+    /// `histogram[k]` is the number of trials where the metric was `k`.
+    pub histogram: Vec<usize>,
+    pub mean: f64,
+    pub median: usize,
+    pub variance: f64,
+    /// The metric at each of [`PERCENTILES`], in order.
+    pub percentiles: Vec<(u32, usize)>,
+}
+
+impl Distribution {
+    pub fn new(histogram: Vec<usize>) -> Self {
+        let trials = histogram.iter().sum::<usize>().max(1) as f64;
+        let mean = histogram
+            .iter()
+            .enumerate()
+            .map(|(k, &n)| k as f64 * n as f64)
+            .sum::<f64>()
+            / trials;
+        let variance = histogram
+            .iter()
+            .enumerate()
+            .map(|(k, &n)| (k as f64 - mean).powi(2) * n as f64)
+            .sum::<f64>()
+            / trials;
+        let percentiles = PERCENTILES
+            .iter()
+            .map(|&p| (p, percentile(&histogram, p)))
+            .collect();
+        Distribution {
+            median: percentile(&histogram, 50),
+            histogram,
+            mean,
+            variance,
+            percentiles,
+        }
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+
+    /// The metric at percentile `p`, if `p` is one of [`PERCENTILES`].
+    pub fn percentile(&self, p: u32) -> Option<usize> {
+        self.percentiles
+            .iter()
+            .find(|&&(q, _)| q == p)
+            .map(|&(_, value)| value)
+    }
+}
+
+/// Nearest-rank percentile of the values counted in `histogram`, or 0 if
+/// it counts no trials.
+fn percentile(histogram: &[usize], p: u32) -> usize {
+    let trials: usize = histogram.iter().sum();
+    if trials == 0 {
+        return 0;
+    }
+    let rank = (trials * p as usize).div_ceil(100).max(1);
+    let mut seen = 0;
+    for (value, &n) in histogram.iter().enumerate() {
+        seen += n;
+        if seen >= rank {
+            return value;
+        }
+    }
+    histogram.len().saturating_sub(1)
+}
+
+/// Aggregate outcome of a batch of simulated spells, each cast until it
+/// sticks or runs out of recasts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimulationReport {
+    /// Spells simulated.
+    pub trials: usize,
+    pub copies: Distribution,
+    pub casts: Distribution,
+    pub chain: Distribution,
+    /// Fraction of casts where at least one trigger returned the spell.
+    pub bounce_rate: f64,
+    /// Fraction of casts that made at least one copy.
+    pub copy_rate: f64,
+    /// Spells by the share of their flips that came up heads, or were
+    /// kept as heads, in steps of `1 / RATIO_BINS`.
+    pub heads_ratio: Vec<usize>,
+    /// Fraction of every flip that came up heads.
+    pub heads_share: f64,
+}
+
+/// Sum of the values counted in `histogram`.
+fn total(histogram: &[usize]) -> usize {
+    histogram.iter().enumerate().map(|(k, &n)| k * n).sum()
+}
+
+impl SimulationReport {
+    pub fn metric(&self, metric: Metric) -> &Distribution {
+        match metric {
+            Metric::Copies => &self.copies,
+            Metric::Casts => &self.casts,
+            Metric::Chain => &self.chain,
+        }
+    }
+
+    /// Spells cast and copied over every trial.
+    pub fn counts(&self) -> SpellCounts {
+        SpellCounts {
+            casts: total(&self.casts.histogram),
+            copies: total(&self.copies.histogram),
+        }
+    }
+
+    /// Copies made by one cast, on average.
+    pub fn copies_per_cast(&self) -> f64 {
+        let counts = self.counts();
+        counts.copies as f64 / counts.casts.max(1) as f64
+    }
+}
+
+/// Running totals of simulated spells, so a batch can be run in pieces.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Tally {
+    pub trials: usize,
+    /// Casts where a trigger returned the spell.
+    pub bounces: usize,
+    /// Casts that made at least one copy.
+    pub copied: usize,
+    /// Spells by number of copies over all their casts.
+    pub copies: Vec<usize>,
+    /// Spells by number of times cast.
+    pub casts: Vec<usize>,
+    /// Casts by number of flips won before the first loss.
+    pub chains: Vec<usize>,
+    /// Flips that came up heads, and every flip.
+    pub heads: usize,
+    pub flips: usize,
+    /// Spells by share of heads, in steps of `1 / RATIO_BINS`.
+    pub heads_ratio: Vec<usize>,
+}
+
+/// Counts one more trial where a metric came out as `value`.
+fn count(histogram: &mut Vec<usize>, value: usize) {
+    if histogram.len() <= value {
+        histogram.resize(value + 1, 0);
+    }
+    histogram[value] += 1;
+}
+
+impl Tally {
+    /// Casts `trials` more spells, each again whenever Krark returns it,
+    /// as [`CastChain::simulate`] does, flipping once per Krark trigger.
+    pub fn run<R: Rng>(&mut self, scenario: &Scenario, trials: usize, rng: &mut R) {
+        let mut coins = CoinBits::new(rng);
+        for _ in 0..trials {
+            let chain =
+                CastChain::simulate_with(WinCondition::YouCall, scenario.max_recasts, || {
+                    (0..scenario.krarks)
+                        .map(|_| coins.flip(scenario.thumbs))
+                        .collect()
+                });
+            for cast in &chain.casts {
+                if cast.bounced() {
+                    self.bounces += 1;
+                }
+                if cast.copies() > 0 {
+                    self.copied += 1;
+                }
+                count(&mut self.chains, cast.chain_length());
+            }
+            count(&mut self.copies, chain.copies());
+            count(&mut self.casts, chain.casts.len());
+            // Heads is the side called, so every won flip was heads.
+            let flips = chain.casts.len() * scenario.krarks;
+            if flips > 0 {
+                let heads = chain.copies();
+                self.heads += heads;
+                self.flips += flips;
+                let bin = (heads * RATIO_BINS + flips / 2) / flips;
+                count(&mut self.heads_ratio, bin);
+            }
+        }
+        self.trials += trials;
+    }
+
+    pub fn report(&self) -> SimulationReport {
+        let casts = total(&self.casts).max(1) as f64;
+        SimulationReport {
+            trials: self.trials,
+            copies: Distribution::new(self.copies.clone()),
+            casts: Distribution::new(self.casts.clone()),
+            chain: Distribution::new(self.chains.clone()),
+            bounce_rate: self.bounces as f64 / casts,
+            copy_rate: self.copied as f64 / casts,
+            heads_ratio: self.heads_ratio.clone(),
+            heads_share: self.heads as f64 / self.flips.max(1) as f64,
+        }
+    }
+}
+
+/// Casts `trials` spells onto `scenario`, each until it sticks, flipping
+/// once per Krark trigger. The UI's simulator and anything embedding the engine share
+/// this entry point.
+pub fn simulate<R: Rng>(scenario: &Scenario, trials: usize, rng: &mut R) -> SimulationReport {
+    let mut tally = Tally::default();
+    tally.run(scenario, trials, rng);
+    tally.report()
+}
+
+/// The configuration value varied by a sweep.
+#[derive(
+    AsRefStr, Clone, Copy, Debug, Display, EnumIter, EnumString, PartialEq, Serialize, Deserialize,
+)]
+pub enum SweepParameter {
+    #[strum(serialize = "thumbs")]
+    Thumbs,
+    #[strum(serialize = "triggers")]
+    Krarks,
+}
+
+impl SweepParameter {
+    fn apply(self, scenario: &mut Scenario, value: usize) {
+        match self {
+            SweepParameter::Thumbs => scenario.thumbs = value,
+            SweepParameter::Krarks => scenario.krarks = value,
+        }
+    }
+}
+
+/// Runs one simulation per value of `parameter`, holding the rest of `base`
+/// fixed.
+pub fn sweep<R: Rng>(
+    base: &SimulationConfig,
+    parameter: SweepParameter,
+    values: RangeInclusive<usize>,
+    rng: &mut R,
+) -> Vec<(usize, SimulationReport)> {
+    values
+        .map(|value| {
+            let mut scenario = base.scenario.clone();
+            parameter.apply(&mut scenario, value);
+            (value, simulate(&scenario, base.trials, rng))
+        })
+        .collect()
+}
+
+/// A sweep run a chunk of trials at a time. Between chunks it can be
+/// stored and picked up later, continuing the same random sequence.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SweepJob {
+    pub base: SimulationConfig,
+    pub parameter: SweepParameter,
+    pub values: Vec<usize>,
+    /// Index into `values` of the value being simulated.
+    pub next: usize,
+    /// Trials so far for the value being simulated.
+    pub tally: Tally,
+    pub results: Vec<(usize, SimulationReport)>,
+    pub seed: u64,
+    /// Position in the generator's stream after the last chunk.
+    pub word_pos: u128,
+}
+
+/// Layout 1 tallied a single cast per trial, so a sweep stored in it
+/// cannot be carried on and is started over.
+impl Layout for SweepJob {
+    const LAYOUT: u32 = 2;
+}
+
+impl SweepJob {
+    pub fn new(
+        base: SimulationConfig,
+        parameter: SweepParameter,
+        values: RangeInclusive<usize>,
+        seed: u64,
+    ) -> Self {
+        SweepJob {
+            base,
+            parameter,
+            values: values.collect(),
+            next: 0,
+            tally: Tally::default(),
+            results: Vec::new(),
+            seed,
+            word_pos: 0,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.next >= self.values.len()
+    }
+
+    /// Fraction of all the sweep's trials run so far.
+    pub fn progress(&self) -> f64 {
+        let total = (self.values.len() * self.base.trials).max(1);
+        (self.next * self.base.trials + self.tally.trials) as f64 / total as f64
+    }
+
+    /// The value being simulated with its trials so far, while it has any.
+    pub fn partial(&self) -> Option<(usize, SimulationReport)> {
+        if self.is_done() || self.tally.trials == 0 {
+            return None;
+        }
+        Some((self.values[self.next], self.tally.report()))
+    }
+
+    /// The board simulated for one value of the swept parameter.
+    pub fn scenario(&self, value: usize) -> Scenario {
+        let mut scenario = self.base.scenario.clone();
+        self.parameter.apply(&mut scenario, value);
+        scenario
+    }
+
+    /// Runs up to `budget` trials, moving on to the next value whenever one
+    /// has all its trials.
+    pub fn step(&mut self, mut budget: usize) {
+        let mut rng = ChaCha12Rng::seed_from_u64(self.seed);
+        rng.set_word_pos(self.word_pos);
+        while !self.is_done() {
+            let value = self.values[self.next];
+            let scenario = self.scenario(value);
+            let trials = (self.base.trials - self.tally.trials).min(budget);
+            self.tally.run(&scenario, trials, &mut rng);
+            budget -= trials;
+            if self.tally.trials < self.base.trials {
+                break;
+            }
+            self.results.push((value, self.tally.report()));
+            self.tally = Tally::default();
+            self.next += 1;
+        }
+        self.word_pos = rng.get_word_pos();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_of_nothing_is_zero() {
+        assert_eq!(percentile(&[], 50), 0);
+        assert_eq!(percentile(&[0, 0], 50), 0);
+        assert_eq!(percentile(&[0, 0, 0], 100), 0);
+    }
+
+    #[test]
+    fn percentile_boundaries_take_the_smallest_and_largest_values() {
+        let histogram = [0, 3, 0, 1];
+        assert_eq!(percentile(&histogram, 0), 1);
+        assert_eq!(percentile(&histogram, 1), 1);
+        assert_eq!(percentile(&histogram, 75), 1);
+        assert_eq!(percentile(&histogram, 76), 3);
+        assert_eq!(percentile(&histogram, 100), 3);
+    }
+
+    #[test]
+    fn percentile_uses_the_nearest_rank() {
+        // Ten trials, one at each value.
+        let histogram = [1; 10];
+        assert_eq!(percentile(&histogram, 5), 0);
+        assert_eq!(percentile(&histogram, 10), 0);
+        assert_eq!(percentile(&histogram, 11), 1);
+        assert_eq!(percentile(&histogram, 50), 4);
+        assert_eq!(percentile(&histogram, 95), 9);
+    }
+
+    #[test]
+    fn distribution_statistics() {
+        let distribution = Distribution::new(vec![1, 2, 1]);
+        assert_eq!(distribution.mean, 1.0);
+        assert_eq!(distribution.variance, 0.5);
+        assert_eq!(distribution.median, 1);
+        assert_eq!(distribution.percentile(5), Some(0));
+        assert_eq!(distribution.percentile(95), Some(2));
+        assert_eq!(distribution.percentile(42), None);
+    }
+}
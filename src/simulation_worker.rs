@@ -0,0 +1,53 @@
+use crate::simulation::SweepJob;
+use serde_derive::{Deserialize, Serialize};
+use yew::worker::{Agent, AgentLink, HandlerId, Public};
+
+/// Trials run per request: enough that passing the job back and forth
+/// costs little, few enough that progress shows and a pause or cancel
+/// takes effect promptly.
+const CHUNK_TRIALS: usize = 100_000;
+
+#[derive(Serialize, Deserialize)]
+pub enum Request {
+    /// Runs the next chunk of the sweep.
+    Step(SweepJob),
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum Response {
+    /// The sweep after a chunk, finished or not.
+    Progress(SweepJob),
+}
+
+/// Runs simulated casts off the main thread, so long sweeps don't freeze
+/// the page. It lives in the worker build, loaded from
+/// [`Agent::name_of_resource`].
+pub struct SimulationWorker {
+    link: AgentLink<Self>,
+}
+
+impl Agent for SimulationWorker {
+    type Reach = Public<Self>;
+    type Message = ();
+    type Input = Request;
+    type Output = Response;
+
+    fn create(link: AgentLink<Self>) -> Self {
+        SimulationWorker { link }
+    }
+
+    fn update(&mut self, _msg: Self::Message) {}
+
+    fn handle_input(&mut self, msg: Self::Input, who: HandlerId) {
+        match msg {
+            Request::Step(mut job) => {
+                job.step(CHUNK_TRIALS);
+                self.link.respond(who, Response::Progress(job));
+            }
+        }
+    }
+
+    fn name_of_resource() -> &'static str {
+        "worker.js"
+    }
+}
@@ -0,0 +1,583 @@
+use crate::bitmap_chart::BitmapChart;
+use crate::chart::Chart;
+use crate::coin_flip::{parse_count, ConfigError};
+use crate::histogram::Histogram;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::packed::Packed;
+use crate::probability::CastOdds;
+use crate::simulation::{
+    Distribution, Metric, Scenario, SimulationConfig, SimulationReport, SweepJob, SweepParameter,
+    PERCENTILES, RATIO_BINS,
+};
+use crate::simulation_worker::{Request, Response, SimulationWorker};
+use crate::trigger::MAX_RECASTS;
+use log::*;
+use rand::{thread_rng, Rng};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use yew::agent::{Bridge, Bridged};
+use yew::prelude::*;
+use yew::services::storage::{Area, StorageService};
+
+/// Where an unfinished sweep is checkpointed.
+const SIMULATION_KEY: &str = "krarkinator.simulation";
+
+/// Width of the sweep chart, in SVG user units.
+const CHART_WIDTH: usize = 240;
+/// Height of the sweep chart, in SVG user units.
+const CHART_HEIGHT: usize = 120;
+/// Sweeps longer than this are drawn as a bitmap rather than SVG.
+const SVG_POINT_LIMIT: usize = 200;
+
+/// Runs batches of simulated casts, optionally sweeping one parameter.
+pub struct Simulator {
+    link: ComponentLink<Self>,
+    props: Props,
+    trials: String,
+    /// Times a returned spell is cast again, at most.
+    recasts: String,
+    parameter: SweepParameter,
+    from: String,
+    to: String,
+    storage: Option<StorageService>,
+    /// The latest sweep, finished or not.
+    job: Option<SweepJob>,
+    /// Runs the sweep a chunk at a time, checkpointed between chunks.
+    worker: Box<dyn Bridge<SimulationWorker>>,
+    /// Whether the next chunk is sent once one comes back.
+    running: bool,
+    trials_error: Option<ConfigError>,
+    recasts_error: Option<ConfigError>,
+    /// Result whose full statistics are shown, or the latest when none is
+    /// picked.
+    selected: Option<usize>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub krarks: usize,
+    pub thumbs: usize,
+}
+
+pub enum Msg {
+    UpdateTrials(String),
+    UpdateRecasts(String),
+    SetParameter(SweepParameter),
+    UpdateFrom(String),
+    UpdateTo(String),
+    Select(usize),
+    Run,
+    Progress(Box<SweepJob>),
+    Pause,
+    Resume,
+    Cancel,
+    Nope,
+}
+
+impl Simulator {
+    fn results(&self) -> &[(usize, SimulationReport)] {
+        self.job.as_ref().map_or(&[], |job| &job.results)
+    }
+
+    /// Hands the sweep's next chunk to the worker.
+    fn step(&mut self) {
+        if let Some(job) = &self.job {
+            self.running = true;
+            self.worker.send(Request::Step(job.clone()));
+        }
+    }
+
+    /// Stores the sweep so it survives a reload, or clears the checkpoint
+    /// once it is done.
+    fn checkpoint(&mut self) {
+        let (storage, job) = match (&mut self.storage, &self.job) {
+            (Some(storage), Some(job)) => (storage, job),
+            _ => return,
+        };
+        if job.is_done() {
+            storage.remove(SIMULATION_KEY);
+        } else {
+            storage.store(SIMULATION_KEY, Packed(job));
+        }
+    }
+
+    fn view_error(&self, error: Option<ConfigError>) -> Html {
+        match error {
+            Some(error) => html! {
+                <p class="error" role="alert">{ self.props.language.t(error.as_ref()) }</p>
+            },
+            None => html! {},
+        }
+    }
+
+    /// How many spells to simulate, and how often each may be recast.
+    fn view_counts_inputs(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <>
+                <label>
+                    { lang.t("trials") }
+                    <input type="number" min="1"
+                           value=&self.trials
+                           aria-invalid=self.trials_error.is_some().to_string()
+                           oninput=self.link.callback(|e: InputData| Msg::UpdateTrials(e.value)) />
+                </label>
+                { self.view_error(self.trials_error) }
+                <label>
+                    { lang.t("max_recasts") }
+                    <input type="number" min="0"
+                           value=&self.recasts
+                           aria-invalid=self.recasts_error.is_some().to_string()
+                           oninput=self.link.callback(|e: InputData| Msg::UpdateRecasts(e.value)) />
+                </label>
+                { self.view_error(self.recasts_error) }
+            </>
+        }
+    }
+
+    fn view_progress(&self) -> Html {
+        let lang = self.props.language;
+        let job = match &self.job {
+            Some(job) if !job.is_done() => job,
+            _ => return html! {},
+        };
+        let percent = format!("{:.0}%", job.progress() * 100.0);
+        html! {
+            <div class="simulation-progress">
+                <progress max="1" value=job.progress() aria-label=lang.t("simulation_progress") />
+                <span>{ percent }</span>
+                { if self.running {
+                    html! { <button onclick=self.link.callback(|_| Msg::Pause)>{ lang.t("pause") }</button> }
+                } else {
+                    html! { <button onclick=self.link.callback(|_| Msg::Resume)>{ lang.t("resume") }</button> }
+                } }
+                <button onclick=self.link.callback(|_| Msg::Cancel)>{ lang.t("cancel_simulation") }</button>
+            </div>
+        }
+    }
+
+    /// The sweep range, or just the board's current value when no range is
+    /// given.
+    fn range(&self) -> (usize, usize) {
+        let current = match self.parameter {
+            SweepParameter::Thumbs => self.props.thumbs,
+            SweepParameter::Krarks => self.props.krarks,
+        };
+        let from = self.from.parse().unwrap_or(current);
+        let to = self.to.parse().unwrap_or(from);
+        (from, to.max(from))
+    }
+
+    fn view_results(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <table class="sweep-results">
+                <thead>
+                    <tr>
+                        <th>{ lang.t(self.parameter.as_ref()) }</th>
+                        <th>{ lang.t("copies_per_spell") }</th>
+                        <th>{ lang.t("median_copies") }</th>
+                        <th>{ lang.t("copies_spread") }</th>
+                        <th>{ lang.t("casts_per_spell") }</th>
+                        <th>{ lang.t("chain_length") }</th>
+                        <th>{ lang.t("bounce_rate") }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for self.results().iter().enumerate().map(|(idx, (value, report))| html! {
+                        <tr class=if self.selected == Some(idx) { "selected" } else { "" }>
+                            <td>
+                                <button onclick=self.link.callback(move |_| Msg::Select(idx))>{ value }</button>
+                            </td>
+                            <td>{ format!("{:.3}", report.copies.mean) }</td>
+                            <td>{ report.copies.median }</td>
+                            <td>{ spread(&report.copies) }</td>
+                            <td>{ format!("{:.3}", report.casts.mean) }</td>
+                            <td>{ format!("{:.3}", report.chain.mean) }</td>
+                            <td>{ format!("{:.1}%", report.bounce_rate * 100.0) }</td>
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+        }
+    }
+
+    /// The result picked out, or else the value being simulated as its
+    /// trials come in, or else the latest result.
+    fn shown(&self) -> Option<(usize, SimulationReport)> {
+        let latest = || {
+            self.job
+                .as_ref()
+                .and_then(SweepJob::partial)
+                .or_else(|| self.results().last().cloned())
+        };
+        match self.selected {
+            Some(idx) => self.results().get(idx).cloned(),
+            None => latest(),
+        }
+    }
+
+    /// Every statistic of one result, with its histograms.
+    fn view_details(&self) -> Html {
+        let lang = self.props.language;
+        let (value, report) = match self.shown() {
+            Some(shown) => shown,
+            None => return html! {},
+        };
+        html! {
+            <div class="simulation-details">
+                <table class="simulation-stats">
+                    <caption>
+                        { lang.fmt("simulation_details", &[&lang.t(self.parameter.as_ref()), &value, &report.trials]) }
+                    </caption>
+                    <thead>
+                        <tr>
+                            <th></th>
+                            <th>{ lang.t("mean") }</th>
+                            <th>{ lang.t("std_dev") }</th>
+                            <th>{ lang.t("variance") }</th>
+                            { for PERCENTILES.iter().map(|p| html! { <th>{ lang.fmt("percentile", &[p]) }</th> }) }
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for Metric::iter().map(|metric| {
+                            let distribution = report.metric(metric);
+                            html! {
+                                <tr>
+                                    <th>{ lang.t(metric.as_ref()) }</th>
+                                    <td>{ format!("{:.3}", distribution.mean) }</td>
+                                    <td>{ format!("{:.3}", distribution.std_dev()) }</td>
+                                    <td>{ format!("{:.3}", distribution.variance) }</td>
+                                    { for distribution.percentiles.iter().map(|(_, v)| html! { <td>{ v }</td> }) }
+                                </tr>
+                            }
+                        }) }
+                    </tbody>
+                </table>
+                { self.view_odds(value, &report) }
+                { self.view_counts(&report) }
+                { self.view_histograms(&report) }
+            </div>
+        }
+    }
+
+    /// Histograms of copies, casts, chain lengths and the share of heads.
+    fn view_histograms(&self, report: &SimulationReport) -> Html {
+        let lang = self.props.language;
+        let copy_labels = (0..report.copies.histogram.len())
+            .map(|copies| lang.fmt("histogram_copies", &[&copies]))
+            .collect::<Vec<_>>();
+        let cast_labels = (0..report.casts.histogram.len())
+            .map(|casts| lang.fmt("histogram_casts", &[&casts]))
+            .collect::<Vec<_>>();
+        let chain_labels = (0..report.chain.histogram.len())
+            .map(|wins| lang.fmt("histogram_chain", &[&wins]))
+            .collect::<Vec<_>>();
+        let ratio_labels = (0..report.heads_ratio.len())
+            .map(|bin| format!("{}%", bin * 100 / RATIO_BINS))
+            .collect::<Vec<_>>();
+        html! {
+            <>
+                <Histogram language=lang
+                           name="copies-per-spell"
+                           label=lang.t("copies_distribution")
+                           bins=report.copies.histogram.clone()
+                           bin_labels=copy_labels />
+                <Histogram language=lang
+                           name="casts-per-spell"
+                           label=lang.t("casts_distribution")
+                           bins=report.casts.histogram.clone()
+                           bin_labels=cast_labels />
+                <Histogram language=lang
+                           name="chain-length"
+                           label=lang.t("chain_distribution")
+                           bins=report.chain.histogram.clone()
+                           bin_labels=chain_labels />
+                <Histogram language=lang
+                           name="heads-ratio"
+                           label=lang.t("heads_ratio_distribution")
+                           bins=report.heads_ratio.clone()
+                           bin_labels=ratio_labels />
+            </>
+        }
+    }
+
+    /// Spells the trials cast and copied. Only the casts add to storm.
+    fn view_counts(&self, report: &SimulationReport) -> Html {
+        let lang = self.props.language;
+        let counts = report.counts();
+        html! {
+            <dl class="spell-counts">
+                <dt>{ lang.t("spell_casts") }</dt>
+                <dd>{ counts.casts }</dd>
+                <dt>{ lang.t("spell_copies") }</dt>
+                <dd>{ counts.copies }</dd>
+                <dt title=lang.t("magecraft_hint")>{ lang.t("spell_total") }</dt>
+                <dd>{ counts.spells() }</dd>
+            </dl>
+        }
+    }
+
+    /// The exact odds for the board a result was simulated on, beside what
+    /// the simulation found.
+    fn view_odds(&self, value: usize, report: &SimulationReport) -> Html {
+        let lang = self.props.language;
+        let scenario = match &self.job {
+            Some(job) => job.scenario(value),
+            None => return html! {},
+        };
+        let odds = CastOdds::new(scenario.krarks, scenario.thumbs);
+        let casts = odds.expected_casts(scenario.max_recasts);
+        let rows = [
+            (
+                "copies_per_cast",
+                odds.expected_copies,
+                report.copies_per_cast(),
+                false,
+            ),
+            (
+                "at_least_one_copy",
+                odds.at_least_one_copy,
+                report.copy_rate,
+                true,
+            ),
+            ("bounce_rate", odds.bounce, report.bounce_rate, true),
+            ("heads_share", odds.win, report.heads_share, true),
+            ("casts_per_spell", casts, report.casts.mean, false),
+            (
+                "copies_per_spell",
+                odds.expected_copies * casts,
+                report.copies.mean,
+                false,
+            ),
+        ];
+        let show = |x: f64, percent: bool| {
+            if percent {
+                format!("{:.2}%", x * 100.0)
+            } else {
+                format!("{:.3}", x)
+            }
+        };
+        html! {
+            <table class="simulation-odds">
+                <thead>
+                    <tr>
+                        <th></th>
+                        <th>{ lang.t("analytic") }</th>
+                        <th>{ lang.t("simulated") }</th>
+                        <th>{ lang.t("difference") }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for rows.iter().map(|&(key, exact, simulated, percent)| html! {
+                        <tr>
+                            <th>{ lang.t(key) }</th>
+                            <td>{ show(exact, percent) }</td>
+                            <td>{ show(simulated, percent) }</td>
+                            <td>{ show(simulated - exact, percent) }</td>
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+        }
+    }
+
+    fn view_chart(&self) -> Html {
+        if self.results().len() < 2 {
+            return html! {};
+        }
+        let max = self
+            .results()
+            .iter()
+            .map(|(_, r)| r.copies.mean)
+            .fold(0.0, f64::max)
+            .max(f64::EPSILON);
+        if self.results().len() > SVG_POINT_LIMIT {
+            return html! {
+                <BitmapChart values=self.results().iter().map(|(_, r)| r.copies.mean).collect::<Vec<_>>()
+                             width=CHART_WIDTH as u32
+                             height=CHART_HEIGHT as u32
+                             label=self.props.language.t("copies_per_spell") />
+            };
+        }
+        let step = CHART_WIDTH as f64 / (self.results().len() - 1) as f64;
+        let points = self
+            .results()
+            .iter()
+            .enumerate()
+            .map(|(i, (_, r))| {
+                let x = i as f64 * step;
+                let y = CHART_HEIGHT as f64 * (1.0 - r.copies.mean / max);
+                format!("{:.1},{:.1}", x, y)
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        html! {
+            <Chart name="sweep">
+                <svg class="sweep-chart"
+                     width=CHART_WIDTH
+                     height=CHART_HEIGHT
+                     viewBox=format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT)>
+                    <polyline points=points />
+                </svg>
+            </Chart>
+        }
+    }
+}
+
+/// The middle 90% of a metric, as "low–high".
+fn spread(distribution: &Distribution) -> String {
+    match (distribution.percentile(5), distribution.percentile(95)) {
+        (Some(low), Some(high)) => format!("{}–{}", low, high),
+        _ => String::new(),
+    }
+}
+
+impl Component for Simulator {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let storage = StorageService::new(Area::Local).ok();
+        // A sweep left unfinished last time comes back paused.
+        let job = match storage.as_ref().map(|s| s.restore(SIMULATION_KEY)) {
+            Some(Packed(Some(job))) => Some(job),
+            _ => None,
+        };
+        let worker = SimulationWorker::bridge(
+            link.callback(|Response::Progress(job)| Msg::Progress(Box::new(job))),
+        );
+        Simulator {
+            link,
+            props,
+            trials: "10000".into(),
+            recasts: MAX_RECASTS.to_string(),
+            parameter: SweepParameter::Thumbs,
+            from: "".into(),
+            to: "".into(),
+            storage,
+            job,
+            worker,
+            running: false,
+            trials_error: None,
+            recasts_error: None,
+            selected: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateTrials(val) => self.trials = val,
+            Msg::UpdateRecasts(val) => self.recasts = val,
+            Msg::SetParameter(parameter) => self.parameter = parameter,
+            Msg::UpdateFrom(val) => self.from = val,
+            Msg::UpdateTo(val) => self.to = val,
+            Msg::Select(idx) => self.selected = Some(idx),
+            Msg::Run => {
+                let trials = match parse_count(&self.trials) {
+                    Ok(trials) => trials,
+                    Err(error) => return self.trials_error.neq_assign(Some(error)),
+                };
+                self.trials_error = None;
+                let max_recasts = match parse_count(&self.recasts) {
+                    Ok(recasts) => recasts,
+                    Err(error) => return self.recasts_error.neq_assign(Some(error)),
+                };
+                self.recasts_error = None;
+                let base = SimulationConfig {
+                    scenario: Scenario {
+                        krarks: self.props.krarks,
+                        thumbs: self.props.thumbs,
+                        max_recasts,
+                    },
+                    trials,
+                };
+                if let Err(error) = base.validate() {
+                    return self.trials_error.neq_assign(Some(error));
+                }
+                self.trials_error = None;
+                self.selected = None;
+                let (from, to) = self.range();
+                let seed = thread_rng().gen();
+                self.job = Some(SweepJob::new(base, self.parameter, from..=to, seed));
+                self.step();
+            }
+            Msg::Progress(job) => {
+                // A chunk of a sweep since cancelled or replaced.
+                if self.job.as_ref().map(|current| current.seed) != Some(job.seed) {
+                    return false;
+                }
+                let done = job.is_done();
+                self.job = Some(*job);
+                self.checkpoint();
+                if self.running && !done {
+                    self.step();
+                } else {
+                    self.running = false;
+                }
+            }
+            Msg::Pause => {
+                self.running = false;
+                self.checkpoint();
+            }
+            Msg::Resume => {
+                if self.job.as_ref().is_some_and(|job| !job.is_done()) {
+                    self.step();
+                } else {
+                    warn!("no simulation to resume");
+                }
+            }
+            Msg::Cancel => {
+                self.running = false;
+                self.job = None;
+                self.selected = None;
+                if let Some(storage) = &mut self.storage {
+                    storage.remove(SIMULATION_KEY);
+                }
+            }
+            Msg::Nope => return false,
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <div class="simulator">
+                { self.view_counts_inputs() }
+                <label>
+                    { lang.t("sweep") }
+                    <select onchange=self.link.callback(|e: ChangeData| match e {
+                        ChangeData::Select(select) => SweepParameter::from_str(&select.value())
+                            .map(Msg::SetParameter)
+                            .unwrap_or(Msg::Nope),
+                        _ => Msg::Nope,
+                    })>
+                        { for SweepParameter::iter().map(|p| html! {
+                            <option value=p selected=p == self.parameter>{ lang.t(p.as_ref()) }</option>
+                        }) }
+                    </select>
+                </label>
+                <input class="sweep-from" type="number" min="0" placeholder=lang.t("from")
+                       aria-label=lang.t("sweep_from")
+                       value=&self.from
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateFrom(e.value)) />
+                <input class="sweep-to" type="number" min="0" placeholder=lang.t("to")
+                       aria-label=lang.t("sweep_to")
+                       value=&self.to
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateTo(e.value)) />
+                <button onclick=self.link.callback(|_| Msg::Run)>{ lang.t("run") }</button>
+                { self.view_progress() }
+                { self.view_results() }
+                { self.view_details() }
+                { self.view_chart() }
+            </div>
+        }
+    }
+}
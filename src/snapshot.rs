@@ -0,0 +1,104 @@
+//! Shareable pictures of the board. The SVG is composed here rather than
+//! copied from the page, so it looks the same whatever panels are open.
+
+use crate::game::Game;
+use crate::i18n::Language;
+use std::fmt::Write;
+
+const WIDTH: u32 = 480;
+const LINE_HEIGHT: u32 = 28;
+const MARGIN: u32 = 24;
+
+pub struct Snapshot {
+    pub svg: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Escapes text for SVG content and attribute values.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A row of the snapshot: a label on the left and its value on the right,
+/// or a heading when there is no value.
+type Row = (String, Option<String>);
+
+fn rows(game: &Game, lang: Language) -> Vec<Row> {
+    let board = &game.board;
+    let mut rows: Vec<Row> = vec![
+        (lang.t("life").into(), Some(board.life.to_string())),
+        (lang.t("cards_in_hand").into(), Some(board.hand.to_string())),
+        (lang.t("mana_pool").into(), Some(board.mana.to_string())),
+        (lang.t("storm").into(), Some(game.storm.to_string())),
+        (lang.t("krarks").into(), Some(board.krarks.to_string())),
+        (lang.t("thumbs").into(), Some(board.thumbs.to_string())),
+    ];
+    for tokens in board.tokens.iter().filter(|t| t.count > 0) {
+        rows.push((tokens.name.clone(), Some(tokens.count.to_string())));
+    }
+    if !game.opponents.is_empty() {
+        rows.push((lang.t("panel_opponents").into(), None));
+        for opponent in &game.opponents {
+            rows.push((opponent.name.clone(), Some(opponent.life.to_string())));
+        }
+    }
+    rows
+}
+
+pub fn render(game: &Game, lang: Language) -> Snapshot {
+    let rows = rows(game, lang);
+    let height = MARGIN * 2 + LINE_HEIGHT * (rows.len() as u32 + 1);
+    let title = if game.name.is_empty() {
+        lang.t("panel_board")
+    } else {
+        &game.name
+    };
+    let mut svg = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="16">"##,
+        w = WIDTH,
+        h = height
+    );
+    let _ = write!(
+        svg,
+        r##"<rect width="100%" height="100%" rx="12" fill="#1d1f27"/><text x="{}" y="{}" fill="#f4d35e" font-size="20" font-weight="bold">{}</text>"##,
+        MARGIN,
+        MARGIN + LINE_HEIGHT / 2,
+        escape(title)
+    );
+    for (i, (label, value)) in rows.iter().enumerate() {
+        let y = MARGIN + LINE_HEIGHT * (i as u32 + 2) - LINE_HEIGHT / 2;
+        match value {
+            Some(value) => {
+                let _ = write!(
+                    svg,
+                    r##"<text x="{}" y="{}" fill="#c8c8d0">{}</text><text x="{}" y="{}" fill="#ffffff" text-anchor="end" font-weight="bold">{}</text>"##,
+                    MARGIN,
+                    y,
+                    escape(label),
+                    WIDTH - MARGIN,
+                    y,
+                    escape(value)
+                );
+            }
+            None => {
+                let _ = write!(
+                    svg,
+                    r##"<text x="{}" y="{}" fill="#f4d35e">{}</text>"##,
+                    MARGIN,
+                    y,
+                    escape(label)
+                );
+            }
+        }
+    }
+    svg.push_str("</svg>");
+    Snapshot {
+        svg,
+        width: WIDTH,
+        height,
+    }
+}
@@ -0,0 +1,82 @@
+//! Shared snapshots. A snapshot link carries one game in its fragment as
+//! it was when the link was made, so whoever opens it can look over the
+//! board without being able to change the original. It does not follow
+//! the game afterwards; share a new one to show how it has gone on.
+
+use crate::game::Game;
+use crate::packed;
+use crate::qr;
+use yew::format::Json;
+
+const PREFIX: &str = "#spectate=";
+
+/// Most recent flips a link carries, so links stay short enough to share.
+pub const HISTORY_LIMIT: usize = 100;
+
+/// `game` as a snapshot shows it, with at most `history` of its flips.
+/// The seed and how far into it the game has got are left out, since they
+/// would tell whoever opens the link how your next flips land.
+fn snapshot(game: &Game, history: usize) -> Game {
+    let mut snapshot = game.clone();
+    let excess = snapshot.history.len().saturating_sub(history);
+    snapshot.history.drain(..excess);
+    snapshot.seed = None;
+    snapshot.word_pos = 0;
+    snapshot
+}
+
+/// Link to `snapshot` on this page.
+fn link_to(snapshot: Game) -> Option<String> {
+    let text = packed::pack(&vec![snapshot])?;
+    let location = web_sys::window()?.location();
+    let encoded = String::from(js_sys::encode_uri_component(&text));
+    Some(format!(
+        "{}{}{}{}",
+        location.origin().ok()?,
+        location.pathname().ok()?,
+        PREFIX,
+        encoded
+    ))
+}
+
+/// Link to a snapshot of `game` on this page, with at most `history` of
+/// its flips.
+pub fn link(game: &Game, history: usize) -> Option<String> {
+    link_to(snapshot(game, history))
+}
+
+/// Link to a snapshot of `game` for a QR code, which holds far less than a
+/// pasted link. It leaves the flips out, and the table log and triggers
+/// too if it would not fit otherwise.
+pub fn qr_link(game: &Game) -> Option<String> {
+    let mut snapshot = snapshot(game, 0);
+    let link = link_to(snapshot.clone())?;
+    if qr::fits(link.len()) {
+        return Some(link);
+    }
+    snapshot.table_log.clear();
+    snapshot.triggers.clear();
+    snapshot.turn_marks.clear();
+    link_to(snapshot)
+}
+
+/// Drops the snapshot from the address, so reloading opens the user's own
+/// games again.
+pub fn leave() {
+    if let Some(window) = web_sys::window() {
+        let _ = window.location().set_hash("");
+    }
+}
+
+/// The game this page was opened to look at, if it was opened from a
+/// snapshot link. Links made before snapshots were packed hold JSON.
+pub fn from_location() -> Option<Game> {
+    let hash = web_sys::window()?.location().hash().ok()?;
+    let encoded = hash.strip_prefix(PREFIX)?;
+    let text = String::from(js_sys::decode_uri_component(encoded).ok()?);
+    if let Some(games) = packed::unpack::<Vec<Game>>(&text) {
+        return games.into_iter().next();
+    }
+    let Json(game) = Json::<Result<Game, _>>::from(Ok(text));
+    game.ok()
+}
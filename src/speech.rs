@@ -0,0 +1,19 @@
+//! Reads results aloud through the browser's speech synthesis, for when
+//! the phone lies flat on the table and everyone wants to hear them.
+
+use crate::i18n::Language;
+use web_sys::SpeechSynthesisUtterance;
+
+/// Speaks `text`, cutting off whatever was still being read so quick
+/// flips don't queue up behind each other.
+pub fn speak(text: &str, language: Language) {
+    let synthesis = match web_sys::window().and_then(|w| w.speech_synthesis().ok()) {
+        Some(synthesis) => synthesis,
+        None => return,
+    };
+    if let Ok(utterance) = SpeechSynthesisUtterance::new_with_text(text) {
+        utterance.set_lang(language.tag());
+        synthesis.cancel();
+        synthesis.speak(&utterance);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::cast::{Spell, SpellKind};
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+/// The form registering a spell. Storm is ticked for the spell by name
+/// when it is one of the storm spells the app knows.
+pub struct SpellForm {
+    link: ComponentLink<Self>,
+    props: Props,
+    name: String,
+    cost: String,
+    draws: String,
+    kind: SpellKind,
+    storm: bool,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub on_add: Callback<Spell>,
+}
+
+pub enum Msg {
+    UpdateName(String),
+    UpdateCost(String),
+    UpdateDraws(String),
+    SetKind(SpellKind),
+    ToggleStorm,
+    Add,
+    Nope,
+}
+
+impl Component for SpellForm {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        SpellForm {
+            link,
+            props,
+            name: String::new(),
+            cost: String::new(),
+            draws: String::new(),
+            kind: SpellKind::default(),
+            storm: false,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateName(val) => self.name.neq_assign(val),
+            Msg::UpdateCost(val) => self.cost.neq_assign(val),
+            Msg::UpdateDraws(val) => self.draws.neq_assign(val),
+            Msg::SetKind(kind) => self.kind.neq_assign(kind),
+            Msg::ToggleStorm => {
+                self.storm = !self.storm;
+                true
+            }
+            Msg::Add => {
+                let name = self.name.trim();
+                if name.is_empty() {
+                    return false;
+                }
+                self.props.on_add.emit(Spell {
+                    name: name.to_string(),
+                    mana_value: self.cost.parse().unwrap_or(0),
+                    kind: self.kind,
+                    draws: self.draws.parse().unwrap_or(0),
+                    storm: self.storm || Spell::has_storm_by_name(name),
+                });
+                self.name.clear();
+                self.cost.clear();
+                self.draws.clear();
+                self.storm = false;
+                true
+            }
+            Msg::Nope => false,
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <>
+                <input class="spell-name"
+                       placeholder=lang.t("spell_name")
+                       aria-label=lang.t("spell_name")
+                       value=&self.name
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateName(e.value))
+                       onkeypress=self.link.callback(|e: KeyboardEvent| {
+                           if e.key() == "Enter" { Msg::Add } else { Msg::Nope }
+                       }) />
+                <input class="spell-cost"
+                       type="number"
+                       min="0"
+                       placeholder=lang.t("mana_value")
+                       aria-label=lang.t("mana_value_long")
+                       value=&self.cost
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateCost(e.value)) />
+                <input class="spell-draws"
+                       type="number"
+                       min="0"
+                       placeholder=lang.t("spell_draws")
+                       aria-label=lang.t("spell_draws_long")
+                       value=&self.draws
+                       oninput=self.link.callback(|e: InputData| Msg::UpdateDraws(e.value)) />
+                <select aria-label=lang.t("spell_kind")
+                        onchange=self.link.callback(|e: ChangeData| match e {
+                    ChangeData::Select(select) => SpellKind::from_str(&select.value())
+                        .map(Msg::SetKind)
+                        .unwrap_or(Msg::Nope),
+                    _ => Msg::Nope,
+                })>
+                    { for SpellKind::iter().map(|kind| html! {
+                        <option value=kind selected=kind == self.kind>{ lang.t(kind.as_ref()) }</option>
+                    }) }
+                </select>
+                <label>
+                    <input type="checkbox"
+                           checked=self.storm
+                           onclick=self.link.callback(|_| Msg::ToggleStorm) />
+                    { lang.t("spell_storm") }
+                </label>
+                <button onclick=self.link.callback(|_| Msg::Add)>{ lang.t("add_spell") }</button>
+            </>
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::cast::{Board, Spell};
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::spell_form::SpellForm;
+use yew::prelude::*;
+
+/// Lists the registered spells with the expected value of casting each
+/// one against the board. Adding or removing one hands back the whole
+/// new list; casting is left to the parent.
+pub struct SpellTable {
+    link: ComponentLink<Self>,
+    props: Props,
+    on_add: Callback<Spell>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub spells: Vec<Spell>,
+    pub board: Board,
+    pub on_change: Callback<Vec<Spell>>,
+    pub on_cast: Callback<usize>,
+    /// Casts the spell until a flip is won.
+    pub on_cast_until: Callback<usize>,
+}
+
+pub enum Msg {
+    Add(Spell),
+    Remove(usize),
+}
+
+impl SpellTable {
+    fn view_spell(&self, (idx, spell): (usize, &Spell)) -> Html {
+        let lang = self.props.language;
+        let ev = spell.expected_value(&self.props.board);
+        html! {
+            <tr>
+                <td>{ &spell.name }</td>
+                <td>{ spell.mana_value }</td>
+                <td>{ format!("{:.2}", ev.copies) }</td>
+                <td>{ format!("{:+.2}", ev.mana) }</td>
+                <td>{ format!("{:.2}", ev.storm) }</td>
+                <td>
+                    <button class="cast" onclick=self.props.on_cast.reform(move |_| idx)>{ lang.t("cast") }</button>
+                    <button class="cast" onclick=self.props.on_cast_until.reform(move |_| idx)>{ lang.t("cast_until_it_sticks") }</button>
+                    <button class="destroy"
+                            aria-label=lang.t("remove")
+                            onclick=self.link.callback(move |_| Msg::Remove(idx))>{ "×" }</button>
+                </td>
+            </tr>
+        }
+    }
+}
+
+impl Component for SpellTable {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        SpellTable {
+            on_add: link.callback(Msg::Add),
+            link,
+            props,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let mut spells = self.props.spells.clone();
+        match msg {
+            Msg::Add(spell) => spells.push(spell),
+            Msg::Remove(idx) => {
+                spells.remove(idx);
+            }
+        }
+        self.props.on_change.emit(spells);
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <div class="spells">
+                <table class="ev-table">
+                    <thead>
+                        <tr>
+                            <th>{ lang.t("spell") }</th>
+                            <th>{ lang.t("mana_value") }</th>
+                            <th>{ lang.t("copies") }</th>
+                            <th>{ lang.t("net_mana") }</th>
+                            <th>{ lang.t("storm") }</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for self.props.spells.iter().enumerate().map(|e| self.view_spell(e)) }
+                    </tbody>
+                </table>
+                <SpellForm language=lang on_add=self.on_add.clone() />
+            </div>
+        }
+    }
+}
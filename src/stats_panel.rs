@@ -0,0 +1,156 @@
+use crate::chart::Chart;
+use crate::clock::format_clock;
+use crate::coin_skin::CoinFaces;
+use crate::day_night::DayNight;
+use crate::history::FlipRecord;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::pace::Pace;
+use yew::prelude::*;
+
+/// Width of a single bar in the flip sparkline, in SVG user units.
+const SPARK_BAR_WIDTH: usize = 4;
+/// Height of the flip sparkline, in SVG user units.
+const SPARK_HEIGHT: usize = 20;
+
+/// The strip of running totals: storm count and copies, day or night, flips won and
+/// lost, a timeline of every flip and the session's pace.
+pub struct StatsPanel {
+    props: Props,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub storm: u32,
+    /// Copies made this turn.
+    pub copies: u32,
+    pub day_night: Option<DayNight>,
+    pub flips: usize,
+    pub wins: usize,
+    pub history: Vec<FlipRecord>,
+    pub faces: CoinFaces,
+    pub pace: Pace,
+    /// Switches day and night by hand.
+    pub on_cycle_day_night: Callback<()>,
+}
+
+impl StatsPanel {
+    /// Day or night, which can be switched by hand, and what it will be
+    /// if the turn ends now.
+    fn view_day_night(&self) -> Html {
+        let lang = self.props.language;
+        let (class, label) = match &self.props.day_night {
+            Some(day_night) => (day_night.as_ref(), lang.t(day_night.as_ref())),
+            None => ("", lang.t("day_night_none")),
+        };
+        let next = self
+            .props
+            .day_night
+            .map(|day_night| day_night.next(self.props.storm))
+            .filter(|&next| Some(next) != self.props.day_night);
+        html! {
+            <span class="stat day-night">
+                <button class=class
+                        title=lang.t("day_night_override")
+                        onclick=self.props.on_cycle_day_night.reform(|_| ())>{ label }</button>
+                { for next.map(|next| html! {
+                    <span class="day-night-next">{ lang.fmt("day_night_next", &[&lang.t(next.as_ref())]) }</span>
+                }) }
+            </span>
+        }
+    }
+
+    /// Renders one bar per flip, rising for a win and falling for a loss.
+    fn view_sparkline(&self) -> Html {
+        let history = &self.props.history;
+        if history.is_empty() {
+            return html! {};
+        }
+        let width = history.len() * SPARK_BAR_WIDTH;
+        let mid = SPARK_HEIGHT / 2;
+        let lang = self.props.language;
+        html! {
+            <Chart name="sparkline">
+                <svg class="sparkline"
+                     role="img"
+                     aria-label=lang.t("flip_timeline")
+                     width=width
+                     height=SPARK_HEIGHT
+                     viewBox=format!("0 0 {} {}", width, SPARK_HEIGHT)>
+                    {
+                        for history.iter().enumerate().map(|(idx, record)| {
+                            let flip = &record.result;
+                            let (y, class) = if record.is_win() { (0, "win") } else { (mid, "loss") };
+                            html! {
+                                <a href=format!("#flip-{}", idx)>
+                                    <title>{ format!("#{}: {}", idx + 1, lang.flip(flip)) }</title>
+                                    <rect class=class
+                                          x=idx * SPARK_BAR_WIDTH
+                                          y=y
+                                          width=SPARK_BAR_WIDTH - 1
+                                          height=mid />
+                                </a>
+                            }
+                        })
+                    }
+                </svg>
+            </Chart>
+        }
+    }
+
+    /// Session length and flips per minute as of the last update.
+    fn view_pace(&self) -> Html {
+        let pace = &self.props.pace;
+        if pace.started.is_none() {
+            return html! {};
+        }
+        let lang = self.props.language;
+        let now = js_sys::Date::now();
+        let per_minute = pace.flips_per_minute(self.props.flips, now);
+        html! {
+            <>
+                <span class="stat">{ lang.fmt("session_length_stat", &[&format_clock(pace.elapsed(now))]) }</span>
+                <span class="stat">{ lang.fmt("flips_per_minute_stat", &[&format!("{:.1}", per_minute)]) }</span>
+            </>
+        }
+    }
+}
+
+impl Component for StatsPanel {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+        StatsPanel { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        let (flips, wins) = (self.props.flips, self.props.wins);
+        let faces = &self.props.faces;
+        html! {
+            <div class="stats-strip">
+                <span class="stat">{ lang.fmt("storm_count", &[&self.props.storm]) }</span>
+                <span class="stat">{ lang.fmt("copy_count", &[&self.props.copies]) }</span>
+                <span class="stat" title=lang.t("magecraft_hint")>
+                    { lang.fmt("magecraft_count", &[&(self.props.storm + self.props.copies)]) }
+                </span>
+                { self.view_day_night() }
+                <span class="stat">{ lang.fmt("flip_count", &[&flips]) }</span>
+                <span class="stat win">{ faces.view(true) }{ lang.fmt("won_count", &[&wins]) }</span>
+                <span class="stat loss">{ faces.view(false) }{ lang.fmt("lost_count", &[&(flips - wins)]) }</span>
+                { self.view_sparkline() }
+                { self.view_pace() }
+            </div>
+        }
+    }
+}
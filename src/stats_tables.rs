@@ -0,0 +1,164 @@
+use crate::clock::format_clock;
+use crate::history::{CardStats, PlayerStats, StatsColumn};
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+/// Totals per card, sortable by any column, then per player and the
+/// average turn length of each player.
+pub struct StatsTables {
+    link: ComponentLink<Self>,
+    props: Props,
+    column: StatsColumn,
+    descending: bool,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    /// Per-card totals in the order the cards first flipped.
+    pub cards: Vec<CardStats>,
+    pub players: Vec<PlayerStats>,
+    /// Each player's name and average turn length, slowest first.
+    pub turn_times: Vec<(String, u64)>,
+    pub average_turn: Option<u64>,
+}
+
+impl StatsTables {
+    fn view_card_stats(&self) -> Html {
+        let mut stats = self.props.cards.clone();
+        self.column.sort(&mut stats, self.descending);
+        let lang = self.props.language;
+        html! {
+            <table class="card-stats">
+                <thead>
+                    <tr>
+                        { for StatsColumn::iter().map(|column| {
+                            let (class, sort) = match (column == self.column, self.descending) {
+                                (false, _) => ("", "none"),
+                                (true, true) => ("sorted", "descending"),
+                                (true, false) => ("sorted", "ascending"),
+                            };
+                            html! {
+                                <th class=class aria-sort=sort>
+                                    <button onclick=self.link.callback(move |_| column)>
+                                        { lang.t(column.as_ref()) }
+                                    </button>
+                                </th>
+                            }
+                        }) }
+                    </tr>
+                </thead>
+                <tbody>
+                    { for stats.iter().map(|s| html! {
+                        <tr>
+                            <td>{ s.source }</td>
+                            <td>{ s.activations }</td>
+                            <td>{ s.flips }</td>
+                            <td>{ format!("{:.0}%", s.win_rate() * 100.0) }</td>
+                            <td>{ format!("{:.1}", s.wins_per_activation()) }</td>
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+        }
+    }
+
+    /// Win rates per player, once anyone but you has flipped.
+    fn view_player_stats(&self) -> Html {
+        let stats = &self.props.players;
+        if stats.iter().all(|s| s.player.is_none()) {
+            return html! {};
+        }
+        let lang = self.props.language;
+        html! {
+            <table class="card-stats player-stats">
+                <caption>{ lang.t("player_stats") }</caption>
+                <thead>
+                    <tr>
+                        <th>{ lang.t("player") }</th>
+                        <th>{ lang.t("flips") }</th>
+                        <th>{ lang.t("win_rate") }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for stats.iter().map(|s| html! {
+                        <tr>
+                            <td>{ s.player.as_deref().unwrap_or_else(|| lang.t("you")) }</td>
+                            <td>{ s.flips }</td>
+                            <td>{ format!("{:.0}%", s.win_rate() * 100.0) }</td>
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+        }
+    }
+
+    /// Average turn length for each player, slowest first.
+    fn view_turn_times(&self) -> Html {
+        let average = match self.props.average_turn {
+            Some(average) => average,
+            None => return html! {},
+        };
+        let lang = self.props.language;
+        html! {
+            <table class="card-stats turn-times">
+                <caption>{ lang.fmt("turn_times", &[&format_clock(average)]) }</caption>
+                <thead>
+                    <tr>
+                        <th>{ lang.t("player") }</th>
+                        <th>{ lang.t("average_turn") }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for self.props.turn_times.iter().map(|(player, millis)| html! {
+                        <tr>
+                            <td>{ player }</td>
+                            <td>{ format_clock(*millis) }</td>
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+        }
+    }
+}
+
+impl Component for StatsTables {
+    /// The column whose heading was clicked.
+    type Message = StatsColumn;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        StatsTables {
+            link,
+            props,
+            column: StatsColumn::Flips,
+            descending: true,
+        }
+    }
+
+    fn update(&mut self, column: Self::Message) -> ShouldRender {
+        if self.column == column {
+            self.descending = !self.descending;
+        } else {
+            self.column = column;
+            self.descending = true;
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <>
+                { self.view_card_stats() }
+                { self.view_player_stats() }
+                { self.view_turn_times() }
+            </>
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use crate::coin_flip::FlipResult;
+use crate::export;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use crate::settings::root_style;
+use serde_derive::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::{AsRefStr, EnumIter};
+use yew::format::{Json, Text};
+use yew::prelude::*;
+use yew::services::reader::{File, FileData, ReaderService, ReaderTask};
+
+/// A named set of colours for the stylesheet's custom properties.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub name: String,
+    pub accent: String,
+    pub background: String,
+    pub win: String,
+    pub loss: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            name: "Default".into(),
+            accent: "#1565c0".into(),
+            background: "#f5f5f5".into(),
+            win: "#2e7d32".into(),
+            loss: "#c62828".into(),
+        }
+    }
+}
+
+/// The colours a theme sets. `as_ref` gives the colour's text key.
+#[derive(AsRefStr, Clone, Copy, Debug, EnumIter, PartialEq)]
+pub enum ThemeColor {
+    #[strum(serialize = "theme_accent")]
+    Accent,
+    #[strum(serialize = "theme_background")]
+    Background,
+    #[strum(serialize = "theme_win")]
+    Win,
+    #[strum(serialize = "theme_loss")]
+    Loss,
+}
+
+impl ThemeColor {
+    /// The CSS custom property the stylesheet reads this colour from.
+    pub fn variable(self) -> &'static str {
+        match self {
+            ThemeColor::Accent => "--accent",
+            ThemeColor::Background => "--background",
+            ThemeColor::Win => "--win",
+            ThemeColor::Loss => "--loss",
+        }
+    }
+}
+
+impl Theme {
+    pub fn color(&self, color: ThemeColor) -> &str {
+        match color {
+            ThemeColor::Accent => &self.accent,
+            ThemeColor::Background => &self.background,
+            ThemeColor::Win => &self.win,
+            ThemeColor::Loss => &self.loss,
+        }
+    }
+
+    pub fn set_color(&mut self, color: ThemeColor, value: String) {
+        match color {
+            ThemeColor::Accent => self.accent = value,
+            ThemeColor::Background => self.background = value,
+            ThemeColor::Win => self.win = value,
+            ThemeColor::Loss => self.loss = value,
+        }
+    }
+
+    /// Publishes the theme's colours as custom properties on the document
+    /// root.
+    pub fn apply(&self) {
+        if let Some(style) = root_style() {
+            for color in ThemeColor::iter() {
+                let _ = style.set_property(color.variable(), self.color(color));
+            }
+        }
+    }
+}
+
+/// Edits the active theme with a live preview, and saves, exports and
+/// imports themes.
+pub struct ThemeEditor {
+    link: ComponentLink<Self>,
+    props: Props,
+    reader: ReaderService,
+    _task: Option<ReaderTask>,
+    import_failed: bool,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub theme: Theme,
+    pub saved: Vec<Theme>,
+    pub on_change: Callback<Theme>,
+    pub on_save: Callback<Vec<Theme>>,
+}
+
+pub enum Msg {
+    SetColor(ThemeColor, String),
+    SetName(String),
+    Save,
+    Load(usize),
+    Delete(usize),
+    Reset,
+    Export,
+    Import(File),
+    Imported(FileData),
+    Nope,
+}
+
+impl ThemeEditor {
+    fn edit(&self, update: impl FnOnce(&mut Theme)) {
+        let mut theme = self.props.theme.clone();
+        update(&mut theme);
+        self.props.on_change.emit(theme);
+    }
+
+    fn view_saved(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <ul class="saved-themes">
+                { for self.props.saved.iter().enumerate().map(|(idx, theme)| html! {
+                    <li>
+                        <span class="theme-swatch" style=format!("background: {}", theme.accent) />
+                        { &theme.name }
+                        <button onclick=self.link.callback(move |_| Msg::Load(idx))>{ lang.t("theme_use") }</button>
+                        <button onclick=self.link.callback(move |_| Msg::Delete(idx))>{ lang.t("remove") }</button>
+                    </li>
+                }) }
+            </ul>
+        }
+    }
+
+    fn view_preview(&self) -> Html {
+        let theme = &self.props.theme;
+        html! {
+            <div class="theme-preview" aria-hidden="true"
+                 style=format!("background: {}; border-color: {}", theme.background, theme.accent)>
+                <span style=format!("color: {}", theme.win)>{ "▲ " }{ self.props.language.flip(&FlipResult::Heads) }</span>
+                <span style=format!("color: {}", theme.loss)>{ "▼ " }{ self.props.language.flip(&FlipResult::Tails) }</span>
+            </div>
+        }
+    }
+}
+
+impl Component for ThemeEditor {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        ThemeEditor {
+            link,
+            props,
+            reader: ReaderService::new(),
+            _task: None,
+            import_failed: false,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::SetColor(color, value) => self.edit(|theme| theme.set_color(color, value)),
+            Msg::SetName(name) => self.edit(|theme| theme.name = name),
+            Msg::Save => {
+                let theme = self.props.theme.clone();
+                let mut saved = self.props.saved.clone();
+                match saved.iter_mut().find(|t| t.name == theme.name) {
+                    Some(existing) => *existing = theme,
+                    None => saved.push(theme),
+                }
+                self.props.on_save.emit(saved);
+            }
+            Msg::Load(idx) => {
+                if let Some(theme) = self.props.saved.get(idx) {
+                    self.props.on_change.emit(theme.clone());
+                }
+            }
+            Msg::Delete(idx) => {
+                let mut saved = self.props.saved.clone();
+                if idx < saved.len() {
+                    saved.remove(idx);
+                    self.props.on_save.emit(saved);
+                }
+            }
+            Msg::Reset => self.props.on_change.emit(Theme::default()),
+            Msg::Export => {
+                let theme = &self.props.theme;
+                let text: Text = Json(theme).into();
+                if let Ok(json) = text {
+                    let filename = format!("{}.theme.json", theme.name);
+                    let _ = export::download(&filename, "application/json", &json);
+                }
+            }
+            Msg::Import(file) => {
+                let callback = self.link.callback(Msg::Imported);
+                self._task = self.reader.read_file(file, callback).ok();
+            }
+            Msg::Imported(data) => {
+                self._task = None;
+                let text: Text = String::from_utf8(data.content).map_err(Into::into);
+                let Json(theme) = Json::<Result<Theme, _>>::from(text);
+                match theme {
+                    Ok(theme) => {
+                        self.import_failed = false;
+                        self.props.on_change.emit(theme);
+                    }
+                    Err(_) => self.import_failed = true,
+                }
+            }
+            Msg::Nope => return false,
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        let theme = &self.props.theme;
+        html! {
+            <fieldset class="theme-editor">
+                <legend>{ lang.t("theme") }</legend>
+                { self.view_preview() }
+                { for ThemeColor::iter().map(|color| html! {
+                    <label>
+                        <input type="color"
+                               value=theme.color(color)
+                               oninput=self.link.callback(move |e: InputData| Msg::SetColor(color, e.value)) />
+                        { lang.t(color.as_ref()) }
+                    </label>
+                }) }
+                <label>
+                    { lang.t("theme_name") }
+                    <input value=&theme.name
+                           oninput=self.link.callback(|e: InputData| Msg::SetName(e.value)) />
+                </label>
+                <button onclick=self.link.callback(|_| Msg::Save)>{ lang.t("theme_save") }</button>
+                <button onclick=self.link.callback(|_| Msg::Reset)>{ lang.t("theme_reset") }</button>
+                <button onclick=self.link.callback(|_| Msg::Export)>{ lang.t("theme_export") }</button>
+                <label>
+                    { lang.t("theme_import") }
+                    <input type="file" accept="application/json,.json"
+                           onchange=self.link.callback(|e: ChangeData| match e {
+                               ChangeData::Files(files) => files.get(0).map(Msg::Import).unwrap_or(Msg::Nope),
+                               _ => Msg::Nope,
+                           }) />
+                </label>
+                { if self.import_failed {
+                    html! { <p class="error" role="alert">{ lang.t("theme_import_failed") }</p> }
+                } else {
+                    html! {}
+                } }
+                { self.view_saved() }
+            </fieldset>
+        }
+    }
+}
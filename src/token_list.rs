@@ -0,0 +1,102 @@
+use crate::cast::Tokens;
+use crate::counter::Counter;
+use crate::i18n::Language;
+use crate::name_form::NameForm;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// Tokens on the battlefield, with the form creating them by hand. Holds
+/// whether new tokens last only until end of turn.
+pub struct TokenList {
+    link: ComponentLink<Self>,
+    props: Props,
+    temporary: bool,
+    on_add: Callback<String>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub tokens: Vec<Tokens>,
+    /// Receives the whole list after a count is stepped.
+    pub on_change: Callback<Vec<Tokens>>,
+    /// Receives the name of a token to create, and whether it lasts only
+    /// until end of turn.
+    pub on_create: Callback<(String, bool)>,
+}
+
+pub enum Msg {
+    Step(usize, i32),
+    Create(String),
+    ToggleTemporary,
+}
+
+impl Component for TokenList {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        TokenList {
+            on_add: link.callback(Msg::Create),
+            link,
+            props,
+            temporary: false,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Step(idx, step) => {
+                let mut tokens = self.props.tokens.clone();
+                if let Some(stack) = tokens.get_mut(idx) {
+                    stack.count = (stack.count as i32 + step).max(0) as u32;
+                }
+                tokens.retain(|t| t.count > 0);
+                self.props.on_change.emit(tokens);
+                false
+            }
+            Msg::Create(name) => {
+                self.props.on_create.emit((name, self.temporary));
+                false
+            }
+            Msg::ToggleTemporary => {
+                self.temporary = !self.temporary;
+                true
+            }
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <>
+                <ul>
+                    { for self.props.tokens.iter().enumerate().map(|(idx, tokens)| html! {
+                        <li>
+                            <Counter label=if tokens.temporary { lang.fmt("tokens_temporary", &[&tokens.name]) } else { tokens.name.clone() }
+                                     value=i64::from(tokens.count)
+                                     decrement_label=lang.fmt("tokens_remove", &[&tokens.name])
+                                     increment_label=lang.fmt("tokens_add", &[&tokens.name])
+                                     on_step=self.link.callback(move |step| Msg::Step(idx, step)) />
+                        </li>
+                    }) }
+                </ul>
+                <NameForm class="token-name"
+                          label=lang.t("token_name")
+                          button=lang.t("add_token")
+                          on_submit=self.on_add.clone()>
+                    <label>
+                        <input type="checkbox"
+                               checked=self.temporary
+                               onclick=self.link.callback(|_| Msg::ToggleTemporary) />
+                        { lang.t("token_temporary") }
+                    </label>
+                </NameForm>
+            </>
+        }
+    }
+}
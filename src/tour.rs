@@ -0,0 +1,121 @@
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// Class added to the element the current step points at.
+const HIGHLIGHT: &str = "tour-highlight";
+
+/// Each step highlights the first element matching a selector and explains
+/// it with the given text key.
+const STEPS: &[(&str, &str)] = &[
+    (".controls .flip", "tour_flip"),
+    (".board", "tour_board"),
+    (".stats-strip", "tour_stats"),
+];
+
+/// A first-run walkthrough of the main controls.
+pub struct Tour {
+    link: ComponentLink<Self>,
+    props: Props,
+    step: usize,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub on_finish: Callback<()>,
+}
+
+pub enum Msg {
+    Next,
+    Back,
+    Finish,
+    KeyDown(KeyboardEvent),
+}
+
+fn target(step: usize) -> Option<web_sys::Element> {
+    let (selector, _) = STEPS.get(step)?;
+    web_sys::window()?
+        .document()?
+        .query_selector(selector)
+        .ok()
+        .flatten()
+}
+
+fn set_highlight(step: usize, on: bool) {
+    if let Some(element) = target(step) {
+        let classes = element.class_list();
+        if on {
+            let _ = classes.add_1(HIGHLIGHT);
+            element.scroll_into_view();
+        } else {
+            let _ = classes.remove_1(HIGHLIGHT);
+        }
+    }
+}
+
+impl Component for Tour {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Tour {
+            link,
+            props,
+            step: 0,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        set_highlight(self.step, false);
+        match msg {
+            Msg::Next if self.step + 1 < STEPS.len() => self.step += 1,
+            Msg::Back => self.step = self.step.saturating_sub(1),
+            Msg::Next | Msg::Finish => {
+                self.props.on_finish.emit(());
+                return false;
+            }
+            Msg::KeyDown(event) => {
+                if event.key() == "Escape" {
+                    self.props.on_finish.emit(());
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn rendered(&mut self, _first_render: bool) {
+        set_highlight(self.step, true);
+    }
+
+    fn destroy(&mut self) {
+        set_highlight(self.step, false);
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        let (_, text) = STEPS[self.step];
+        let last = self.step + 1 == STEPS.len();
+        html! {
+            <div class="tour"
+                 role="dialog"
+                 aria-live="polite"
+                 aria-label=lang.t("tour")
+                 onkeydown=self.link.callback(Msg::KeyDown)>
+                <p>{ lang.t(text) }</p>
+                <p class="tour-progress">{ lang.fmt("tour_step", &[&(self.step + 1), &STEPS.len()]) }</p>
+                <button disabled=self.step == 0
+                        onclick=self.link.callback(|_| Msg::Back)>{ lang.t("prev") }</button>
+                <button onclick=self.link.callback(|_| Msg::Next)>
+                    { if last { lang.t("tour_done") } else { lang.t("next") } }
+                </button>
+                <button onclick=self.link.callback(|_| Msg::Finish)>{ lang.t("tour_skip") }</button>
+            </div>
+        }
+    }
+}
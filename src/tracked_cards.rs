@@ -0,0 +1,73 @@
+use crate::game::TrackedCard;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use yew::prelude::*;
+
+/// The cards being tracked, each switched on while it is in play.
+/// Switching or removing one hands back the whole new list.
+pub struct TrackedCards {
+    link: ComponentLink<Self>,
+    props: Props,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub cards: Vec<TrackedCard>,
+    pub on_change: Callback<Vec<TrackedCard>>,
+}
+
+pub enum Msg {
+    Toggle(usize),
+    Remove(usize),
+}
+
+impl Component for TrackedCards {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        TrackedCards { link, props }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let mut cards = self.props.cards.clone();
+        match msg {
+            Msg::Toggle(idx) => {
+                if let Some(card) = cards.get_mut(idx) {
+                    card.active = !card.active;
+                }
+            }
+            Msg::Remove(idx) => {
+                cards.remove(idx);
+            }
+        }
+        self.props.on_change.emit(cards);
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <ul class="tracked-cards">
+                { for self.props.cards.iter().enumerate().map(|(idx, card)| html! {
+                    <li>
+                        <label title=&card.text>
+                            <input type="checkbox"
+                                   checked=card.active
+                                   onclick=self.link.callback(move |_| Msg::Toggle(idx)) />
+                            { &card.name }
+                        </label>
+                        <button class="destroy"
+                                aria-label=lang.t("remove")
+                                onclick=self.link.callback(move |_| Msg::Remove(idx))>{ "×" }</button>
+                    </li>
+                }) }
+            </ul>
+        }
+    }
+}
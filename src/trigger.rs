@@ -0,0 +1,309 @@
+//! A cast spell's Krark triggers, followed one at a time. Each won flip
+//! copies the spell and the first lost one returns it to its owner's hand,
+//! after which lost flips find nothing to return. A returned spell can be
+//! cast again for a fresh set of triggers, so one spell can make a chain
+//! of casts.
+//!
+//! Copies never start chains of their own. Krark triggers only when a
+//! spell is cast, and a copy is put on the stack rather than cast. A copy
+//! also cannot be returned to hand: it ceases to exist as it leaves the
+//! stack. So the resolution tree of a spell has a single branch, the
+//! original cast again and again, which is why [`CastChain`] is a flat
+//! list of casts.
+
+use crate::coin_flip::{Call, FlipConfig, FlipResult, WinCondition};
+use rand::Rng;
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::AsRefStr;
+
+/// What one Krark trigger did. `as_ref` gives its text key.
+#[derive(AsRefStr, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Trigger {
+    #[strum(serialize = "trigger_copied")]
+    Copied,
+    #[strum(serialize = "trigger_bounced")]
+    Bounced,
+    /// Lost after the spell had already gone back to hand.
+    #[strum(serialize = "trigger_missed")]
+    Missed,
+}
+
+/// The triggers of one cast, in the order they resolved.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Resolution {
+    pub triggers: Vec<Trigger>,
+}
+
+impl Resolution {
+    /// Follows `flips`, one per trigger, won as `win` says.
+    pub fn new(flips: &[FlipResult], win: WinCondition) -> Self {
+        let mut resolution = Resolution::default();
+        for flip in flips {
+            resolution.push(win.wins(flip));
+        }
+        resolution
+    }
+
+    /// Resolves the next trigger, won or lost.
+    pub fn push(&mut self, won: bool) -> Trigger {
+        let trigger = match (won, self.bounced()) {
+            (true, _) => Trigger::Copied,
+            (false, false) => Trigger::Bounced,
+            (false, true) => Trigger::Missed,
+        };
+        self.triggers.push(trigger);
+        trigger
+    }
+
+    pub fn copies(&self) -> usize {
+        self.triggers
+            .iter()
+            .filter(|&&t| t == Trigger::Copied)
+            .count()
+    }
+
+    pub fn bounced(&self) -> bool {
+        self.triggers.contains(&Trigger::Bounced)
+    }
+
+    /// Won flips before the first lost one.
+    pub fn chain_length(&self) -> usize {
+        self.triggers
+            .iter()
+            .take_while(|&&t| t == Trigger::Copied)
+            .count()
+    }
+}
+
+/// A trigger resolved during the session, kept for exports.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TriggerRecord {
+    /// Which of the session's casts it belonged to, counting from 0.
+    pub cast: usize,
+    /// Which of the cast's triggers it was, counting from 1.
+    pub number: usize,
+    /// Activation of the flip that resolved it.
+    pub activation: usize,
+    pub trigger: Trigger,
+}
+
+/// Spells put on the stack, split by how they got there. Cast spells add
+/// to storm; copies are not cast, but both trigger magecraft and cards such
+/// as Storm-Kiln Artist.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SpellCounts {
+    pub casts: usize,
+    pub copies: usize,
+}
+
+impl SpellCounts {
+    /// Every spell, cast or copied, so one magecraft trigger each.
+    pub fn spells(&self) -> usize {
+        self.casts + self.copies
+    }
+}
+
+/// Times a returned spell is cast again, at most, unless told otherwise.
+pub const MAX_RECASTS: usize = 20;
+
+/// Casts of one spell, each after the one before returned it to hand.
+/// Only the original is recast; its copies resolve and are gone.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CastChain {
+    pub casts: Vec<Resolution>,
+}
+
+impl CastChain {
+    /// Casts a spell with `krarks` Krarks and `thumbs` Krark's Thumbs in
+    /// play, casting it again each time it is returned, at most
+    /// `max_recasts` times.
+    pub fn simulate<R: Rng>(
+        krarks: usize,
+        thumbs: usize,
+        win: WinCondition,
+        max_recasts: usize,
+        rng: &mut R,
+    ) -> Self {
+        let config = FlipConfig::new().thumbs(thumbs).flips(krarks);
+        CastChain::simulate_with(win, max_recasts, || config.flip(rng))
+    }
+
+    /// Like [`CastChain::simulate`], with `flip_cast` making each cast's
+    /// Krark flips, so batch simulations can flip from a faster source.
+    pub fn simulate_with(
+        win: WinCondition,
+        max_recasts: usize,
+        mut flip_cast: impl FnMut() -> Vec<FlipResult>,
+    ) -> Self {
+        let mut chain = CastChain::default();
+        while chain.casts.len() <= max_recasts {
+            let resolution = Resolution::new(&flip_cast(), win);
+            let bounced = resolution.bounced();
+            chain.casts.push(resolution);
+            if !bounced {
+                break;
+            }
+        }
+        chain
+    }
+
+    /// Copies made over every cast.
+    pub fn copies(&self) -> usize {
+        self.casts.iter().map(Resolution::copies).sum()
+    }
+
+    pub fn counts(&self) -> SpellCounts {
+        SpellCounts {
+            casts: self.casts.len(),
+            copies: self.copies(),
+        }
+    }
+
+    /// Whether the last cast went back to hand too.
+    pub fn bounced(&self) -> bool {
+        self.casts.last().is_some_and(Resolution::bounced)
+    }
+}
+
+/// One trigger resolved by hand.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepLine {
+    /// Which of the cast's triggers it was, counting from 1.
+    pub number: usize,
+    /// Every coin tossed for it.
+    pub coins: Vec<Call>,
+    /// The side kept.
+    pub kept: Call,
+    pub trigger: Trigger,
+}
+
+/// A cast whose Krark triggers are resolved one at a time, as they are
+/// at the table, with the caller picking which coin to keep.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepCast {
+    /// Triggers the cast has, one per Krark.
+    pub triggers: usize,
+    pub resolution: Resolution,
+}
+
+impl StepCast {
+    pub fn new(triggers: usize) -> Self {
+        StepCast {
+            triggers,
+            resolution: Resolution::default(),
+        }
+    }
+
+    /// Whether every trigger has resolved.
+    pub fn done(&self) -> bool {
+        self.resolution.triggers.len() >= self.triggers
+    }
+
+    /// Resolves the next trigger with `coins` tossed and `kept` kept,
+    /// won when it is the side `win` calls.
+    pub fn resolve(&mut self, coins: Vec<Call>, kept: Call, win: WinCondition) -> StepLine {
+        let trigger = self.resolution.push(kept == win.side());
+        StepLine {
+            number: self.resolution.triggers.len(),
+            coins,
+            kept,
+            trigger,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coin_flip::FlipResult::{Both, Heads, Tails};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    #[test]
+    fn won_flips_copy_and_the_first_lost_one_bounces() {
+        let resolution = Resolution::new(&[Heads, Tails, Heads, Tails], WinCondition::YouCall);
+        assert_eq!(
+            resolution.triggers,
+            vec![
+                Trigger::Copied,
+                Trigger::Bounced,
+                Trigger::Copied,
+                Trigger::Missed
+            ]
+        );
+        assert_eq!(resolution.copies(), 2);
+        assert_eq!(resolution.chain_length(), 1);
+        assert!(resolution.bounced());
+
+        let won = Resolution::new(&[Heads, Heads], WinCondition::YouCall);
+        assert_eq!(won.copies(), 2);
+        assert!(!won.bounced());
+        // The same flips lose when an opponent calls.
+        let lost = Resolution::new(&[Heads, Heads], WinCondition::OpponentCalls);
+        assert_eq!(lost.triggers, vec![Trigger::Bounced, Trigger::Missed]);
+    }
+
+    #[test]
+    fn thumbs_win_mixed_flips_whoever_calls() {
+        for win in [WinCondition::YouCall, WinCondition::OpponentCalls] {
+            let resolution = Resolution::new(&[Both, Both], win);
+            assert_eq!(resolution.copies(), 2);
+            assert!(!resolution.bounced());
+        }
+        // Each thumb doubles the coins a trigger tosses, and only all of
+        // them losing returns the spell: two Krarks bounce three casts in
+        // four without thumbs, but about one in eight with two thumbs.
+        let mut rng = ChaCha12Rng::seed_from_u64(7);
+        let mut bounced = |thumbs| {
+            (0..200)
+                .filter(|_| {
+                    CastChain::simulate(2, thumbs, WinCondition::YouCall, 0, &mut rng).bounced()
+                })
+                .count()
+        };
+        let (without, with) = (bounced(0), bounced(2));
+        assert!((120..180).contains(&without), "{} of 200 bounced", without);
+        assert!((5..50).contains(&with), "{} of 200 bounced", with);
+    }
+
+    #[test]
+    fn a_returned_spell_is_cast_again_until_it_sticks() {
+        let mut casts =
+            vec![vec![Heads, Tails], vec![Tails, Tails], vec![Heads, Heads]].into_iter();
+        let chain =
+            CastChain::simulate_with(WinCondition::YouCall, MAX_RECASTS, || casts.next().unwrap());
+        assert_eq!(
+            chain.counts(),
+            SpellCounts {
+                casts: 3,
+                copies: 3
+            }
+        );
+        assert_eq!(chain.counts().spells(), 6);
+        assert!(!chain.bounced());
+    }
+
+    #[test]
+    fn recasts_stop_at_the_limit() {
+        let chain = CastChain::simulate_with(WinCondition::YouCall, 2, || vec![Tails]);
+        assert_eq!(chain.casts.len(), 3);
+        assert_eq!(chain.copies(), 0);
+        assert!(chain.bounced());
+    }
+
+    #[test]
+    fn steps_through_one_trigger_per_krark() {
+        assert!(StepCast::new(0).done());
+        let mut cast = StepCast::new(2);
+        let first = cast.resolve(
+            vec![Call::Heads, Call::Tails],
+            Call::Tails,
+            WinCondition::YouCall,
+        );
+        assert_eq!((first.number, first.trigger), (1, Trigger::Bounced));
+        assert!(!cast.done());
+        let second = cast.resolve(vec![Call::Heads], Call::Heads, WinCondition::YouCall);
+        assert_eq!((second.number, second.trigger), (2, Trigger::Copied));
+        assert!(cast.done());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::clock::{format_clock, TurnClock, MINUTE};
+use crate::coin_flip::{parse_count, ConfigError};
+use crate::i18n::Language;
+use std::time::Duration;
+use yew::prelude::*;
+use yew::services::interval::{IntervalService, IntervalTask};
+
+/// How often the display updates while the clock runs.
+const TICK: Duration = Duration::from_millis(250);
+
+/// A chess clock over every player's turns, passed on by the end-turn
+/// action.
+pub struct TurnTimer {
+    link: ComponentLink<Self>,
+    props: Props,
+    clock: TurnClock,
+    bank: String,
+    warning: String,
+    bank_error: Option<ConfigError>,
+    warning_error: Option<ConfigError>,
+    /// Runs while the clock does.
+    _interval: Option<IntervalTask>,
+    /// When the clock last charged time, from `Date.now()`.
+    last_tick: f64,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    /// Every player in turn order.
+    pub players: Vec<String>,
+    /// The player whose turn the clock starts on.
+    pub first: usize,
+    /// Turns ended so far. The clock passes on whenever it goes up.
+    pub turn: usize,
+}
+
+pub enum Msg {
+    Start,
+    Pause,
+    Pass,
+    Reset,
+    Tick,
+    UpdateBank(String),
+    UpdateWarning(String),
+}
+
+impl TurnTimer {
+    fn bank_millis(&self) -> u64 {
+        parse_count(&self.bank).unwrap_or(0) as u64 * MINUTE
+    }
+
+    /// Charges the time since the last tick to the active player.
+    fn catch_up(&mut self) {
+        let now = js_sys::Date::now();
+        self.clock.elapse((now - self.last_tick).max(0.0) as u64);
+        self.last_tick = now;
+    }
+
+    /// Whether the current turn has run past the warning time.
+    fn running_long(&self) -> bool {
+        match parse_count(&self.warning) {
+            Ok(minutes) if minutes > 0 => self.clock.turn_elapsed >= minutes as u64 * MINUTE,
+            _ => false,
+        }
+    }
+}
+
+impl Component for TurnTimer {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let bank = "20".to_string();
+        let mut clock = TurnClock::new(props.players.len(), 20 * MINUTE);
+        clock.hand_to(props.first);
+        TurnTimer {
+            link,
+            props,
+            clock,
+            bank,
+            warning: "3".into(),
+            bank_error: None,
+            warning_error: None,
+            _interval: None,
+            last_tick: 0.0,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Start => {
+                self.last_tick = js_sys::Date::now();
+                self.clock.running = true;
+                let callback = self.link.callback(|_| Msg::Tick);
+                self._interval = Some(IntervalService::spawn(TICK, callback));
+            }
+            Msg::Pause => {
+                self.catch_up();
+                self.clock.running = false;
+                self._interval = None;
+            }
+            Msg::Pass => {
+                self.catch_up();
+                self.clock.pass();
+            }
+            Msg::Reset => {
+                self.clock = TurnClock::new(self.props.players.len(), self.bank_millis());
+                self.clock.hand_to(self.props.first);
+                self._interval = None;
+            }
+            Msg::Tick => self.catch_up(),
+            Msg::UpdateBank(val) => {
+                self.bank_error = parse_count(&val).err();
+                self.bank = val;
+            }
+            Msg::UpdateWarning(val) => {
+                self.warning_error = parse_count(&val).err();
+                self.warning = val;
+            }
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if props == self.props {
+            return false;
+        }
+        if props.players.len() != self.props.players.len() {
+            let bank = self.bank_millis();
+            self.clock.resize(props.players.len(), bank);
+        }
+        if props.first != self.props.first {
+            self.clock.hand_to(props.first);
+        }
+        if props.turn > self.props.turn {
+            self.catch_up();
+            self.clock.pass();
+        }
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        let mut class = vec!["turn-timer"];
+        if self.running_long() {
+            class.push("warning");
+        }
+        html! {
+            <div class=class>
+                <label>
+                    { lang.t("time_bank") }
+                    <input type="number" min="0"
+                           value=&self.bank
+                           aria-invalid=self.bank_error.is_some().to_string()
+                           oninput=self.link.callback(|e: InputData| Msg::UpdateBank(e.value)) />
+                </label>
+                <label>
+                    { lang.t("turn_warning") }
+                    <input type="number" min="0"
+                           value=&self.warning
+                           aria-invalid=self.warning_error.is_some().to_string()
+                           oninput=self.link.callback(|e: InputData| Msg::UpdateWarning(e.value)) />
+                </label>
+                { for self.bank_error.or(self.warning_error).map(|error| html! {
+                    <p class="error" role="alert">{ lang.t(error.as_ref()) }</p>
+                }) }
+                <ol class="clocks">
+                    { for self.props.players.iter().enumerate().map(|(idx, player)| {
+                        let mut class = vec!["clock"];
+                        if idx == self.clock.active {
+                            class.push("active");
+                        }
+                        if self.clock.flagged(idx) {
+                            class.push("flagged");
+                        }
+                        let bank = self.clock.banks.get(idx).cloned().unwrap_or(0);
+                        html! {
+                            <li class=class>
+                                <span class="player">{ player }</span>
+                                <span class="bank">{ format_clock(bank) }</span>
+                            </li>
+                        }
+                    }) }
+                </ol>
+                <span class="turn-elapsed" role="timer">
+                    { lang.fmt("turn_elapsed", &[&format_clock(self.clock.turn_elapsed)]) }
+                </span>
+                { if self.clock.running {
+                    html! { <button onclick=self.link.callback(|_| Msg::Pause)>{ lang.t("pause") }</button> }
+                } else {
+                    html! { <button onclick=self.link.callback(|_| Msg::Start)>{ lang.t("start_clock") }</button> }
+                } }
+                <button onclick=self.link.callback(|_| Msg::Pass)>{ lang.t("pass_clock") }</button>
+                <button onclick=self.link.callback(|_| Msg::Reset)>{ lang.t("reset_clock") }</button>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use serde_derive::{Deserialize, Serialize};
+
+/// A sticker from an Unfinity sticker sheet.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Sticker {
+    pub text: String,
+    /// Whether the sticker has been put on a card this game.
+    pub placed: bool,
+}
+
+/// Tickets and stickers, kept apart from the rest of the game state
+/// because they follow their own reset rules.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Unfinity {
+    pub tickets: u32,
+    pub stickers: Vec<Sticker>,
+}
+
+impl Unfinity {
+    /// Starts a new game: tickets are lost and placed stickers come back to
+    /// the sheet, but the sheet itself is kept.
+    pub fn new_game(&mut self) {
+        self.tickets = 0;
+        for sticker in &mut self.stickers {
+            sticker.placed = false;
+        }
+    }
+}
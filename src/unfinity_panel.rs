@@ -0,0 +1,101 @@
+use crate::counter::Counter;
+use crate::i18n::Language;
+use crate::name_form::NameForm;
+use crate::neq_assign::NeqAssign;
+use crate::unfinity::{Sticker, Unfinity};
+use yew::prelude::*;
+
+/// Tickets and the sticker sheet, handing each change back as a whole new
+/// set for the app to keep and save.
+pub struct UnfinityPanel {
+    link: ComponentLink<Self>,
+    props: Props,
+    on_step: Callback<i32>,
+    on_add: Callback<String>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub unfinity: Unfinity,
+    pub on_change: Callback<Unfinity>,
+}
+
+pub enum Msg {
+    StepTickets(i32),
+    AddSticker(String),
+    ToggleSticker(usize),
+    RemoveSticker(usize),
+}
+
+impl Component for UnfinityPanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        UnfinityPanel {
+            on_step: link.callback(Msg::StepTickets),
+            on_add: link.callback(Msg::AddSticker),
+            link,
+            props,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let mut unfinity = self.props.unfinity.clone();
+        match msg {
+            Msg::StepTickets(step) => {
+                unfinity.tickets = (unfinity.tickets as i32 + step).max(0) as u32;
+            }
+            Msg::AddSticker(text) => unfinity.stickers.push(Sticker {
+                text,
+                placed: false,
+            }),
+            Msg::ToggleSticker(idx) => {
+                if let Some(sticker) = unfinity.stickers.get_mut(idx) {
+                    sticker.placed = !sticker.placed;
+                }
+            }
+            Msg::RemoveSticker(idx) => {
+                unfinity.stickers.remove(idx);
+            }
+        }
+        self.props.on_change.emit(unfinity);
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <div class="unfinity">
+                <Counter label=lang.t("tickets")
+                         value=i64::from(self.props.unfinity.tickets)
+                         decrement_label=lang.t("ticket_spend")
+                         increment_label=lang.t("ticket_gain")
+                         on_step=self.on_step.clone() />
+                <ul class="stickers">
+                    { for self.props.unfinity.stickers.iter().enumerate().map(|(idx, sticker)| html! {
+                        <li class=if sticker.placed { "placed" } else { "" }>
+                            <label>
+                                <input type="checkbox"
+                                       checked=sticker.placed
+                                       onclick=self.link.callback(move |_| Msg::ToggleSticker(idx)) />
+                                { &sticker.text }
+                            </label>
+                            <button class="destroy"
+                                    aria-label=lang.t("remove")
+                                    onclick=self.link.callback(move |_| Msg::RemoveSticker(idx))>{ "×" }</button>
+                        </li>
+                    }) }
+                </ul>
+                <NameForm label=lang.t("sticker_text")
+                          button=lang.t("add_sticker")
+                          on_submit=self.on_add.clone() />
+            </div>
+        }
+    }
+}
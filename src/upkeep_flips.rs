@@ -0,0 +1,86 @@
+use crate::card::FlipCard;
+use crate::game::UpkeepFlip;
+use crate::i18n::Language;
+use crate::neq_assign::NeqAssign;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+/// Your permanents that must flip in your upkeep, and whether each has
+/// this turn. Adding or removing one hands back the whole new list.
+pub struct UpkeepFlips {
+    link: ComponentLink<Self>,
+    props: Props,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub language: Language,
+    pub flips: Vec<UpkeepFlip>,
+    pub on_change: Callback<Vec<UpkeepFlip>>,
+}
+
+pub enum Msg {
+    Add(FlipCard),
+    Remove(usize),
+    Nope,
+}
+
+impl Component for UpkeepFlips {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        UpkeepFlips { link, props }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let mut flips = self.props.flips.clone();
+        match msg {
+            Msg::Add(card) => flips.push(UpkeepFlip::new(card)),
+            Msg::Remove(idx) => {
+                flips.remove(idx);
+            }
+            Msg::Nope => return false,
+        }
+        self.props.on_change.emit(flips);
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> Html {
+        let lang = self.props.language;
+        html! {
+            <div class="upkeep-flips">
+                <h2>{ lang.t("panel_upkeep") }</h2>
+                <ul>
+                    { for self.props.flips.iter().enumerate().map(|(idx, upkeep)| html! {
+                        <li class=if upkeep.flipped { "flipped" } else { "due" }>
+                            { upkeep.card }
+                            { " " }
+                            { lang.t(if upkeep.flipped { "upkeep_flipped" } else { "upkeep_due" }) }
+                            <button class="destroy"
+                                    aria-label=lang.t("remove")
+                                    onclick=self.link.callback(move |_| Msg::Remove(idx))>{ "×" }</button>
+                        </li>
+                    }) }
+                </ul>
+                <select aria-label=lang.t("upkeep_add")
+                        onchange=self.link.callback(|e: ChangeData| match e {
+                    ChangeData::Select(select) => FlipCard::from_str(&select.value())
+                        .map(Msg::Add)
+                        .unwrap_or(Msg::Nope),
+                    _ => Msg::Nope,
+                })>
+                    <option value="" selected=true>{ lang.t("upkeep_add") }</option>
+                    { for FlipCard::iter().filter(|card| card.flips_in_upkeep()).map(|card| html! {
+                        <option value=card>{ card }</option>
+                    }) }
+                </select>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Hands-free commands through the browser's speech recognition, so the
+//! app can be driven while shuffling real cards.
+
+use crate::i18n::Language;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{SpeechRecognition, SpeechRecognitionEvent};
+use yew::Callback;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VoiceCommand {
+    /// Flip this many times.
+    Flip(usize),
+    EndTurn,
+    Undo,
+}
+
+/// Words that introduce each command in one language.
+struct Vocabulary {
+    flip: &'static [&'static str],
+    end_turn: &'static [&'static str],
+    undo: &'static [&'static str],
+    /// Numbers from one up, as spoken.
+    numbers: &'static [&'static [&'static str]],
+}
+
+const ENGLISH: Vocabulary = Vocabulary {
+    flip: &["flip"],
+    end_turn: &["end turn", "end my turn", "next turn"],
+    undo: &["undo"],
+    numbers: &[
+        &["one", "once"],
+        &["two", "twice"],
+        &["three", "thrice"],
+        &["four"],
+        &["five"],
+        &["six"],
+        &["seven"],
+        &["eight"],
+        &["nine"],
+        &["ten"],
+    ],
+};
+
+const DEUTSCH: Vocabulary = Vocabulary {
+    flip: &["wirf", "werfen", "münze"],
+    end_turn: &["zug beenden", "zugende", "nächster zug"],
+    undo: &["rückgängig", "zurück"],
+    numbers: &[
+        &["eins", "ein", "einmal"],
+        &["zwei", "zweimal"],
+        &["drei", "dreimal"],
+        &["vier", "viermal"],
+        &["fünf", "fünfmal"],
+        &["sechs", "sechsmal"],
+        &["sieben", "siebenmal"],
+        &["acht", "achtmal"],
+        &["neun", "neunmal"],
+        &["zehn", "zehnmal"],
+    ],
+};
+
+fn vocabulary(language: Language) -> &'static Vocabulary {
+    match language {
+        Language::English => &ENGLISH,
+        Language::Deutsch => &DEUTSCH,
+    }
+}
+
+/// Reads a command out of what the recognizer heard, such as "flip five".
+pub fn parse(transcript: &str, language: Language) -> Option<VoiceCommand> {
+    let words = vocabulary(language);
+    let heard = transcript.trim().to_lowercase();
+    let says = |phrases: &[&str]| phrases.iter().any(|p| heard.contains(p));
+    if says(words.undo) {
+        return Some(VoiceCommand::Undo);
+    }
+    if says(words.end_turn) {
+        return Some(VoiceCommand::EndTurn);
+    }
+    let mut tokens = heard
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty());
+    tokens.find(|token| words.flip.contains(token))?;
+    let times = tokens
+        .find_map(|token| {
+            token.parse().ok().or_else(|| {
+                words
+                    .numbers
+                    .iter()
+                    .position(|spoken| spoken.contains(&token))
+                    .map(|i| i + 1)
+            })
+        })
+        .unwrap_or(1);
+    Some(VoiceCommand::Flip(times))
+}
+
+/// Chrome only ships the prefixed constructor.
+fn recognition() -> Option<SpeechRecognition> {
+    if let Ok(recognition) = SpeechRecognition::new() {
+        return Some(recognition);
+    }
+    let window = web_sys::window()?;
+    let constructor = js_sys::Reflect::get(&window, &"webkitSpeechRecognition".into()).ok()?;
+    let constructor = constructor.dyn_into::<js_sys::Function>().ok()?;
+    let recognition = js_sys::Reflect::construct(&constructor, &js_sys::Array::new()).ok()?;
+    Some(recognition.unchecked_into())
+}
+
+/// Listens until dropped, passing on each final transcript.
+pub struct Listener {
+    recognition: SpeechRecognition,
+    _onresult: Closure<dyn FnMut(SpeechRecognitionEvent)>,
+    _onend: Closure<dyn FnMut()>,
+}
+
+impl Listener {
+    /// Starts listening, or returns `None` if the browser can't. Browsers
+    /// stop on their own after a while, which `on_end` hears about.
+    pub fn start(
+        language: Language,
+        on_heard: Callback<String>,
+        on_end: Callback<()>,
+    ) -> Option<Listener> {
+        let recognition = recognition()?;
+        recognition.set_lang(language.tag());
+        let _ = recognition.set_continuous(true);
+        let onresult = Closure::wrap(Box::new(move |event: SpeechRecognitionEvent| {
+            let results = match event.results() {
+                Some(results) => results,
+                None => return,
+            };
+            for i in event.result_index()..results.length() {
+                let result = results.item(i);
+                if let (true, Some(best)) = (result.is_final(), result.get(0)) {
+                    on_heard.emit(best.transcript());
+                }
+            }
+        }) as Box<dyn FnMut(SpeechRecognitionEvent)>);
+        let onend = Closure::wrap(Box::new(move || on_end.emit(())) as Box<dyn FnMut()>);
+        recognition.set_onresult(Some(onresult.as_ref().unchecked_ref()));
+        recognition.set_onend(Some(onend.as_ref().unchecked_ref()));
+        recognition.start().ok()?;
+        Some(Listener {
+            recognition,
+            _onresult: onresult,
+            _onend: onend,
+        })
+    }
+
+    /// Listens again after the browser stopped on its own.
+    pub fn resume(&self) {
+        let _ = self.recognition.start();
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        self.recognition.set_onresult(None);
+        self.recognition.set_onend(None);
+        self.recognition.abort();
+    }
+}
@@ -30,7 +30,10 @@ module.exports = (env, argv) => {
     },
     plugins: [
       new CopyWebpackPlugin([
-        { from: './static', to: distPath }
+        { from: './static', to: distPath },
+        // The simulation worker loads its own build of the crate.
+        { from: './pkg-worker/worker.js', to: distPath },
+        { from: './pkg-worker/worker_bg.wasm', to: distPath }
       ]),
       new WasmPackPlugin({
         crateDirectory: ".",